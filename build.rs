@@ -0,0 +1,37 @@
+use std::{env, fs, io, path::Path};
+
+fn main() -> io::Result<()> {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let src = Path::new(&manifest_dir).join("source-data/data");
+    let dst = Path::new(&out_dir).join("data");
+
+    println!("cargo:rerun-if-changed=source-data/data");
+
+    let banks_only = env::var_os("CARGO_FEATURE_DATA_BANKS_ONLY").is_some();
+    let major_banks = env::var_os("CARGO_FEATURE_DATA_MAJOR_BANKS").is_some();
+
+    if dst.exists() {
+        fs::remove_dir_all(&dst)?;
+    }
+    fs::create_dir_all(dst.join("branches"))?;
+    fs::copy(src.join("banks.json"), dst.join("banks.json"))?;
+
+    if banks_only {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(src.join("branches"))? {
+        let path = entry?.path();
+        let Some(code) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        // Keep in sync with `is_major_bank_code` in src/lib.rs.
+        if major_banks && !code.starts_with("00") {
+            continue;
+        }
+        fs::copy(&path, dst.join("branches").join(path.file_name().unwrap()))?;
+    }
+
+    Ok(())
+}
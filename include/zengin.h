@@ -0,0 +1,96 @@
+/* The C API of zengin-rs. Generated by cbindgen from src/ffi.rs. */
+
+#ifndef ZENGIN_H
+#define ZENGIN_H
+
+#include <stdint.h>
+
+// The `Zengin` struct represents a collection of banks and their branches.
+//
+// This struct provides methods to load bank and branch data from JSON files,
+// and to retrieve information about banks and branches.
+//
+// It serializes as a single map of bank codes to banks, with each bank
+// carrying its branches in a nested `branches` map.
+typedef struct Zengin Zengin;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Loads the embedded dataset.
+//
+// Returns null if the dataset cannot be loaded. Free the returned handle
+// with [`zengin_free`].
+struct Zengin *zengin_new(void);
+
+// Frees a dataset loaded by [`zengin_new`]. Null is ignored.
+//
+// # Safety
+//
+// `zengin` must be null or a handle returned by [`zengin_new`] that has not
+// been freed yet.
+void zengin_free(struct Zengin *zengin);
+
+// Frees a string returned by this API. Null is ignored.
+//
+// # Safety
+//
+// `s` must be null or a string returned by this API that has not been freed
+// yet.
+void zengin_string_free(char *s);
+
+// Looks up a bank by its code, such as `"0001"`.
+//
+// Returns the bank as a JSON object, or null if there is no bank with the
+// code. Stripped leading zeros are restored, so `"1"` finds bank `0001`.
+//
+// # Safety
+//
+// `zengin` must be a valid handle, and `code` must be null or point to a
+// NUL-terminated string.
+char *zengin_bank(const struct Zengin *zengin, const char *code);
+
+// Looks up a branch by the code of its bank and its own code.
+//
+// Returns the branch as a JSON object, or null if there is no such branch.
+//
+// # Safety
+//
+// `zengin` must be a valid handle, and `bank_code` and `code` must be null
+// or point to NUL-terminated strings.
+char *zengin_branch(const struct Zengin *zengin, const char *bank_code, const char *code);
+
+// Searches banks by a query matched against all of their names.
+//
+// Returns a JSON array of at most `limit` banks, most relevant first, or of
+// all matching banks if `limit` is 0. Returns null if an argument is
+// invalid.
+//
+// # Safety
+//
+// `zengin` must be a valid handle, and `query` must be null or point to a
+// NUL-terminated string.
+char *zengin_search_banks(const struct Zengin *zengin, const char *query, uintptr_t limit);
+
+// Searches the branches of a bank by a query matched against all of their
+// names.
+//
+// Returns a JSON array of at most `limit` branches, most relevant first, or
+// of all matching branches if `limit` is 0. Returns null if there is no bank
+// with the code or an argument is invalid.
+//
+// # Safety
+//
+// `zengin` must be a valid handle, and `bank_code` and `query` must be null
+// or point to NUL-terminated strings.
+char *zengin_search_branches(const struct Zengin *zengin,
+                             const char *bank_code,
+                             const char *query,
+                             uintptr_t limit);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ZENGIN_H */
@@ -0,0 +1,196 @@
+//! N-API bindings of the dataset, built as a Node.js addon.
+//!
+//! The addon exports a `Zengin` class that looks up and searches banks and
+//! branches, which it returns as plain `BankObject` and `BranchObject`
+//! objects. Methods and fields are camelCased, so `get_bank` is `getBank`
+//! and `bank_code` is `bankCode`:
+//!
+//! ```js
+//! const { Zengin } = require("./zengin.node");
+//! const zengin = new Zengin();
+//! console.log(zengin.getBranch("0001", "110").name);
+//! console.log(zengin.searchBanks("みずほ", 5).map((bank) => bank.name));
+//! ```
+//!
+//! Build the addon with `npm run build` in this directory, which runs
+//! `napi build` on this crate and generates `index.js` and its TypeScript
+//! declarations. The crate is a `cdylib` of its own, so that the N-API
+//! symbols, which only Node.js provides, stay out of the binaries of the
+//! `zengin` crate.
+
+use napi_derive::napi;
+use zengin::{Bank, Branch, Record, Zengin};
+
+/// A bank, without its branches.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BankObject {
+    pub code: String,
+    pub name: String,
+    pub kana: String,
+    pub hira: String,
+    pub roma: String,
+}
+
+/// A branch of a bank.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchObject {
+    pub bank_code: String,
+    pub code: String,
+    pub name: String,
+    pub kana: String,
+    pub hira: String,
+    pub roma: String,
+}
+
+impl From<&Bank> for BankObject {
+    fn from(bank: &Bank) -> Self {
+        let [name, kana, hira, roma] = bank.names().map(str::to_string);
+        BankObject {
+            code: bank.code().to_string(),
+            name,
+            kana,
+            hira,
+            roma,
+        }
+    }
+}
+
+impl BranchObject {
+    fn new(bank: &Bank, branch: &Branch) -> Self {
+        let [name, kana, hira, roma] = branch.names().map(str::to_string);
+        BranchObject {
+            bank_code: bank.code().to_string(),
+            code: branch.code().to_string(),
+            name,
+            kana,
+            hira,
+            roma,
+        }
+    }
+}
+
+/// Returns at most `limit` ranked results, or all of them if `limit` is
+/// `None`.
+fn take_ranked<T>(ranked: Vec<(f64, T)>, limit: Option<u32>) -> impl Iterator<Item = T> {
+    ranked
+        .into_iter()
+        .take(limit.map_or(usize::MAX, |limit| limit as usize))
+        .map(|(_, record)| record)
+}
+
+/// The dataset, as the `Zengin` class.
+#[napi(js_name = "Zengin")]
+pub struct JsZengin {
+    zengin: Zengin,
+}
+
+#[napi]
+impl JsZengin {
+    /// Loads the embedded dataset.
+    #[napi(constructor)]
+    pub fn new() -> napi::Result<Self> {
+        let zengin = Zengin::new().map_err(|e| napi::Error::from_reason(e.to_string()))?;
+        Ok(JsZengin { zengin })
+    }
+
+    /// Returns a bank by its code.
+    #[napi]
+    pub fn get_bank(&self, code: String) -> Option<BankObject> {
+        self.zengin.get_bank(code.as_str()).map(BankObject::from)
+    }
+
+    /// Returns a branch by the code of its bank and its own code.
+    #[napi]
+    pub fn get_branch(&self, bank_code: String, code: String) -> Option<BranchObject> {
+        self.zengin
+            .get_branch(bank_code.as_str(), code.as_str())
+            .map(|(bank, branch)| BranchObject::new(bank, branch))
+    }
+
+    /// Returns every bank in code order.
+    #[napi]
+    pub fn banks(&self) -> Vec<BankObject> {
+        self.zengin.iter().map(|(_, bank)| bank.into()).collect()
+    }
+
+    /// Returns the branches of a bank in code order, or none if there is no
+    /// bank with the code.
+    #[napi]
+    pub fn branches(&self, bank_code: String) -> Vec<BranchObject> {
+        self.zengin
+            .get_bank(bank_code.as_str())
+            .map(|bank| {
+                bank.iter()
+                    .map(|(_, branch)| BranchObject::new(bank, branch))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the banks whose names match a regular expression.
+    #[napi]
+    pub fn find_banks_by_name(&self, pattern: String) -> napi::Result<Vec<BankObject>> {
+        let banks = self
+            .zengin
+            .find_banks_by_name(&pattern)
+            .map_err(|e| napi::Error::from_reason(e.to_string()))?;
+        Ok(banks.into_iter().map(BankObject::from).collect())
+    }
+
+    /// Returns the banks matching a query in any of their names, most
+    /// relevant first.
+    #[napi]
+    pub fn search_banks(&self, query: String, limit: Option<u32>) -> Vec<BankObject> {
+        take_ranked(self.zengin.find_banks_ranked(&query), limit)
+            .map(BankObject::from)
+            .collect()
+    }
+
+    /// Returns the branches of a bank matching a query in any of their
+    /// names, most relevant first, or none if there is no bank with the code.
+    #[napi]
+    pub fn search_branches(
+        &self,
+        bank_code: String,
+        query: String,
+        limit: Option<u32>,
+    ) -> Vec<BranchObject> {
+        let Some(bank) = self.zengin.get_bank(bank_code.as_str()) else {
+            return vec![];
+        };
+        take_ranked(bank.find_branches_ranked(&query), limit)
+            .map(|branch| BranchObject::new(bank, branch))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zengin() {
+        let zengin = JsZengin::new().unwrap();
+        let bank = zengin.get_bank("1".to_string()).unwrap();
+        assert_eq!((bank.code.as_str(), bank.name.as_str()), ("0001", "みずほ"));
+        let branch = zengin
+            .get_branch("0001".to_string(), "110".to_string())
+            .unwrap();
+        assert_eq!(
+            (branch.bank_code.as_str(), branch.kana.as_str()),
+            ("0001", "シンジユク")
+        );
+        assert_eq!(zengin.get_bank("9999".to_string()), None);
+
+        assert_eq!(
+            zengin.search_banks("みずほ".to_string(), Some(1)),
+            vec![bank]
+        );
+        let branches = zengin.search_branches("0001".to_string(), "新宿".to_string(), None);
+        assert_eq!(branches[0], branch);
+        assert!(zengin.branches("9999".to_string()).is_empty());
+        assert!(zengin.find_banks_by_name("(".to_string()).is_err());
+    }
+}
@@ -0,0 +1,413 @@
+//! Bank accounts and their numbers.
+//!
+//! Zengin account numbers are up to seven digits long and are written
+//! zero-padded to seven digits in transfer data, so `12345` is `0012345`.
+//! [`BankAccount`] puts them together with the bank, the branch, and the
+//! account holder, and checks all of them against the dataset at once.
+
+use crate::{
+    field::WIDE_NAME_LEN, history::bank_not_found, Bank, BankCode, Branch, BranchCode,
+    ValidationError, Zengin,
+};
+use serde::Serialize;
+use std::{error::Error, fmt, str::FromStr};
+use unicode_normalization::UnicodeNormalization;
+
+/// The number of digits of a Zengin account number.
+pub const ACCOUNT_NUMBER_LEN: usize = 7;
+
+/// An error returned when an account number is malformed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum AccountNumberError {
+    /// The account number is empty.
+    Empty,
+    /// The account number contains a character other than a digit.
+    InvalidCharacter(char),
+    /// The account number has more than seven digits.
+    TooLong(usize),
+}
+
+impl fmt::Display for AccountNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccountNumberError::Empty => write!(f, "account number is empty"),
+            AccountNumberError::InvalidCharacter(c) => {
+                write!(f, "account number contains a non-digit character {c:?}")
+            }
+            AccountNumberError::TooLong(len) => write!(
+                f,
+                "account number has {len} digits, expected at most {ACCOUNT_NUMBER_LEN}"
+            ),
+        }
+    }
+}
+
+impl Error for AccountNumberError {}
+
+/// Validates an account number and zero-pads it to seven digits.
+///
+/// Surrounding whitespace is ignored, and full-width digits such as `１２３`
+/// are accepted as typed by Japanese input methods. Any other character,
+/// including hyphens, is rejected.
+///
+/// # Arguments
+///
+/// * `number` - A string slice that holds the account number.
+///
+/// # Errors
+///
+/// Returns an error if the number is empty, contains a non-digit character,
+/// or has more than seven digits.
+///
+/// # Examples
+/// ```
+/// use zengin::account::normalize_account_number;
+/// assert_eq!(normalize_account_number("12345").unwrap(), "0012345");
+/// assert_eq!(normalize_account_number("１２３４５６７").unwrap(), "1234567");
+/// assert!(normalize_account_number("12345678").is_err());
+/// ```
+pub fn normalize_account_number(number: &str) -> Result<String, AccountNumberError> {
+    let digits = number
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '0'..='9' => Ok(c),
+            '０'..='９' => Ok(char::from(b'0' + (c as u32 - '０' as u32) as u8)),
+            _ => Err(AccountNumberError::InvalidCharacter(c)),
+        })
+        .collect::<Result<String, _>>()?;
+    match digits.len() {
+        0 => Err(AccountNumberError::Empty),
+        len if len > ACCOUNT_NUMBER_LEN => Err(AccountNumberError::TooLong(len)),
+        _ => Ok(format!("{digits:0>ACCOUNT_NUMBER_LEN$}")),
+    }
+}
+
+/// The type of a bank account, 預金種目.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum AccountType {
+    /// 普通預金, an ordinary deposit account, code `1`.
+    Ordinary,
+    /// 当座預金, a checking account, code `2`.
+    Checking,
+    /// 貯蓄預金, a savings account, code `4`.
+    Savings,
+    /// その他, any other account, code `9`.
+    Other,
+}
+
+impl AccountType {
+    /// All account types, in code order.
+    pub const ALL: [AccountType; 4] = [
+        AccountType::Ordinary,
+        AccountType::Checking,
+        AccountType::Savings,
+        AccountType::Other,
+    ];
+
+    /// Returns the numeric code of the account type used in transfer records.
+    pub fn code(self) -> u8 {
+        match self {
+            AccountType::Ordinary => 1,
+            AccountType::Checking => 2,
+            AccountType::Savings => 4,
+            AccountType::Other => 9,
+        }
+    }
+
+    /// Returns the account type with a numeric code, if any.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::account::AccountType;
+    /// assert_eq!(AccountType::from_code(2), Some(AccountType::Checking));
+    /// assert_eq!(AccountType::from_code(3), None);
+    /// ```
+    pub fn from_code(code: u8) -> Option<AccountType> {
+        AccountType::ALL.into_iter().find(|ty| ty.code() == code)
+    }
+
+    /// Returns the Japanese name of the account type, such as `普通`.
+    pub fn name(self) -> &'static str {
+        match self {
+            AccountType::Ordinary => "普通",
+            AccountType::Checking => "当座",
+            AccountType::Savings => "貯蓄",
+            AccountType::Other => "その他",
+        }
+    }
+
+    /// Returns the name of the account type in katakana, such as `フツウ`.
+    pub fn kana(self) -> &'static str {
+        match self {
+            AccountType::Ordinary => "フツウ",
+            AccountType::Checking => "トウザ",
+            AccountType::Savings => "チヨチク",
+            AccountType::Other => "ソノタ",
+        }
+    }
+}
+
+impl fmt::Display for AccountType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// An error returned when parsing an [`AccountType`] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseAccountTypeError(String);
+
+impl fmt::Display for ParseAccountTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown account type {:?}", self.0)
+    }
+}
+
+impl Error for ParseAccountTypeError {}
+
+impl FromStr for AccountType {
+    type Err = ParseAccountTypeError;
+
+    /// Parses an account type from its numeric code, its Japanese name with
+    /// or without `預金`, or its name in katakana.
+    ///
+    /// Full-width and half-width forms are accepted alike, so `１`, `普通預金`,
+    /// `フツウ`, and `ﾌﾂｳ` all parse as [`AccountType::Ordinary`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let folded: String = s.trim().nfkc().collect();
+        let name = folded.strip_suffix("預金").unwrap_or(&folded);
+        AccountType::ALL
+            .into_iter()
+            .find(|ty| {
+                name == ty.code().to_string()
+                    || name == ty.name()
+                    || name == ty.kana()
+                    || name == ty.kana().replace('ヨ', "ョ")
+            })
+            .ok_or_else(|| ParseAccountTypeError(s.to_string()))
+    }
+}
+
+/// Returns whether transfer data can carry a character of an account holder
+/// name.
+///
+/// These are the characters of the Zengin format: upper-case letters, digits,
+/// half-width katakana without the small kana, the voicing marks, the space,
+/// and the symbols `(`, `)`, `.`, `-`, `/`, `,`, `\`, `｢`, and `｣`. Full-width
+/// characters and lower-case letters are rejected, as banks reject them.
+///
+/// # Examples
+/// ```
+/// use zengin::account::is_holder_char;
+/// assert!(is_holder_char('ｶ'));
+/// assert!(!is_holder_char('カ'));
+/// assert!(!is_holder_char('ｯ'));
+/// ```
+pub fn is_holder_char(c: char) -> bool {
+    matches!(c,
+        'A'..='Z' | '0'..='9' | 'ｱ'..='ﾝ' | 'ﾞ' | 'ﾟ'
+        | ' ' | '(' | ')' | '.' | '-' | '/' | ',' | '\\' | '｢' | '｣')
+}
+
+/// Returns the characters of an account holder name that transfer data
+/// cannot carry, as by [`is_holder_char`].
+///
+/// Each offending character is listed once, in order of first appearance, so
+/// the result can be shown to the user as is. An empty result means the name
+/// is valid.
+///
+/// # Arguments
+///
+/// * `name` - A string slice that holds the account holder name.
+///
+/// # Examples
+/// ```
+/// use zengin::account::invalid_holder_chars;
+/// assert!(invalid_holder_chars("ｶ)ﾐｽﾞﾎ").is_empty());
+/// assert_eq!(invalid_holder_chars("ﾔﾏﾀﾞ 太郎"), vec!['太', '郎']);
+/// ```
+pub fn invalid_holder_chars(name: &str) -> Vec<char> {
+    let mut invalid = Vec::new();
+    for c in name.chars().filter(|&c| !is_holder_char(c)) {
+        if !invalid.contains(&c) {
+            invalid.push(c);
+        }
+    }
+    invalid
+}
+
+/// A bank account, as written in transfer data.
+///
+/// The fields are kept as given; [`BankAccount::validate`] checks them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BankAccount {
+    /// The code of the bank.
+    pub bank_code: BankCode,
+    /// The code of the branch.
+    pub branch_code: BranchCode,
+    /// The type of the account.
+    pub account_type: AccountType,
+    /// The account number.
+    pub number: String,
+    /// The name of the account holder in half-width katakana, such as
+    /// `ﾔﾏﾀﾞ ﾀﾛｳ`.
+    pub holder_kana: String,
+}
+
+impl BankAccount {
+    /// Validates the account against a dataset.
+    ///
+    /// This function checks that the bank and the branch exist, that the
+    /// account number is well-formed as by [`normalize_account_number`], and
+    /// that the holder name only contains characters of the Zengin format and
+    /// fits the widest name field, [`WIDE_NAME_LEN`]. Formats with narrower
+    /// fields check their own width when the account is written.
+    ///
+    /// # Arguments
+    ///
+    /// * `zengin` - The dataset to look the bank and branch up in.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValidationError`] describing the first problem found.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{account::{AccountType, BankAccount}, Zengin};
+    /// let zengin = Zengin::new().unwrap();
+    /// let account = BankAccount {
+    ///     bank_code: "0001".parse().unwrap(),
+    ///     branch_code: "001".parse().unwrap(),
+    ///     account_type: AccountType::Ordinary,
+    ///     number: "1234567".to_string(),
+    ///     holder_kana: "ﾔﾏﾀﾞ ﾀﾛｳ".to_string(),
+    /// };
+    /// let (bank, branch) = account.validate(&zengin).unwrap();
+    /// println!("{} {}", bank.name, branch.name);
+    /// ```
+    pub fn validate<'a>(
+        &self,
+        zengin: &'a Zengin,
+    ) -> Result<(&'a Bank, &'a Branch), ValidationError> {
+        let bank = zengin
+            .get_bank(self.bank_code)
+            .ok_or_else(|| bank_not_found(self.bank_code))?;
+        let branch = bank
+            .get_branch(self.branch_code)
+            .ok_or(ValidationError::BranchNotFound {
+                bank: self.bank_code,
+                branch: self.branch_code,
+            })?;
+        normalize_account_number(&self.number).map_err(ValidationError::InvalidAccountNumber)?;
+        let invalid = invalid_holder_chars(&self.holder_kana);
+        if !invalid.is_empty() {
+            return Err(ValidationError::InvalidHolderName(invalid));
+        }
+        let len = self.holder_kana.chars().count();
+        if len > WIDE_NAME_LEN {
+            return Err(ValidationError::HolderNameTooLong(len));
+        }
+        Ok((bank, branch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_account_number() {
+        assert_eq!(normalize_account_number("1").unwrap(), "0000001");
+        assert_eq!(normalize_account_number(" 1234567 ").unwrap(), "1234567");
+        assert_eq!(normalize_account_number("０１２").unwrap(), "0000012");
+        assert_eq!(normalize_account_number(""), Err(AccountNumberError::Empty));
+        assert_eq!(
+            normalize_account_number("123-45"),
+            Err(AccountNumberError::InvalidCharacter('-'))
+        );
+        assert_eq!(
+            normalize_account_number("00000001"),
+            Err(AccountNumberError::TooLong(8))
+        );
+    }
+
+    #[test]
+    fn test_account_type() {
+        for ty in AccountType::ALL {
+            assert_eq!(ty.to_string().parse(), Ok(ty));
+            assert_eq!(ty.code().to_string().parse(), Ok(ty));
+            assert_eq!(AccountType::from_code(ty.code()), Some(ty));
+        }
+        assert_eq!("普通預金".parse(), Ok(AccountType::Ordinary));
+        assert_eq!("ﾄｳｻﾞ".parse(), Ok(AccountType::Checking));
+        assert_eq!("チョチク".parse(), Ok(AccountType::Savings));
+        assert!("定期".parse::<AccountType>().is_err());
+    }
+
+    #[test]
+    fn test_validate_bank_account() {
+        let zengin = crate::fixture();
+        let account = BankAccount {
+            bank_code: "0001".parse().unwrap(),
+            branch_code: "110".parse().unwrap(),
+            account_type: AccountType::Ordinary,
+            number: "12345".to_string(),
+            holder_kana: "ｶ)ﾐｽﾞﾎ ﾀﾛｳ".to_string(),
+        };
+        let (bank, branch) = account.validate(&zengin).unwrap();
+        assert_eq!((bank.code.as_str(), branch.code.as_str()), ("0001", "110"));
+
+        let invalid = |account: BankAccount| account.validate(&zengin).unwrap_err();
+        assert_eq!(
+            invalid(BankAccount {
+                branch_code: "999".parse().unwrap(),
+                ..account.clone()
+            }),
+            ValidationError::BranchNotFound {
+                bank: account.bank_code,
+                branch: "999".parse().unwrap(),
+            }
+        );
+        assert_eq!(
+            invalid(BankAccount {
+                number: "12345678".to_string(),
+                ..account.clone()
+            }),
+            ValidationError::InvalidAccountNumber(AccountNumberError::TooLong(8))
+        );
+        assert_eq!(
+            invalid(BankAccount {
+                holder_kana: "ﾔﾏﾀﾞ ｮｼｺ山".to_string(),
+                ..account.clone()
+            }),
+            ValidationError::InvalidHolderName(vec!['ｮ', '山'])
+        );
+        assert!(BankAccount {
+            holder_kana: "ｱ".repeat(WIDE_NAME_LEN),
+            ..account.clone()
+        }
+        .validate(&zengin)
+        .is_ok());
+        assert_eq!(
+            invalid(BankAccount {
+                holder_kana: "ｱ".repeat(WIDE_NAME_LEN + 1),
+                ..account
+            }),
+            ValidationError::HolderNameTooLong(WIDE_NAME_LEN + 1)
+        );
+    }
+
+    #[test]
+    fn test_invalid_holder_chars() {
+        assert!(invalid_holder_chars("ﾊﾟﾝ ｶ)ﾒ.-/(ABC123)").is_empty());
+        assert_eq!(invalid_holder_chars("ﾔﾏﾀﾞ ﾀﾛｰ"), vec!['ｰ']);
+        assert_eq!(
+            invalid_holder_chars("yamada　ﾀﾛｳ"),
+            vec!['y', 'a', 'm', 'd', '　']
+        );
+    }
+}
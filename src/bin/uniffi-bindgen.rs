@@ -0,0 +1,6 @@
+//! Generates the Swift and Kotlin sources of the UniFFI bindings of
+//! `zengin::mobile` from a build of the library.
+
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
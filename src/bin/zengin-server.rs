@@ -0,0 +1,16 @@
+//! Serves the REST API of `zengin::server` over the embedded dataset.
+//!
+//! The server listens on the address of the `ZENGIN_ADDR` environment
+//! variable, or on `0.0.0.0:3000` if it is not set.
+
+use std::{env, sync::Arc};
+use zengin::{server, Zengin};
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let zengin = Zengin::new().map_err(std::io::Error::other)?;
+    let addr = env::var("ZENGIN_ADDR").unwrap_or_else(|_| "0.0.0.0:3000".to_string());
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    eprintln!("listening on {}", listener.local_addr()?);
+    axum::serve(listener, server::router(Arc::new(zengin))).await
+}
@@ -0,0 +1,54 @@
+//! The `completions` command.
+
+use clap::{builder::PossibleValue, Args, Command};
+use clap_complete::{Generator, Shell};
+use std::io::{self, Write};
+use zengin::Zengin;
+
+#[derive(Debug, Args)]
+pub struct CompletionsArgs {
+    /// The shell to generate the completions for.
+    shell: Shell,
+}
+
+/// Returns the bank codes of the dataset as possible values, with the bank
+/// names as their help, which zsh and fish show next to the codes.
+fn bank_codes(zengin: &Zengin) -> Vec<PossibleValue> {
+    zengin
+        .iter()
+        .map(|(code, bank)| PossibleValue::new(code.to_string()).help(bank.name.to_string()))
+        .collect()
+}
+
+/// Returns the command with the bank codes of the dataset as the values of
+/// the bank code arguments, for the completion script only, so that the
+/// commands still accept codes such as `1`.
+pub fn with_bank_codes(command: Command, zengin: &Zengin) -> Command {
+    let codes = bank_codes(zengin);
+    command
+        .mut_subcommand("bank", |bank| {
+            bank.mut_arg("code", |arg| arg.value_parser(codes.clone()))
+        })
+        .mut_subcommand("branch", |branch| {
+            branch.mut_arg("bank_code", |arg| arg.value_parser(codes.clone()))
+        })
+        .mut_subcommand("validate", |validate| {
+            validate.mut_arg("bank_code", |arg| arg.value_parser(codes.clone()))
+        })
+        .mut_subcommand("pick", |pick| {
+            pick.mut_arg("bank", |arg| arg.value_parser(codes.clone()))
+        })
+}
+
+/// Writes the completion script of a shell.
+pub fn run(
+    command: Command,
+    zengin: &Zengin,
+    args: &CompletionsArgs,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let mut command = with_bank_codes(command, zengin);
+    command.set_bin_name(command.get_name().to_string());
+    command.build();
+    args.shell.try_generate(&command, out)
+}
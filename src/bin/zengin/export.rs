@@ -0,0 +1,147 @@
+//! The `export` command.
+
+use crate::output::{self, Output};
+use clap::{Args, ValueEnum};
+use serde_json::{json, Map};
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+use zengin::{
+    export::{Column, ExportOptions, SqlDialect},
+    Record, Zengin,
+};
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// The format to export the dataset in. With --output instead, the
+    /// dataset is exported as rows of banks and branches.
+    #[arg(
+        long,
+        value_enum,
+        required_unless_present = "output",
+        conflicts_with = "output"
+    )]
+    format: Option<Format>,
+    /// Export the banks without their branches.
+    #[arg(long)]
+    banks_only: bool,
+    /// The database that an SQL export is for.
+    #[arg(long, value_enum, default_value = "postgres")]
+    dialect: Dialect,
+    /// The file to write to, instead of stdout.
+    #[arg(long, value_name = "PATH")]
+    out: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    Csv,
+    Json,
+    Yaml,
+    Sql,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Dialect {
+    Postgres,
+    Mysql,
+    Sqlite,
+}
+
+impl From<Dialect> for SqlDialect {
+    fn from(dialect: Dialect) -> Self {
+        match dialect {
+            Dialect::Postgres => SqlDialect::Postgres,
+            Dialect::Mysql => SqlDialect::MySql,
+            Dialect::Sqlite => SqlDialect::Sqlite,
+        }
+    }
+}
+
+/// Returns a copy of the dataset without any branches.
+fn banks_only(zengin: &Zengin) -> Result<Zengin, zengin::ZenginError> {
+    let banks: Map<_, _> = zengin
+        .iter()
+        .map(|(code, bank)| {
+            let [name, kana, hira, roma] = bank.names();
+            let bank =
+                json!({ "code": code, "name": name, "kana": kana, "hira": hira, "roma": roma });
+            (code.to_string(), bank)
+        })
+        .collect();
+    Zengin::from_combined_json(&serde_json::Value::Object(banks).to_string())
+}
+
+/// Writes the rows of the dataset, one per branch, in an output mode.
+fn write_rows(
+    zengin: &Zengin,
+    output: Output,
+    columns: &[Column],
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let mut objects = vec![];
+    for (_, bank) in zengin {
+        let mut branches: Vec<_> = bank.iter().map(|(_, branch)| Some(branch)).collect();
+        if branches.is_empty() {
+            branches.push(None);
+        }
+        for branch in branches {
+            objects.push(output::row(bank, branch, columns));
+        }
+    }
+    let keys: Vec<_> = columns.iter().map(|column| column.name()).collect();
+    output::write_objects(out, output, &keys, objects, false)
+}
+
+/// Writes the dataset in the format of the arguments, or as rows in an
+/// output mode, to the file of the arguments or to `out`.
+pub fn export(
+    zengin: &Zengin,
+    args: &ExportArgs,
+    output: Option<Output>,
+    out: &mut dyn Write,
+) -> Result<(), Box<dyn Error>> {
+    let mut file;
+    let out: &mut dyn Write = match &args.out {
+        Some(path) => {
+            file = BufWriter::new(File::create(path)?);
+            &mut file
+        }
+        None => out,
+    };
+    let banks;
+    let zengin = if args.banks_only {
+        banks = banks_only(zengin)?;
+        &banks
+    } else {
+        zengin
+    };
+    let columns = if args.banks_only {
+        &Column::ALL[..5]
+    } else {
+        &Column::ALL[..]
+    };
+    match (args.format, output) {
+        (Some(Format::Csv), _) => {
+            let options = ExportOptions {
+                columns: columns.to_vec(),
+                ..Default::default()
+            };
+            zengin.export_csv(&mut *out, options)?;
+        }
+        (Some(Format::Json), _) => writeln!(out, "{}", zengin.to_json()?)?,
+        (Some(Format::Yaml), _) if args.banks_only => {
+            out.write_all(zengin.banks_yaml().as_bytes())?
+        }
+        (Some(Format::Yaml), _) => out.write_all(zengin.to_yaml().as_bytes())?,
+        (Some(Format::Sql), _) => out.write_all(zengin.to_sql(args.dialect.into()).as_bytes())?,
+        (None, Some(output)) => write_rows(zengin, output, columns, out)?,
+        // clap requires --format or --output.
+        (None, None) => unreachable!(),
+    }
+    out.flush()?;
+    Ok(())
+}
@@ -0,0 +1,405 @@
+//! The `file` commands, which read and write Zengin-format transfer files.
+
+use crate::{
+    output::{self, Output},
+    validate::Type,
+};
+use clap::{Args, Subcommand, ValueEnum};
+use encoding_rs::SHIFT_JIS;
+use serde_json::{Map, Value};
+use std::{
+    error::Error,
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+use zengin::{
+    account::BankAccount,
+    format::{
+        DataRecord, FormatError, Newline, PaymentRow, RecordDate, TransferFile, TransferKind,
+    },
+    normalize::to_holder_kana,
+    Zengin,
+};
+
+#[derive(Debug, Args)]
+pub struct FileArgs {
+    #[command(subcommand)]
+    command: FileCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum FileCommand {
+    /// Print the header, the transfers, and the totals of a transfer file.
+    Parse(InputArgs),
+    /// Check a transfer file: the layout of its records, the banks and
+    /// branches it names against the dataset, and its totals.
+    ///
+    /// Exits with status 1 if the file has any problem, printing every one
+    /// of them.
+    Validate(InputArgs),
+    /// Build a 総合振込 or payroll transfer file from payment rows.
+    Build(BuildArgs),
+}
+
+#[derive(Debug, Args)]
+struct InputArgs {
+    /// The transfer file, or - for stdin.
+    path: PathBuf,
+    /// The encoding of the file. Files that are not UTF-8 are read as
+    /// Shift_JIS by default.
+    #[arg(long, value_enum)]
+    encoding: Option<Encoding>,
+}
+
+#[derive(Debug, Args)]
+struct BuildArgs {
+    /// The format of the payment rows.
+    #[arg(long, value_enum, default_value = "csv")]
+    from: Source,
+    /// The payment rows, or - for stdin, in UTF-8 or Shift_JIS. Each CSV line
+    /// has the bank code, branch code, account type, account number, holder
+    /// name, and amount, and a header line is skipped.
+    path: PathBuf,
+    /// The kind of the file.
+    #[arg(long, value_enum, default_value = "general")]
+    kind: Kind,
+    /// The requester code that the source bank assigned, up to ten digits.
+    #[arg(long, value_name = "CODE")]
+    requester_code: String,
+    /// The requester name, in katakana or hiragana. Small kana and long vowel
+    /// marks are converted as in the holder names of the rows.
+    #[arg(long, value_name = "KANA")]
+    requester_name: String,
+    /// The transfer date, as MMDD.
+    #[arg(long, value_parser = parse_date)]
+    date: RecordDate,
+    /// The code of the bank the transfers are paid from.
+    #[arg(long, value_name = "CODE")]
+    source_bank: String,
+    /// The code of the branch the transfers are paid from.
+    #[arg(long, value_name = "CODE")]
+    source_branch: String,
+    /// The number of the account the transfers are paid from.
+    #[arg(long, value_name = "NUMBER")]
+    source_account: String,
+    /// The type of the account the transfers are paid from.
+    #[arg(long, value_enum, default_value = "ordinary")]
+    source_type: Type,
+    /// The encoding of the file. Banks expect Shift_JIS.
+    #[arg(long, value_enum, default_value = "sjis")]
+    encoding: Encoding,
+    /// The separator written after each record.
+    #[arg(long, value_enum, default_value = "crlf")]
+    newline: NewlineArg,
+    /// The file to write to, instead of stdout.
+    #[arg(long, value_name = "PATH")]
+    out: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Encoding {
+    Utf8,
+    #[value(alias = "shift_jis", alias = "cp932")]
+    Sjis,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Source {
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Kind {
+    General,
+    Salary,
+    Bonus,
+}
+
+impl From<Kind> for TransferKind {
+    fn from(kind: Kind) -> Self {
+        match kind {
+            Kind::General => TransferKind::General,
+            Kind::Salary => TransferKind::Salary,
+            Kind::Bonus => TransferKind::Bonus,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum NewlineArg {
+    Crlf,
+    Lf,
+    None,
+}
+
+impl From<NewlineArg> for Newline {
+    fn from(newline: NewlineArg) -> Self {
+        match newline {
+            NewlineArg::Crlf => Newline::CrLf,
+            NewlineArg::Lf => Newline::Lf,
+            NewlineArg::None => Newline::None,
+        }
+    }
+}
+
+/// Parses a transfer date given as MMDD.
+fn parse_date(s: &str) -> Result<RecordDate, String> {
+    s.parse()
+        .map_err(|()| format!("expected a date as MMDD, got {s:?}"))
+}
+
+/// Reads a file, or stdin for `-`.
+fn read(path: &Path) -> io::Result<Vec<u8>> {
+    if path == Path::new("-") {
+        let mut bytes = vec![];
+        io::stdin().read_to_end(&mut bytes)?;
+        return Ok(bytes);
+    }
+    fs::read(path)
+}
+
+/// Decodes text in an encoding, or in UTF-8 if it is valid UTF-8 and in
+/// Shift_JIS otherwise. A UTF-8 byte order mark, as spreadsheets write, is
+/// dropped. Returns `None` if the text is not valid in the encoding.
+fn decode(bytes: &[u8], encoding: Option<Encoding>) -> Option<String> {
+    let utf8 = std::str::from_utf8(bytes);
+    match (encoding, utf8) {
+        (Some(Encoding::Utf8) | None, Ok(text)) => {
+            Some(text.strip_prefix('\u{feff}').unwrap_or(text).to_string())
+        }
+        (Some(Encoding::Utf8), Err(_)) => None,
+        (Some(Encoding::Sjis) | None, _) => SHIFT_JIS
+            .decode_without_bom_handling_and_without_replacement(bytes)
+            .map(String::from),
+    }
+}
+
+/// Reads and parses a transfer file, with every error found in it.
+fn parse(args: &InputArgs) -> io::Result<Result<TransferFile, Vec<FormatError>>> {
+    let bytes = read(&args.path)?;
+    let Some(text) = decode(&bytes, args.encoding) else {
+        if let Some(Encoding::Utf8) = args.encoding {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the file is not valid UTF-8",
+            ));
+        }
+        // Decoding the records one by one finds the line that is not
+        // Shift_JIS.
+        return Ok(TransferFile::parse_shift_jis(&bytes).map_err(|e| vec![e]));
+    };
+    Ok(TransferFile::parse_all(&text))
+}
+
+/// The keys of [`data_object`], in order.
+const DATA_KEYS: [&str; 12] = [
+    "line",
+    "bank_code",
+    "bank_name",
+    "branch_code",
+    "branch_name",
+    "account_type",
+    "account_number",
+    "holder_name",
+    "amount",
+    "customer_code1",
+    "customer_code2",
+    "result_code",
+];
+
+/// Returns the object of a data record, with the line it is on. The names
+/// and codes have their padding trimmed.
+fn data_object(record: &DataRecord, line: usize) -> Map<String, Value> {
+    let values: [Value; 12] = [
+        line.into(),
+        record.bank_code.to_string().into(),
+        record.bank_name.trim_end().into(),
+        record.branch_code.to_string().into(),
+        record.branch_name.trim_end().into(),
+        record.account_type.name().into(),
+        record.account_number.as_str().into(),
+        record.holder_name.trim_end().into(),
+        record.amount.into(),
+        record.customer_code1.trim_end().into(),
+        record.customer_code2.trim_end().into(),
+        record
+            .result_code
+            .map(|code| code.code().to_string())
+            .into(),
+    ];
+    DATA_KEYS
+        .iter()
+        .map(|key| key.to_string())
+        .zip(values)
+        .collect()
+}
+
+/// Writes the header, the data records, and the totals of a file.
+fn write_file(out: &mut impl Write, file: &TransferFile) -> io::Result<()> {
+    let header = &file.header;
+    writeln!(out, "kind: {} ({})", header.kind, header.kind.code())?;
+    writeln!(
+        out,
+        "requester: {} {}",
+        header.requester_code,
+        header.requester_name.trim_end()
+    )?;
+    writeln!(out, "date: {}", header.date)?;
+    writeln!(
+        out,
+        "source: {} {} {} {} {}",
+        header.bank_code,
+        header.branch_code,
+        header.account_type,
+        header.account_number,
+        header.bank_name.trim_end()
+    )?;
+    for (record, line) in file.data.iter().zip(file.data_lines()) {
+        writeln!(
+            out,
+            "{line}: {} {} {} {} {} {}",
+            record.bank_code,
+            record.branch_code,
+            record.account_type,
+            record.account_number,
+            record.holder_name.trim_end(),
+            record.amount
+        )?;
+    }
+    writeln!(
+        out,
+        "total: {} records, {} yen",
+        file.trailer.count, file.trailer.total
+    )
+}
+
+/// Parses a file and writes its records.
+fn run_parse(args: &InputArgs, output: Option<Output>, out: &mut impl Write) -> io::Result<bool> {
+    let file = match parse(args)? {
+        Ok(file) => file,
+        Err(errors) => {
+            for error in errors {
+                eprintln!("zengin: {error}");
+            }
+            return Ok(false);
+        }
+    };
+    match output {
+        Some(output) => {
+            let lines = file.data_lines();
+            let objects = file.data.iter().zip(lines).map(|(r, l)| data_object(r, l));
+            output::write_objects(out, output, &DATA_KEYS, objects.collect(), false)?;
+        }
+        None => write_file(out, &file)?,
+    }
+    Ok(true)
+}
+
+/// Parses a file and checks it against the dataset, writing every problem
+/// found, or a summary of the file if there is none.
+fn run_validate(
+    zengin: &Zengin,
+    args: &InputArgs,
+    output: Option<Output>,
+    out: &mut impl Write,
+) -> io::Result<bool> {
+    // The problems, as their line, their kind, and their message.
+    let problems: Vec<(Option<usize>, &str, String)> = match parse(args)? {
+        Ok(file) => zengin
+            .validate_transfer_file(&file)
+            .issues
+            .iter()
+            .map(|issue| (issue.line(), "dataset", issue.to_string()))
+            .collect(),
+        Err(errors) => errors
+            .iter()
+            .map(|error| (error.line(), "format", error.to_string()))
+            .collect(),
+    };
+    match output {
+        Some(output) => {
+            let objects = problems
+                .iter()
+                .map(|(line, kind, message)| {
+                    let mut object = Map::new();
+                    object.insert("line".to_string(), (*line).into());
+                    object.insert("kind".to_string(), (*kind).into());
+                    object.insert("message".to_string(), message.as_str().into());
+                    object
+                })
+                .collect();
+            output::write_objects(out, output, &["line", "kind", "message"], objects, false)?;
+        }
+        None if problems.is_empty() => writeln!(out, "ok: {}", args.path.display())?,
+        None => {
+            for (_, _, message) in &problems {
+                writeln!(out, "invalid: {message}")?;
+            }
+        }
+    }
+    Ok(problems.is_empty())
+}
+
+/// Builds a file from payment rows, writing it to the path of `--out` or to
+/// `out`.
+fn run_build(
+    zengin: &Zengin,
+    args: &BuildArgs,
+    out: &mut impl Write,
+) -> Result<bool, Box<dyn Error>> {
+    let bytes = read(&args.path)?;
+    let text = decode(&bytes, None).ok_or("the payment rows are neither UTF-8 nor Shift_JIS")?;
+    let rows = match args.from {
+        Source::Csv => PaymentRow::parse_csv_all(&text),
+    };
+    let rows = match rows {
+        Ok(rows) => rows,
+        Err(errors) => {
+            for error in errors {
+                eprintln!("zengin: {error}");
+            }
+            return Ok(false);
+        }
+    };
+    let source = BankAccount {
+        bank_code: args.source_bank.parse()?,
+        branch_code: args.source_branch.parse()?,
+        account_type: args.source_type.into(),
+        number: args.source_account.clone(),
+        holder_kana: to_holder_kana(&args.requester_name),
+    };
+    let mut file = zengin
+        .transfer_file()
+        .kind(args.kind.into())
+        .requester_code(&args.requester_code)
+        .transfer_date(args.date.month(), args.date.day())
+        .source_account(source)
+        .rows(rows)
+        .build_file()?;
+    file.newline = args.newline.into();
+    file.final_newline = true;
+    let bytes = match args.encoding {
+        Encoding::Utf8 => file.to_text(file.newline)?.into_bytes(),
+        Encoding::Sjis => file.to_shift_jis(file.newline)?,
+    };
+    match &args.out {
+        Some(path) => fs::write(path, bytes)?,
+        None => out.write_all(&bytes)?,
+    }
+    Ok(true)
+}
+
+/// Runs a `file` command. Returns whether it succeeded.
+pub fn run(
+    zengin: &Zengin,
+    args: &FileArgs,
+    output: Option<Output>,
+    out: &mut impl Write,
+) -> Result<bool, Box<dyn Error>> {
+    match &args.command {
+        FileCommand::Parse(args) => Ok(run_parse(args, output, out)?),
+        FileCommand::Validate(args) => Ok(run_validate(zengin, args, output, out)?),
+        FileCommand::Build(args) => run_build(zengin, args, out),
+    }
+}
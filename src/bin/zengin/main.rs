@@ -0,0 +1,451 @@
+//! The `zengin` command, which looks up banks and branches of the embedded
+//! dataset from the terminal.
+//!
+//! ```sh
+//! zengin bank 0001
+//! zengin branch 0001 001 --json
+//! zengin search --fuzzy みづほ
+//! zengin export --format csv --out zengin.csv
+//! zengin validate 0001 001 --account 1234567 --kana ﾔﾏﾀﾞﾀﾛｳ --json
+//! zengin pick
+//! zengin completions zsh > ~/.zfunc/_zengin
+//! zengin search 新宿 --output ndjson | jq .bank_name
+//! zengin file build --from csv payments.csv --requester-code 1234567890 \
+//!     --requester-name ｻﾝﾌﾟﾙ --date 0425 --source-bank 0001 --source-branch 001 \
+//!     --source-account 1234567 --out transfers.txt
+//! zengin file validate transfers.txt
+//! ```
+
+mod completions;
+mod export;
+mod file;
+mod output;
+mod pick;
+mod search;
+mod validate;
+
+use clap::{CommandFactory, Parser, Subcommand};
+use completions::CompletionsArgs;
+use export::ExportArgs;
+use file::FileArgs;
+use output::Output;
+use pick::PickArgs;
+use search::SearchArgs;
+use std::{
+    error::Error,
+    io::{self, Write},
+    process::ExitCode,
+};
+use validate::ValidateArgs;
+use zengin::{Record, Zengin};
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Look up Japanese bank and branch codes")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Print the results in a machine-readable format, with the same keys
+    /// for every command. Completion scripts and built transfer files are
+    /// not affected.
+    #[arg(long, global = true, value_enum)]
+    output: Option<Output>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print a bank by its code.
+    Bank {
+        /// The bank code, such as 0001.
+        code: String,
+        /// Print the bank as JSON, as --output json does.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print a branch by the code of its bank and its own code.
+    Branch {
+        /// The bank code, such as 0001.
+        bank_code: String,
+        /// The branch code, such as 001.
+        code: String,
+        /// Print the branch as JSON, as --output json does.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the banks and branches matching a query.
+    ///
+    /// Exits with status 1 if nothing matches, as grep does.
+    Search(SearchArgs),
+    /// Write the dataset to stdout or a file.
+    Export(ExportArgs),
+    /// Check that a bank and branch exist, and optionally that an account
+    /// is well-formed.
+    ///
+    /// Exits with status 1 if the check fails, printing the reason, which is
+    /// the variant of zengin::ValidationError in the "reason" field of the
+    /// machine-readable output.
+    Validate(ValidateArgs),
+    /// Pick a bank, then one of its branches, with a fuzzy filter in the
+    /// terminal, and print their codes.
+    ///
+    /// The picker is drawn on stderr, so the codes can be captured with
+    /// $(zengin pick). Exits with status 1 if the picker is cancelled with
+    /// Esc.
+    Pick(PickArgs),
+    /// Print the completion script of a shell.
+    ///
+    /// The script completes the bank codes of the dataset, with the bank
+    /// names as their descriptions in zsh and fish, and has to be generated
+    /// again when the dataset changes.
+    Completions(CompletionsArgs),
+    /// Parse, validate, or build Zengin-format transfer files, in Shift_JIS
+    /// or UTF-8.
+    File(FileArgs),
+}
+
+/// Writes the fields of a bank or branch, one per line.
+fn write_record(out: &mut impl Write, record: &impl Record) -> io::Result<()> {
+    let [name, kana, hira, roma] = record.names();
+    writeln!(out, "code: {}", record.code())?;
+    writeln!(out, "name: {name}")?;
+    writeln!(out, "kana: {kana}")?;
+    writeln!(out, "hira: {hira}")?;
+    writeln!(out, "roma: {roma}")
+}
+
+/// Runs a command, writing its output to `out` in a machine-readable format
+/// if `output` is set. Returns whether the command succeeded; failures are
+/// reported to stderr.
+fn run(
+    command: Command,
+    output: Option<Output>,
+    zengin: &Zengin,
+    out: &mut impl Write,
+) -> Result<bool, Box<dyn Error>> {
+    match command {
+        Command::Bank { code, json } => {
+            let Some(bank) = zengin.get_bank(code.as_str()) else {
+                eprintln!("zengin: bank {code} not found");
+                return Ok(false);
+            };
+            match output.or(json.then_some(Output::Json)) {
+                Some(output) => output::write_rows(out, output, &[(bank, None)], true)?,
+                None => write_record(out, bank)?,
+            }
+        }
+        Command::Branch {
+            bank_code,
+            code,
+            json,
+        } => {
+            let Some(bank) = zengin.get_bank(bank_code.as_str()) else {
+                eprintln!("zengin: bank {bank_code} not found");
+                return Ok(false);
+            };
+            let Some(branch) = bank.get_branch(code.as_str()) else {
+                eprintln!("zengin: branch {code} not found for bank {}", bank.code());
+                return Ok(false);
+            };
+            match output.or(json.then_some(Output::Json)) {
+                Some(output) => output::write_rows(out, output, &[(bank, Some(branch))], true)?,
+                None => {
+                    writeln!(out, "bank: {} {}", bank.code(), bank.name)?;
+                    write_record(out, branch)?;
+                }
+            }
+        }
+        Command::Search(args) => {
+            let hits = search::search(zengin, &args)?;
+            match output {
+                Some(output) => output::write_rows(out, output, &hits, false)?,
+                None => search::write_hits(out, &hits)?,
+            }
+            return Ok(!hits.is_empty());
+        }
+        Command::Export(args) => export::export(zengin, &args, output, out)?,
+        Command::Validate(args) => {
+            return Ok(validate::run(zengin, &args, output, out)?);
+        }
+        Command::Pick(args) => {
+            let Some((bank, branch)) = pick::run(zengin, &args)? else {
+                return Ok(false);
+            };
+            match (output, branch) {
+                (Some(output), _) => output::write_rows(out, output, &[(bank, branch)], true)?,
+                (None, Some(branch)) => writeln!(out, "{} {}", bank.code(), branch.code())?,
+                (None, None) => writeln!(out, "{}", bank.code())?,
+            }
+        }
+        Command::Completions(args) => completions::run(Cli::command(), zengin, &args, out)?,
+        Command::File(args) => return file::run(zengin, &args, output, out),
+    }
+    Ok(true)
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = Zengin::new()
+        .map_err(Box::from)
+        .and_then(|zengin| run(cli.command, cli.output, &zengin, &mut io::stdout().lock()));
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("zengin: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    /// The dataset of the tests, which does not depend on the data features.
+    fn fixture() -> Zengin {
+        Zengin::from_combined_json(include_str!("../../fixture.json")).unwrap()
+    }
+
+    /// Runs the command of a command line, returning whether it succeeded
+    /// and its output.
+    fn run_args(args: &[&str]) -> (bool, String) {
+        let cli = Cli::try_parse_from([&["zengin"], args].concat()).unwrap();
+        let mut out = vec![];
+        let ok = run(cli.command, cli.output, &fixture(), &mut out).unwrap();
+        (ok, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_lookup() {
+        let (ok, out) = run_args(&["bank", "1"]);
+        assert!(ok);
+        assert_eq!(
+            out,
+            "code: 0001\nname: みずほ\nkana: ミズホ\nhira: みずほ\nroma: mizuho\n"
+        );
+
+        let (ok, out) = run_args(&["branch", "0001", "110", "--json"]);
+        assert!(ok);
+        let value: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value["branch_name"], "新宿");
+        assert_eq!(value["bank_code"], "0001");
+
+        assert_eq!(run_args(&["bank", "9999"]), (false, String::new()));
+        assert_eq!(run_args(&["branch", "0001", "999"]), (false, String::new()));
+    }
+
+    #[test]
+    fn test_export() {
+        let (ok, out) = run_args(&["export", "--format", "csv", "--banks-only"]);
+        assert!(ok);
+        let mut lines = out.lines();
+        assert_eq!(
+            lines.next(),
+            Some("bank_code,bank_name,bank_kana,bank_hira,bank_roma")
+        );
+        assert_eq!(lines.next(), Some("0001,みずほ,ミズホ,みずほ,mizuho"));
+        assert_eq!(lines.count(), fixture().all_banks().len() - 1);
+
+        let (_, out) = run_args(&["export", "--format", "json"]);
+        let zengin = Zengin::from_combined_json(&out).unwrap();
+        assert_eq!(zengin["0001"]["110"].name, "新宿");
+
+        let (_, out) = run_args(&[
+            "export",
+            "--format",
+            "sql",
+            "--dialect",
+            "mysql",
+            "--banks-only",
+        ]);
+        assert!(out.contains("START TRANSACTION;"));
+        assert!(!out.contains("INSERT INTO branches"));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(
+            run_args(&["validate", "0001", "110"]),
+            (true, "ok: 0001 110 みずほ 新宿\n".to_string())
+        );
+
+        let (ok, out) = run_args(&["validate", "0001", "999", "--json"]);
+        assert!(!ok);
+        let value: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value["valid"], false);
+        assert_eq!(value["reason"], "BranchNotFound");
+        assert_eq!(value["error"]["BranchNotFound"]["branch"], "999");
+
+        let args = ["validate", "0001", "110", "--account", "1234567", "--kana"];
+        let (ok, out) = run_args(&[&args[..], &["ﾔﾏﾀﾞﾀﾛｳ", "--json"]].concat());
+        assert!(ok);
+        assert_eq!(serde_json::from_str::<Value>(&out).unwrap()["valid"], true);
+        let (ok, out) = run_args(&[&args[..], &["山田", "--json"]].concat());
+        assert!(!ok);
+        assert_eq!(
+            serde_json::from_str::<Value>(&out).unwrap()["reason"],
+            "InvalidHolderName"
+        );
+
+        let (ok, out) = run_args(&["validate", "1", "110", "--account", "12-34"]);
+        assert!(!ok);
+        assert!(out.starts_with("invalid: "));
+        assert!(
+            Cli::try_parse_from(["zengin", "validate", "0001", "110", "--kana", "ﾀﾛｳ"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_output() {
+        let (_, out) = run_args(&["bank", "0001", "--output", "json"]);
+        let value: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value["bank_roma"], "mizuho");
+        assert_eq!(value["branch_code"], Value::Null);
+
+        let (_, out) = run_args(&["--output", "tsv", "search", "新宿"]);
+        let mut lines = out.lines();
+        assert_eq!(
+            lines.next(),
+            Some("bank_code\tbank_name\tbank_kana\tbank_hira\tbank_roma\tbranch_code\tbranch_name\tbranch_kana\tbranch_hira\tbranch_roma")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("0001\tみずほ\tミズホ\tみずほ\tmizuho\t110\t新宿\tシンジユク\tしんじゆく\tshinjiyuku")
+        );
+        let (_, out) = run_args(&["search", "^みずほ", "--output", "json"]);
+        let value: Value = serde_json::from_str(&out).unwrap();
+        let rows = value.as_array().unwrap();
+        assert!(!rows.is_empty());
+        assert!(rows
+            .iter()
+            .all(|row| row["bank_name"].as_str().unwrap().starts_with("みずほ")));
+
+        let (_, out) = run_args(&["export", "--banks-only", "--output", "ndjson"]);
+        let value: Value = serde_json::from_str(out.lines().next().unwrap()).unwrap();
+        assert_eq!(value.as_object().unwrap().len(), 5);
+        assert!(Cli::try_parse_from(["zengin", "export"]).is_err());
+        assert!(
+            Cli::try_parse_from(["zengin", "export", "--format", "csv", "--output", "tsv"])
+                .is_err()
+        );
+
+        let (_, out) = run_args(&["validate", "0001", "999", "--output", "tsv"]);
+        assert!(out.starts_with("valid\treason\tmessage\tbank_code\t"));
+        assert!(out.ends_with(
+            "\nfalse\tBranchNotFound\tbranch 999 not found for bank 0001\t\t\t\t\t\t\t\t\t\t\n"
+        ));
+    }
+
+    #[test]
+    fn test_pick_label() {
+        let zengin = fixture();
+        assert_eq!(
+            pick::label(&zengin["0001"]["110"]),
+            "110 新宿  シンジユク しんじゆく shinjiyuku"
+        );
+    }
+
+    #[test]
+    fn test_completions() {
+        let (ok, out) = run_args(&["completions", "fish"]);
+        assert!(ok);
+        assert!(out.contains("__fish_zengin_using_subcommand bank"));
+        assert!(out.contains(r#"-l bank -d 'Pick a branch of this bank, rather than a bank first' -r -f -a "0001\t'みずほ'"#));
+
+        let (_, out) = run_args(&["completions", "bash"]);
+        let codes = fixture().bank_codes().join(" ");
+        assert!(out.contains(&format!(" {codes}\"")));
+        // The bank codes only restrict the completions, not the commands.
+        assert!(run_args(&["bank", "1"]).0);
+    }
+
+    #[test]
+    fn test_search() {
+        let (ok, out) = run_args(&["search", "^みずほ$"]);
+        assert!(ok);
+        assert_eq!(out, "0001\tみずほ\n");
+
+        let (_, out) = run_args(&["search", "--field", "roma", "shinjiyuku"]);
+        assert!(out.starts_with("0001 110\tみずほ 新宿\n"));
+        let (_, out) = run_args(&["search", "--field", "kana", "shinjiyuku"]);
+        assert_eq!(out, "");
+
+        let (_, out) = run_args(&["search", "--substring", "(", "--limit", "1"]);
+        assert_eq!(out, "");
+        let (_, out) = run_args(&["search", "--fuzzy", "みづほ", "--limit", "1"]);
+        assert_eq!(out, "0001\tみずほ\n");
+
+        assert!(
+            Cli::try_parse_from(["zengin", "search", "--fuzzy", "--field", "name", "a"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_file() {
+        let dir = std::env::temp_dir().join(format!("zengin-cli-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = |name: &str| dir.join(name).to_str().unwrap().to_string();
+        std::fs::write(
+            path("payments.csv"),
+            "銀行,支店,種別,番号,名義,金額\n\
+             0005,001,普通,22222,やまだ　たろう,\"10,000\"\n\
+             0001,110,当座,3333333,ｻﾄｳ ﾊﾅｺ,2500\n",
+        )
+        .unwrap();
+        let build = [
+            "file",
+            "build",
+            "--from",
+            "csv",
+            "--requester-code",
+            "123",
+            "--requester-name",
+            "サンプル　ショップ",
+            "--date",
+            "0425",
+            "--source-bank",
+            "0001",
+            "--source-branch",
+            "001",
+            "--source-account",
+            "1111111",
+        ];
+        let (transfers, csv) = (path("transfers.txt"), path("payments.csv"));
+        let (ok, _) = run_args(&[&build[..], &[&csv, "--out", &transfers]].concat());
+        assert!(ok);
+        let bytes = std::fs::read(&transfers).unwrap();
+        assert_eq!(bytes.len(), 5 * 122);
+        assert!(std::str::from_utf8(&bytes).is_err());
+
+        let (ok, out) = run_args(&["file", "parse", &transfers]);
+        assert!(ok);
+        assert!(out.starts_with("kind: 総合振込 (21)\nrequester: 0000000123 ｻﾝﾌﾟﾙ ｼﾖﾂﾌﾟ\n"));
+        assert!(out.contains("\n2: 0005 001 普通 0022222 ﾔﾏﾀﾞ ﾀﾛｳ 10000\n"));
+        assert!(out.ends_with("\ntotal: 2 records, 12500 yen\n"));
+        let (_, out) = run_args(&["file", "parse", &transfers, "--output", "json"]);
+        let value: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value[1]["branch_name"], "ｼﾝｼﾞﾕｸ");
+        assert_eq!(value[1]["line"], 3);
+        let invalid_date = [&["zengin"], &build[..9], &["1332"], &build[10..], &[&csv]];
+        assert!(Cli::try_parse_from(invalid_date.concat()).is_err());
+
+        assert!(run_args(&["file", "validate", &transfers]).0);
+        let text = encoding_rs::SHIFT_JIS
+            .decode(&bytes)
+            .0
+            .replace("ｼﾝｼﾞﾕｸ", "ｼﾌﾞﾔ  ");
+        std::fs::write(&transfers, text).unwrap();
+        let (ok, out) = run_args(&["file", "validate", &transfers, "--output", "ndjson"]);
+        assert!(!ok);
+        let value: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value["kind"], "dataset");
+        assert_eq!(value["line"], 3);
+
+        std::fs::write(&csv, "0001,110,定期,3333333,ｻﾄｳ,1\n").unwrap();
+        assert_eq!(
+            run_args(&[&build[..], &[&csv]].concat()),
+            (false, String::new())
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,105 @@
+//! The machine-readable output modes of `--output`.
+//!
+//! Every mode writes objects with the same keys: the fields of a bank and
+//! of one of its branches are named as the columns of
+//! [`Zengin::export_csv`](zengin::Zengin::export_csv), such as `bank_code`
+//! and `branch_name`, and the branch fields of a bank are null.
+
+use clap::ValueEnum;
+use serde_json::{Map, Value};
+use std::io::{self, Write};
+use zengin::{export::Column, Bank, Branch, Record};
+
+/// A machine-readable output mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Output {
+    /// A JSON object, or an array of them for commands with several
+    /// results.
+    Json,
+    /// One JSON object per line.
+    Ndjson,
+    /// Tab-separated values, with a header row.
+    Tsv,
+}
+
+/// A bank, or a branch and its bank.
+pub type Row<'a> = (&'a Bank, Option<&'a Branch>);
+
+/// Returns the object of a bank, or of a branch and its bank, with the
+/// given columns.
+pub fn row(bank: &Bank, branch: Option<&Branch>, columns: &[Column]) -> Map<String, Value> {
+    columns
+        .iter()
+        .map(|&column| {
+            let value = match column {
+                Column::Bank(field) => Some(bank.field(field)),
+                Column::Branch(field) => branch.map(|branch| branch.field(field)),
+            };
+            (column.name().to_string(), value.into())
+        })
+        .collect()
+}
+
+/// Returns a value as a TSV field. Tabs and newlines in strings become
+/// spaces, and null becomes an empty field.
+fn tsv_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.replace(['\t', '\n', '\r'], " "),
+        value => value.to_string(),
+    }
+}
+
+/// Writes objects in an output mode. A single object is written as is in
+/// JSON, rather than in an array, if `single` is set. TSV has the given
+/// keys as columns, in order.
+pub fn write_objects(
+    out: &mut dyn Write,
+    output: Output,
+    keys: &[&str],
+    objects: Vec<Map<String, Value>>,
+    single: bool,
+) -> io::Result<()> {
+    match output {
+        Output::Json if single && objects.len() == 1 => {
+            let object = objects.into_iter().next().map(Value::Object);
+            writeln!(out, "{}", object.unwrap_or_default())
+        }
+        Output::Json => {
+            let objects = objects.into_iter().map(Value::Object).collect();
+            writeln!(out, "{}", Value::Array(objects))
+        }
+        Output::Ndjson => {
+            for object in objects {
+                writeln!(out, "{}", Value::Object(object))?;
+            }
+            Ok(())
+        }
+        Output::Tsv => {
+            writeln!(out, "{}", keys.join("\t"))?;
+            for object in objects {
+                let fields: Vec<_> = keys
+                    .iter()
+                    .map(|&key| tsv_field(object.get(key).unwrap_or(&Value::Null)))
+                    .collect();
+                writeln!(out, "{}", fields.join("\t"))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Writes banks or branches in an output mode, with every column.
+pub fn write_rows(
+    out: &mut dyn Write,
+    output: Output,
+    rows: &[Row],
+    single: bool,
+) -> io::Result<()> {
+    let keys: Vec<_> = Column::ALL.iter().map(|column| column.name()).collect();
+    let objects = rows
+        .iter()
+        .map(|&(bank, branch)| row(bank, branch, &Column::ALL))
+        .collect();
+    write_objects(out, output, &keys, objects, single)
+}
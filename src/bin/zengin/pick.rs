@@ -0,0 +1,60 @@
+//! The `pick` command.
+
+use crate::output::Row;
+use clap::Args;
+use dialoguer::{theme::ColorfulTheme, FuzzySelect};
+use std::error::Error;
+use zengin::{Bank, Record, Zengin};
+
+#[derive(Debug, Args)]
+pub struct PickArgs {
+    /// Pick a branch of this bank, rather than a bank first.
+    #[arg(long, value_name = "CODE", conflicts_with = "banks_only")]
+    bank: Option<String>,
+    /// Pick a bank only.
+    #[arg(long)]
+    banks_only: bool,
+}
+
+/// Returns the label of a bank or branch in a picker, with every field so
+/// that any of them can be typed to filter.
+pub fn label(record: &impl Record) -> String {
+    let [name, kana, hira, roma] = record.names();
+    format!("{} {name}  {kana} {hira} {roma}", record.code())
+}
+
+/// Shows a fuzzy picker on stderr over records, and returns the picked one,
+/// or `None` if the user cancelled it.
+fn pick<'a, R: Record + 'a>(
+    prompt: &str,
+    records: impl Iterator<Item = (&'a str, &'a R)>,
+) -> Result<Option<&'a R>, Box<dyn Error>> {
+    let records: Vec<_> = records.map(|(_, record)| record).collect();
+    let labels: Vec<_> = records.iter().map(|&record| label(record)).collect();
+    let picked = FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .items(&labels)
+        .max_length(15)
+        .interact_opt()?;
+    Ok(picked.map(|i| records[i]))
+}
+
+/// Lets the user pick a bank, then one of its branches unless
+/// `--banks-only` is set, in the terminal. Returns `None` if the user
+/// cancelled.
+pub fn run<'a>(zengin: &'a Zengin, args: &PickArgs) -> Result<Option<Row<'a>>, Box<dyn Error>> {
+    let bank: &Bank = match &args.bank {
+        Some(code) => zengin
+            .get_bank(code.as_str())
+            .ok_or_else(|| format!("bank {code} not found"))?,
+        None => match pick("Bank", zengin.iter())? {
+            Some(bank) => bank,
+            None => return Ok(None),
+        },
+    };
+    if args.banks_only {
+        return Ok(Some((bank, None)));
+    }
+    let prompt = format!("Branch of {}", bank.name);
+    Ok(pick(&prompt, bank.iter())?.map(|branch| (bank, Some(branch))))
+}
@@ -0,0 +1,124 @@
+//! The `search` command.
+
+use crate::output::Row;
+use clap::{Args, ValueEnum};
+use std::io::{self, Write};
+use zengin::{regex, Field, Record, SearchOptions, Zengin, ZenginError};
+
+#[derive(Debug, Args)]
+pub struct SearchArgs {
+    /// A regular expression, or the text to find with --substring or
+    /// --fuzzy.
+    query: String,
+    /// Find the query as plain text rather than a regular expression.
+    #[arg(long, conflicts_with = "fuzzy")]
+    substring: bool,
+    /// Rank banks and branches by similarity to the query in any of their
+    /// names, allowing typos.
+    #[arg(long)]
+    fuzzy: bool,
+    /// The fields to match, which default to all names.
+    #[arg(long, value_enum, value_delimiter = ',', conflicts_with = "fuzzy")]
+    field: Vec<SearchField>,
+    /// Match letters regardless of case.
+    #[arg(short = 'i', long)]
+    ignore_case: bool,
+    /// Print at most this many results.
+    #[arg(long)]
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SearchField {
+    Code,
+    Name,
+    Kana,
+    Hira,
+    Roma,
+}
+
+impl From<SearchField> for Field {
+    fn from(field: SearchField) -> Self {
+        match field {
+            SearchField::Code => Field::Code,
+            SearchField::Name => Field::Name,
+            SearchField::Kana => Field::Kana,
+            SearchField::Hira => Field::Hira,
+            SearchField::Roma => Field::Roma,
+        }
+    }
+}
+
+/// Returns the banks, then the branches, that match a search, in code order
+/// or most similar first with --fuzzy.
+pub fn search<'a>(zengin: &'a Zengin, args: &SearchArgs) -> Result<Vec<Row<'a>>, ZenginError> {
+    let mut hits = vec![];
+    if args.fuzzy {
+        hits.extend(
+            zengin
+                .find_banks_fuzzy(&args.query)
+                .into_iter()
+                .map(|(_, bank)| (bank, None)),
+        );
+        let mut branches: Vec<_> = zengin
+            .iter()
+            .flat_map(|(_, bank)| {
+                bank.find_branches_fuzzy(&args.query)
+                    .into_iter()
+                    .map(move |(score, branch)| (score, bank, branch))
+            })
+            .collect();
+        branches.sort_by(|(a, ..), (b, ..)| b.total_cmp(a));
+        hits.extend(
+            branches
+                .into_iter()
+                .map(|(_, bank, branch)| (bank, Some(branch))),
+        );
+    } else {
+        let pattern = if args.substring {
+            regex::escape(&args.query)
+        } else {
+            args.query.clone()
+        };
+        let mut options = SearchOptions {
+            case_insensitive: args.ignore_case,
+            ..Default::default()
+        };
+        if !args.field.is_empty() {
+            options.fields = args.field.iter().map(|&field| field.into()).collect();
+        }
+        hits.extend(
+            zengin
+                .find_banks(&pattern, &options)?
+                .into_iter()
+                .map(|bank| (bank, None)),
+        );
+        hits.extend(
+            zengin
+                .find_branches(&pattern, &options)?
+                .into_iter()
+                .map(|(bank, branch)| (bank, Some(branch))),
+        );
+    }
+    hits.truncate(args.limit.unwrap_or(usize::MAX));
+    Ok(hits)
+}
+
+/// Writes the hits of a search, one per line: the codes, then a tab, then
+/// the names.
+pub fn write_hits(out: &mut impl Write, hits: &[Row]) -> io::Result<()> {
+    for hit in hits {
+        match hit {
+            (bank, None) => writeln!(out, "{}\t{}", bank.code(), bank.name)?,
+            (bank, Some(branch)) => writeln!(
+                out,
+                "{} {}\t{} {}",
+                bank.code(),
+                branch.code(),
+                bank.name,
+                branch.name
+            )?,
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,146 @@
+//! The `validate` command.
+
+use crate::output::{self, Output};
+use clap::{Args, ValueEnum};
+use serde_json::{Map, Value};
+use std::io::{self, Write};
+use zengin::{
+    account::{AccountType, BankAccount},
+    export::Column,
+    Bank, Branch, ValidationError, Zengin,
+};
+
+#[derive(Debug, Args)]
+pub struct ValidateArgs {
+    /// The bank code, such as 0001.
+    bank_code: String,
+    /// The branch code, such as 001.
+    branch_code: String,
+    /// An account number to check as well, of up to seven digits.
+    #[arg(long, value_name = "NUMBER")]
+    account: Option<String>,
+    /// The type of the account.
+    #[arg(long, value_enum, default_value = "ordinary", requires = "account")]
+    account_type: Type,
+    /// The name of the account holder to check as well, in half-width
+    /// katakana.
+    #[arg(long, requires = "account")]
+    kana: Option<String>,
+    /// Print the result as JSON, as --output json does.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Type {
+    Ordinary,
+    Checking,
+    Savings,
+    Other,
+}
+
+impl From<Type> for AccountType {
+    fn from(account_type: Type) -> Self {
+        match account_type {
+            Type::Ordinary => AccountType::Ordinary,
+            Type::Checking => AccountType::Checking,
+            Type::Savings => AccountType::Savings,
+            Type::Other => AccountType::Other,
+        }
+    }
+}
+
+/// Validates the bank and branch codes of the arguments, and their account
+/// if any.
+fn validate<'a>(
+    zengin: &'a Zengin,
+    args: &ValidateArgs,
+) -> Result<(&'a Bank, &'a Branch), ValidationError> {
+    let Some(number) = &args.account else {
+        return zengin.validate(&args.bank_code, &args.branch_code);
+    };
+    let account = BankAccount {
+        bank_code: args
+            .bank_code
+            .parse()
+            .map_err(ValidationError::InvalidBankCode)?,
+        branch_code: args
+            .branch_code
+            .parse()
+            .map_err(ValidationError::InvalidBranchCode)?,
+        account_type: args.account_type.into(),
+        number: number.clone(),
+        holder_kana: args.kana.clone().unwrap_or_default(),
+    };
+    account.validate(zengin)
+}
+
+/// Returns the name of the variant of an error, such as `BranchNotFound`,
+/// from its serialized form.
+fn reason(error: &Value) -> &str {
+    match error {
+        Value::String(reason) => reason,
+        Value::Object(map) => map.keys().next().map_or("", String::as_str),
+        _ => "",
+    }
+}
+
+/// Returns the object of the result of a validation: `valid`, the `reason`
+/// and `message` of the failure, and the bank and branch columns if it
+/// succeeded. The `error` key holds the serialized failure, and is left out
+/// of TSV.
+fn result_object(
+    result: &Result<(&Bank, &Branch), ValidationError>,
+) -> io::Result<Map<String, Value>> {
+    let (mut object, error) = match result {
+        Ok((bank, branch)) => (output::row(bank, Some(branch), &Column::ALL), None),
+        Err(e) => (Map::new(), Some(e)),
+    };
+    object.insert("valid".to_string(), result.is_ok().into());
+    let error = error.map(serde_json::to_value).transpose()?;
+    object.insert(
+        "reason".to_string(),
+        error.as_ref().map(|error| reason(error).to_string()).into(),
+    );
+    object.insert(
+        "message".to_string(),
+        result.as_ref().err().map(ToString::to_string).into(),
+    );
+    object.insert("error".to_string(), error.unwrap_or_default());
+    Ok(object)
+}
+
+/// Writes the result of a validation: the bank and branch, or the reason it
+/// failed.
+fn write_result(
+    out: &mut impl Write,
+    result: &Result<(&Bank, &Branch), ValidationError>,
+    output: Option<Output>,
+) -> io::Result<()> {
+    let Some(output) = output else {
+        return match result {
+            Ok((bank, branch)) => writeln!(
+                out,
+                "ok: {} {} {} {}",
+                bank.code, branch.code, bank.name, branch.name
+            ),
+            Err(e) => writeln!(out, "invalid: {e}"),
+        };
+    };
+    let mut keys = vec!["valid", "reason", "message"];
+    keys.extend(Column::ALL.iter().map(|column| column.name()));
+    output::write_objects(out, output, &keys, vec![result_object(result)?], true)
+}
+
+/// Validates the codes and account of the arguments, writing the result to
+/// `out`. Returns whether they are valid.
+pub fn run(
+    zengin: &Zengin,
+    args: &ValidateArgs,
+    output: Option<Output>,
+    out: &mut impl Write,
+) -> io::Result<bool> {
+    let result = validate(zengin, args);
+    write_result(out, &result, output.or(args.json.then_some(Output::Json)))?;
+    Ok(result.is_ok())
+}
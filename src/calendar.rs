@@ -0,0 +1,368 @@
+//! Business days of Japanese banks.
+//!
+//! Banks are closed on weekends, on national holidays, and from December 31
+//! to January 3. National holidays are computed from the rules of the
+//! National Holidays Act (国民の祝日に関する法律) as they stood in each year,
+//! including substitute holidays (振替休日), the days sandwiched between two
+//! holidays (国民の休日), the Happy Monday moves of 2000 and 2003, and the
+//! one-off holidays such as those of 1989, 1990, 1993 and 2019 to 2021. The
+//! calendar holds from 1980 to 2099, as the equinox days use the usual
+//! approximation for those years; the government fixes them officially only a
+//! year in advance.
+//!
+//! [`estimate_settlement_date`] builds on the calendar to estimate when a
+//! transfer reaches the recipient.
+
+use chrono::{Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+/// Returns the holiday with a fixed rule on a date, without substitute
+/// holidays and 国民の休日.
+fn fixed_holiday(date: NaiveDate) -> Option<&'static str> {
+    let (year, month, day) = (date.year(), date.month(), date.day());
+    let nth_monday = |n| NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Mon, n);
+    let is_nth_monday = |n| nth_monday(n) == Some(date);
+    let name = match (month, day) {
+        (1, 1) => "元日",
+        (1, 15) if year < 2000 => "成人の日",
+        (1, _) if year >= 2000 && is_nth_monday(2) => "成人の日",
+        (2, 11) => "建国記念の日",
+        (2, 23) if year >= 2020 => "天皇誕生日",
+        (2, 24) if year == 1989 => "昭和天皇の大喪の礼",
+        (3, _) if day == vernal_equinox(year) => "春分の日",
+        (4, 29) if year < 1989 => "天皇誕生日",
+        (4, 29) if year < 2007 => "みどりの日",
+        (4, 29) => "昭和の日",
+        (5, 1) if year == 2019 => "即位の日",
+        (5, 3) => "憲法記念日",
+        (5, 4) if year >= 2007 => "みどりの日",
+        (5, 5) => "こどもの日",
+        (6, 9) if year == 1993 => "皇太子徳仁親王の結婚の儀",
+        (7, 20) if (1996..2003).contains(&year) => "海の日",
+        (7, 23) if year == 2020 => "海の日",
+        (7, 22) if year == 2021 => "海の日",
+        (7, _) if year >= 2003 && year != 2020 && year != 2021 && is_nth_monday(3) => "海の日",
+        (7, 24) if year == 2020 => "スポーツの日",
+        (7, 23) if year == 2021 => "スポーツの日",
+        (8, 10) if year == 2020 => "山の日",
+        (8, 8) if year == 2021 => "山の日",
+        (8, 11) if year >= 2016 && year != 2020 && year != 2021 => "山の日",
+        (9, 15) if year < 2003 => "敬老の日",
+        (9, _) if year >= 2003 && is_nth_monday(3) => "敬老の日",
+        (9, _) if day == autumnal_equinox(year) => "秋分の日",
+        (10, 10) if year < 2000 => "体育の日",
+        (10, 22) if year == 2019 => "即位礼正殿の儀の行われる日",
+        (10, _) if year >= 2000 && year != 2020 && year != 2021 && is_nth_monday(2) => {
+            if year >= 2020 {
+                "スポーツの日"
+            } else {
+                "体育の日"
+            }
+        }
+        (11, 3) => "文化の日",
+        (11, 12) if year == 1990 => "即位礼正殿の儀",
+        (11, 23) => "勤労感謝の日",
+        (12, 23) if (1989..=2018).contains(&year) => "天皇誕生日",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Returns the day of March of the vernal equinox.
+fn vernal_equinox(year: i32) -> u32 {
+    equinox(year, 20.8431)
+}
+
+/// Returns the day of September of the autumnal equinox.
+fn autumnal_equinox(year: i32) -> u32 {
+    equinox(year, 23.2488)
+}
+
+fn equinox(year: i32, base: f64) -> u32 {
+    let years = f64::from(year - 1980);
+    (base + 0.242194 * years - (years / 4.0).floor()).floor() as u32
+}
+
+/// Returns the name of the national holiday on a date, if any.
+///
+/// Substitute holidays are named `振替休日`, and days sandwiched between two
+/// holidays `国民の休日`.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use zengin::calendar::holiday_name;
+/// let date = NaiveDate::from_ymd_opt(2024, 2, 12).unwrap();
+/// assert_eq!(holiday_name(date), Some("振替休日"));
+/// ```
+pub fn holiday_name(date: NaiveDate) -> Option<&'static str> {
+    if let Some(name) = fixed_holiday(date) {
+        return Some(name);
+    }
+    // Until 2006, only the day right after a holiday on a Sunday was a
+    // substitute holiday; since 2007, it is the first day that is not one.
+    let mut previous = date.pred_opt()?;
+    while fixed_holiday(previous).is_some() {
+        if previous.weekday() == Weekday::Sun {
+            return Some("振替休日");
+        }
+        if date.year() < 2007 {
+            break;
+        }
+        previous = previous.pred_opt()?;
+    }
+    let sandwiched =
+        fixed_holiday(date.pred_opt()?).is_some() && fixed_holiday(date.succ_opt()?).is_some();
+    if sandwiched && date.year() >= 1986 && date.weekday() != Weekday::Sun {
+        return Some("国民の休日");
+    }
+    None
+}
+
+/// Returns whether a date is a national holiday, including substitute
+/// holidays and 国民の休日.
+pub fn is_holiday(date: NaiveDate) -> bool {
+    holiday_name(date).is_some()
+}
+
+/// Returns whether banks are open on a date.
+///
+/// Banks are closed on Saturdays, Sundays, national holidays, and from
+/// December 31 to January 3.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use zengin::calendar::is_bank_business_day;
+/// assert!(!is_bank_business_day(NaiveDate::from_ymd_opt(2024, 1, 3).unwrap()));
+/// assert!(is_bank_business_day(NaiveDate::from_ymd_opt(2024, 1, 4).unwrap()));
+/// ```
+pub fn is_bank_business_day(date: NaiveDate) -> bool {
+    let year_end = matches!((date.month(), date.day()), (12, 31) | (1, 1..=3));
+    !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !year_end && !is_holiday(date)
+}
+
+/// Returns the first bank business day after a date.
+///
+/// # Panics
+///
+/// Panics if there is no such day within the range of [`NaiveDate`].
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use zengin::calendar::next_business_day;
+/// let friday = NaiveDate::from_ymd_opt(2024, 12, 27).unwrap();
+/// assert_eq!(next_business_day(friday), NaiveDate::from_ymd_opt(2025, 1, 6).unwrap());
+/// ```
+pub fn next_business_day(date: NaiveDate) -> NaiveDate {
+    let mut date = date;
+    loop {
+        date = date
+            .checked_add_days(Days::new(1))
+            .expect("date out of range");
+        if is_bank_business_day(date) {
+            return date;
+        }
+    }
+}
+
+/// The rules by which [`SettlementRules::estimate`] estimates the settlement
+/// date of a transfer.
+///
+/// The defaults follow the Zengin system: transfers between banks settle on
+/// the same day when they are made on a business day before the end of the
+/// core time at 15:30, and at any time when both banks take part in モアタイム
+/// (the 24-hour extension of the system). Banks settle transfers between their
+/// own accounts at any time. Banks may have earlier cutoffs of their own, so
+/// adjust the rules to them where they are known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettlementRules {
+    /// The end of the core time. Transfers made later on a business day
+    /// settle on the next business day.
+    pub core_time_cutoff: NaiveTime,
+    /// Whether transfers within a bank settle at any time.
+    pub same_bank_always_open: bool,
+}
+
+impl Default for SettlementRules {
+    fn default() -> Self {
+        SettlementRules {
+            core_time_cutoff: NaiveTime::from_hms_opt(15, 30, 0).expect("valid time"),
+            same_bank_always_open: true,
+        }
+    }
+}
+
+impl SettlementRules {
+    /// Estimates the date a transfer settles on under these rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `initiated_at` - The date and time the transfer was made, in
+    ///   Japan Standard Time.
+    /// * `same_bank` - Whether the payer and the recipient have accounts at the
+    ///   same bank.
+    /// * `supports_moretime` - Whether both banks take part in モアタイム.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the settlement date is out of the range of [`NaiveDate`].
+    pub fn estimate(
+        &self,
+        initiated_at: NaiveDateTime,
+        same_bank: bool,
+        supports_moretime: bool,
+    ) -> NaiveDate {
+        let date = initiated_at.date();
+        if supports_moretime || same_bank && self.same_bank_always_open {
+            return date;
+        }
+        if is_bank_business_day(date) && initiated_at.time() < self.core_time_cutoff {
+            date
+        } else {
+            next_business_day(date)
+        }
+    }
+}
+
+/// Estimates the date a transfer settles on, under the default
+/// [`SettlementRules`].
+///
+/// The estimate ignores the cutoffs of individual banks and system outages,
+/// so treat it as the earliest date the recipient can expect the money.
+///
+/// # Arguments
+///
+/// * `initiated_at` - The date and time the transfer was made, in Japan
+///   Standard Time.
+/// * `same_bank` - Whether the payer and the recipient have accounts at the
+///   same bank.
+/// * `supports_moretime` - Whether both banks take part in モアタイム.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use zengin::calendar::estimate_settlement_date;
+/// let thursday_evening = NaiveDate::from_ymd_opt(2024, 5, 2)
+///     .unwrap()
+///     .and_hms_opt(18, 0, 0)
+///     .unwrap();
+/// assert_eq!(
+///     estimate_settlement_date(thursday_evening, false, false),
+///     NaiveDate::from_ymd_opt(2024, 5, 7).unwrap()
+/// );
+/// ```
+pub fn estimate_settlement_date(
+    initiated_at: NaiveDateTime,
+    same_bank: bool,
+    supports_moretime: bool,
+) -> NaiveDate {
+    SettlementRules::default().estimate(initiated_at, same_bank, supports_moretime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_holidays() {
+        let holidays: Vec<_> = date(2024, 1, 1)
+            .iter_days()
+            .take_while(|d| d.year() == 2024)
+            .filter(|&d| is_holiday(d))
+            .map(|d| (d.month(), d.day()))
+            .collect();
+        assert_eq!(
+            holidays,
+            vec![
+                (1, 1),
+                (1, 8),
+                (2, 11),
+                (2, 12),
+                (2, 23),
+                (3, 20),
+                (4, 29),
+                (5, 3),
+                (5, 4),
+                (5, 5),
+                (5, 6),
+                (7, 15),
+                (8, 11),
+                (8, 12),
+                (9, 16),
+                (9, 22),
+                (9, 23),
+                (10, 14),
+                (11, 3),
+                (11, 4),
+                (11, 23),
+            ]
+        );
+        assert_eq!(holiday_name(date(2019, 4, 30)), Some("国民の休日"));
+        assert_eq!(holiday_name(date(2019, 5, 2)), Some("国民の休日"));
+        assert_eq!(holiday_name(date(2015, 9, 22)), Some("国民の休日"));
+        assert_eq!(holiday_name(date(2021, 7, 23)), Some("スポーツの日"));
+        assert_eq!(holiday_name(date(2021, 10, 11)), None);
+    }
+
+    #[test]
+    fn test_historical_holidays() {
+        assert_eq!(holiday_name(date(1988, 4, 29)), Some("天皇誕生日"));
+        assert_eq!(holiday_name(date(1989, 2, 24)), Some("昭和天皇の大喪の礼"));
+        assert_eq!(holiday_name(date(1999, 1, 15)), Some("成人の日"));
+        assert_eq!(holiday_name(date(1999, 1, 11)), None);
+        assert_eq!(holiday_name(date(1999, 10, 10)), Some("体育の日"));
+        assert_eq!(holiday_name(date(1999, 10, 11)), Some("振替休日"));
+        assert_eq!(holiday_name(date(2002, 7, 20)), Some("海の日"));
+        assert_eq!(holiday_name(date(2002, 9, 16)), Some("振替休日"));
+        assert_eq!(holiday_name(date(2006, 4, 29)), Some("みどりの日"));
+        assert_eq!(holiday_name(date(2006, 5, 4)), Some("国民の休日"));
+        assert_eq!(holiday_name(date(1985, 5, 4)), None);
+        assert_eq!(holiday_name(date(1992, 5, 4)), Some("振替休日"));
+        assert_eq!(holiday_name(date(1998, 5, 6)), None);
+    }
+
+    #[test]
+    fn test_business_days() {
+        assert!(!is_bank_business_day(date(2024, 12, 31)));
+        assert!(!is_bank_business_day(date(2024, 5, 6)));
+        assert!(is_bank_business_day(date(2024, 5, 7)));
+        assert_eq!(next_business_day(date(2024, 5, 2)), date(2024, 5, 7));
+        assert_eq!(next_business_day(date(2024, 5, 7)), date(2024, 5, 8));
+    }
+
+    #[test]
+    fn test_estimate_settlement_date() {
+        let at = |day, hour| date(2024, 5, day).and_hms_opt(hour, 0, 0).unwrap();
+        assert_eq!(
+            estimate_settlement_date(at(2, 10), false, false),
+            date(2024, 5, 2)
+        );
+        assert_eq!(
+            estimate_settlement_date(at(2, 16), false, false),
+            date(2024, 5, 7)
+        );
+        assert_eq!(
+            estimate_settlement_date(at(3, 10), false, false),
+            date(2024, 5, 7)
+        );
+        assert_eq!(
+            estimate_settlement_date(at(3, 10), false, true),
+            date(2024, 5, 3)
+        );
+        assert_eq!(
+            estimate_settlement_date(at(3, 10), true, false),
+            date(2024, 5, 3)
+        );
+
+        let rules = SettlementRules {
+            core_time_cutoff: NaiveTime::from_hms_opt(14, 0, 0).unwrap(),
+            same_bank_always_open: false,
+        };
+        assert_eq!(rules.estimate(at(2, 14), true, false), date(2024, 5, 7));
+    }
+}
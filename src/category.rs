@@ -0,0 +1,289 @@
+use crate::{Bank, BankCode, Zengin};
+use std::fmt;
+
+/// A category of financial institutions, as allocated by blocks of bank codes.
+///
+/// The blocks are the ones used by the Zengin system: for example, shinkin
+/// banks have codes `1000`-`1999` and labour banks `2950`-`2999`. Codes
+/// outside of the known blocks fall into [`Category::Other`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Category {
+    /// 都市銀行 and the net banks sharing their block, `0001`-`0099`.
+    CityBank,
+    /// 地方銀行, `0116`-`0199`.
+    RegionalBank,
+    /// 信託銀行, `0288`-`0399`.
+    TrustBank,
+    /// 外国銀行, `0400`-`0499`.
+    ForeignBank,
+    /// 第二地方銀行, `0500`-`0599`.
+    SecondRegionalBank,
+    /// 信用金庫, `1000`-`1999`.
+    ShinkinBank,
+    /// 信用組合, `2000`-`2899`.
+    CreditCooperative,
+    /// 労働金庫, `2950`-`2999`.
+    LaborBank,
+    /// 農業協同組合 and their federations, `3000`-`9449`.
+    AgriculturalCooperative,
+    /// 漁業協同組合 and their federations, `9450`-`9499`.
+    FisheryCooperative,
+    /// ゆうちょ銀行, `9900`.
+    JapanPostBank,
+    /// Any other institution.
+    Other,
+}
+
+impl Category {
+    /// Returns the category of the block a bank code belongs to.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{BankCode, Category};
+    /// let code: BankCode = "1310".parse().unwrap();
+    /// assert_eq!(Category::from_code(code), Category::ShinkinBank);
+    /// ```
+    pub fn from_code(code: BankCode) -> Category {
+        let n: u16 = code.as_str().parse().unwrap_or_default();
+        match n {
+            1..=99 => Category::CityBank,
+            116..=199 => Category::RegionalBank,
+            288..=399 => Category::TrustBank,
+            400..=499 => Category::ForeignBank,
+            500..=599 => Category::SecondRegionalBank,
+            1000..=1999 => Category::ShinkinBank,
+            2000..=2899 => Category::CreditCooperative,
+            2950..=2999 => Category::LaborBank,
+            3000..=9449 => Category::AgriculturalCooperative,
+            9450..=9499 => Category::FisheryCooperative,
+            9900 => Category::JapanPostBank,
+            _ => Category::Other,
+        }
+    }
+
+    /// Returns the category a name suffix such as `信用金庫` or `農協` implies.
+    fn from_name(name: &str) -> Option<Category> {
+        const SUFFIXES: [(&str, Category); 12] = [
+            ("信託", Category::TrustBank),
+            ("信託銀行", Category::TrustBank),
+            ("信用金庫", Category::ShinkinBank),
+            ("信金", Category::ShinkinBank),
+            ("信用組合", Category::CreditCooperative),
+            ("信組", Category::CreditCooperative),
+            ("労働金庫", Category::LaborBank),
+            ("労金", Category::LaborBank),
+            ("農協", Category::AgriculturalCooperative),
+            ("信連", Category::AgriculturalCooperative),
+            ("漁協", Category::FisheryCooperative),
+            ("漁連", Category::FisheryCooperative),
+        ];
+        SUFFIXES
+            .iter()
+            .find(|(suffix, _)| name.ends_with(suffix))
+            .map(|&(_, category)| category)
+    }
+
+    /// Returns whether institutions of the category are banks, as opposed to
+    /// cooperative financial institutions such as shinkin banks.
+    pub fn is_bank(self) -> bool {
+        matches!(
+            self,
+            Category::CityBank
+                | Category::RegionalBank
+                | Category::TrustBank
+                | Category::ForeignBank
+                | Category::SecondRegionalBank
+                | Category::JapanPostBank
+        )
+    }
+
+    /// Returns the Japanese name of the category, such as `信用金庫`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Category::CityBank => "都市銀行",
+            Category::RegionalBank => "地方銀行",
+            Category::TrustBank => "信託銀行",
+            Category::ForeignBank => "外国銀行",
+            Category::SecondRegionalBank => "第二地方銀行",
+            Category::ShinkinBank => "信用金庫",
+            Category::CreditCooperative => "信用組合",
+            Category::LaborBank => "労働金庫",
+            Category::AgriculturalCooperative => "農業協同組合",
+            Category::FisheryCooperative => "漁業協同組合",
+            Category::JapanPostBank => "ゆうちょ銀行",
+            Category::Other => "その他",
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Bank {
+    /// Returns the type of the institution.
+    ///
+    /// The type is derived from the block the bank code belongs to, as by
+    /// [`Category::from_code`]. Codes outside of the known blocks are
+    /// classified by the suffix of the name instead, such as `信用金庫` or
+    /// `農協`, and are [`Category::Other`] if that does not help either.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Category;
+    /// if bank.institution_type() == Category::AgriculturalCooperative {
+    ///     println!("{} is a JA bank", bank.name);
+    /// }
+    /// ```
+    pub fn institution_type(&self) -> Category {
+        let by_code = self
+            .code
+            .parse()
+            .map_or(Category::Other, Category::from_code);
+        if by_code != Category::Other {
+            return by_code;
+        }
+        Category::from_name(&self.name).unwrap_or(Category::Other)
+    }
+
+    /// Returns the full name of the institution, with the suffix that the
+    /// dataset leaves out.
+    ///
+    /// The dataset names banks without `銀行`, so みずほ銀行 is named just
+    /// `みずほ`. This function appends the suffix that matches
+    /// [`Bank::institution_type`], such as `銀行` or `信用組合`, unless the
+    /// name already ends with an institutional suffix like `信用金庫`.
+    ///
+    /// # Examples
+    /// ```
+    /// println!("{}", bank.full_name()); // みずほ銀行
+    /// ```
+    pub fn full_name(&self) -> String {
+        const INSTITUTIONAL_SUFFIXES: [&str; 9] = [
+            "銀行",
+            "金庫",
+            "組合",
+            "連合会",
+            "中金",
+            "信連",
+            "農協",
+            "漁協",
+            "漁連",
+        ];
+        if INSTITUTIONAL_SUFFIXES
+            .iter()
+            .any(|suffix| self.name.ends_with(suffix))
+        {
+            return self.name.clone();
+        }
+        let category = self.institution_type();
+        let suffix = match category {
+            category if category.is_bank() => "銀行",
+            Category::ShinkinBank => "信用金庫",
+            Category::CreditCooperative => "信用組合",
+            Category::LaborBank => "労働金庫",
+            Category::AgriculturalCooperative => "農業協同組合",
+            Category::FisheryCooperative => "漁業協同組合",
+            _ => "",
+        };
+        format!("{}{suffix}", self.name)
+    }
+}
+
+impl Zengin {
+    /// Retrieves the banks of a category.
+    ///
+    /// This function classifies the banks by [`Bank::institution_type`],
+    /// which uses the standard blocks of bank codes, so callers do not have to
+    /// maintain their own tables of code ranges. The banks are returned in
+    /// code order.
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - The category of the banks to retrieve.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{Category, Zengin};
+    /// let zengin = Zengin::new().unwrap();
+    /// for bank in zengin.banks_by_category(Category::ShinkinBank) {
+    ///     println!("Found bank: {} {}", bank.code, bank.name);
+    /// }
+    /// ```
+    pub fn banks_by_category(&self, category: Category) -> Vec<&Bank> {
+        let mut banks: Vec<_> = self
+            .banks
+            .values()
+            .filter(|bank| bank.institution_type() == category)
+            .collect();
+        banks.sort_by(|a, b| a.code.cmp(&b.code));
+        banks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_banks_by_category() {
+        let zengin = crate::fixture();
+        let codes = |category| {
+            zengin
+                .banks_by_category(category)
+                .iter()
+                .map(|bank| bank.code.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(codes(Category::ShinkinBank), vec!["1000", "1310"]);
+        assert_eq!(codes(Category::LaborBank), vec!["2950"]);
+        assert_eq!(codes(Category::TrustBank), vec!["0289"]);
+        assert_eq!(codes(Category::JapanPostBank), vec!["9900"]);
+        assert_eq!(Category::ShinkinBank.to_string(), "信用金庫");
+    }
+
+    #[test]
+    fn test_from_code() {
+        let category = |code: &str| Category::from_code(code.parse().unwrap());
+        assert_eq!(category("0001"), Category::CityBank);
+        assert_eq!(category("0099"), Category::CityBank);
+        assert_eq!(category("0100"), Category::Other);
+        assert_eq!(category("2999"), Category::LaborBank);
+        assert_eq!(category("3000"), Category::AgriculturalCooperative);
+        assert_eq!(category("9000"), Category::AgriculturalCooperative);
+        assert_eq!(category("9449"), Category::AgriculturalCooperative);
+        assert_eq!(category("9450"), Category::FisheryCooperative);
+        assert_eq!(category("9499"), Category::FisheryCooperative);
+        assert_eq!(category("9500"), Category::Other);
+        assert_eq!(category("9900"), Category::JapanPostBank);
+    }
+
+    #[test]
+    fn test_institution_type() {
+        let zengin = Zengin::from_combined_json(
+            r#"{
+                "0001":{"code":"0001","name":"みずほ","kana":"ミズホ","hira":"みずほ","roma":"mizuho"},
+                "2900":{"code":"2900","name":"どこか信用組合","kana":"ドコカシンヨウクミアイ","hira":"どこかしんようくみあい","roma":"dokokashinyoukumiai"},
+                "9600":{"code":"9600","name":"どこか漁協","kana":"ドコカギヨキヨウ","hira":"どこかぎよきよう","roma":"dokokagiyokiyou"},
+                "9700":{"code":"9700","name":"どこか","kana":"ドコカ","hira":"どこか","roma":"dokoka"}
+            }"#,
+        )
+        .unwrap();
+        let category = |code| zengin.get_bank(code).unwrap().institution_type();
+        assert_eq!(category("0001"), Category::CityBank);
+        assert_eq!(category("2900"), Category::CreditCooperative);
+        assert_eq!(category("9600"), Category::FisheryCooperative);
+        assert_eq!(category("9700"), Category::Other);
+        assert_eq!(zengin.get_bank("0001").unwrap().full_name(), "みずほ銀行");
+        assert_eq!(
+            zengin.get_bank("2900").unwrap().full_name(),
+            "どこか信用組合"
+        );
+        assert_eq!(zengin.get_bank("9700").unwrap().full_name(), "どこか");
+        assert!(Category::CityBank.is_bank());
+        assert!(!Category::ShinkinBank.is_bank());
+    }
+}
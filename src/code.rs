@@ -0,0 +1,543 @@
+use crate::{Bank, Branch, Zengin};
+use serde::{Serialize, Serializer};
+use std::{
+    error::Error,
+    fmt,
+    ops::{Bound, RangeBounds},
+    str::FromStr,
+};
+
+/// A bank code, which consists of exactly four ASCII digits such as `0001`.
+///
+/// # Examples
+/// ```
+/// use zengin::BankCode;
+/// let code: BankCode = "0001".parse().unwrap();
+/// assert_eq!(code.to_string(), "0001");
+/// assert!("001".parse::<BankCode>().is_err());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BankCode([u8; 4]);
+
+impl BankCode {
+    /// Returns the code as a string slice.
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("bank codes consist of ASCII digits")
+    }
+}
+
+/// A branch code, which consists of exactly three ASCII digits such as `001`.
+///
+/// # Examples
+/// ```
+/// use zengin::BranchCode;
+/// let code: BranchCode = "001".parse().unwrap();
+/// assert_eq!(code.to_string(), "001");
+/// assert!("0001".parse::<BranchCode>().is_err());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BranchCode([u8; 3]);
+
+impl BranchCode {
+    /// Returns the code as a string slice.
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("branch codes consist of ASCII digits")
+    }
+}
+
+/// The reason a bank or branch code failed to parse.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum CodeErrorKind {
+    /// The code is empty.
+    Empty,
+    /// The code contains a character other than an ASCII digit, at the given
+    /// character position. Full-width digits such as `１` fall into this case.
+    InvalidCharacter { position: usize, character: char },
+    /// The code consists of ASCII digits, but has the given number of them
+    /// instead of four for banks or three for branches.
+    InvalidLength(usize),
+}
+
+/// An error returned when parsing a [`BankCode`] or [`BranchCode`] fails.
+///
+/// [`ParseCodeError::kind`] tells what exactly is wrong with the code, so that
+/// forms can report each failure differently.
+///
+/// # Examples
+/// ```
+/// use zengin::{BankCode, CodeErrorKind};
+/// let e = "001".parse::<BankCode>().unwrap_err();
+/// assert_eq!(e.kind(), &CodeErrorKind::InvalidLength(3));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ParseCodeError {
+    /// `bank` or `branch`.
+    #[serde(rename = "record")]
+    code: &'static str,
+    len: usize,
+    kind: CodeErrorKind,
+}
+
+impl ParseCodeError {
+    /// Returns the reason the code failed to parse.
+    pub fn kind(&self) -> &CodeErrorKind {
+        &self.kind
+    }
+
+    /// Returns the number of digits a valid code has.
+    pub fn expected_len(&self) -> usize {
+        self.len
+    }
+}
+
+impl fmt::Display for ParseCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid {} code: expected {} ASCII digits, ",
+            self.code, self.len
+        )?;
+        match &self.kind {
+            CodeErrorKind::Empty => write!(f, "got an empty string"),
+            CodeErrorKind::InvalidCharacter {
+                position,
+                character,
+            } => write!(f, "got {character:?} at position {position}"),
+            CodeErrorKind::InvalidLength(len) => write!(f, "got {len} digits"),
+        }
+    }
+}
+
+impl Error for ParseCodeError {}
+
+fn parse_digits<const N: usize>(s: &str, code: &'static str) -> Result<[u8; N], ParseCodeError> {
+    let error = |kind| ParseCodeError { code, len: N, kind };
+    if s.is_empty() {
+        return Err(error(CodeErrorKind::Empty));
+    }
+    if let Some((position, character)) = s.chars().enumerate().find(|(_, c)| !c.is_ascii_digit()) {
+        return Err(error(CodeErrorKind::InvalidCharacter {
+            position,
+            character,
+        }));
+    }
+    s.as_bytes()
+        .try_into()
+        .map_err(|_| error(CodeErrorKind::InvalidLength(s.len())))
+}
+
+/// Parses up to `N` ASCII digits, restoring any leading zeros that were
+/// stripped from the code.
+fn pad_digits<const N: usize>(s: &str) -> Option<[u8; N]> {
+    if s.is_empty() || s.len() > N || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let mut digits = [b'0'; N];
+    digits[N - s.len()..].copy_from_slice(s.as_bytes());
+    Some(digits)
+}
+
+impl FromStr for BankCode {
+    type Err = ParseCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_digits(s, "bank").map(BankCode)
+    }
+}
+
+impl FromStr for BranchCode {
+    type Err = ParseCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_digits(s, "branch").map(BranchCode)
+    }
+}
+
+impl fmt::Display for BankCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for BranchCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Codes serialize as their strings, such as `"0001"`.
+impl Serialize for BankCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl Serialize for BranchCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for BankCode {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "BankCode".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "A bank code of four ASCII digits, such as 0001.",
+            "type": "string",
+            "pattern": "^[0-9]{4}$"
+        })
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for BranchCode {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "BranchCode".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "A branch code of three ASCII digits, such as 001.",
+            "type": "string",
+            "pattern": "^[0-9]{3}$"
+        })
+    }
+}
+
+impl fmt::Debug for BankCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BankCode").field(&self.as_str()).finish()
+    }
+}
+
+impl fmt::Debug for BranchCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BranchCode").field(&self.as_str()).finish()
+    }
+}
+
+impl AsRef<str> for BankCode {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for BranchCode {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// A value that can be used to look up a bank, such as a [`BankCode`], a
+/// string slice, or an integer.
+///
+/// Lookups are lenient about leading zeros, which upstream systems frequently
+/// strip: `"1"` and `1u16` both resolve to the bank code `0001`.
+///
+/// A [`BranchCode`] deliberately does not implement this trait, so that bank
+/// and branch codes cannot be swapped by accident.
+pub trait IntoBankCode {
+    /// Converts the value into a bank code, or returns `None` if it is not one.
+    fn into_bank_code(self) -> Option<BankCode>;
+}
+
+impl IntoBankCode for BankCode {
+    fn into_bank_code(self) -> Option<BankCode> {
+        Some(self)
+    }
+}
+
+impl IntoBankCode for &BankCode {
+    fn into_bank_code(self) -> Option<BankCode> {
+        Some(*self)
+    }
+}
+
+impl IntoBankCode for &str {
+    fn into_bank_code(self) -> Option<BankCode> {
+        pad_digits(self).map(BankCode)
+    }
+}
+
+impl IntoBankCode for &String {
+    fn into_bank_code(self) -> Option<BankCode> {
+        self.as_str().into_bank_code()
+    }
+}
+
+/// A value that can be used to look up a branch, such as a [`BranchCode`], a
+/// string slice, or an integer.
+///
+/// Lookups are lenient about leading zeros, which upstream systems frequently
+/// strip: `"1"` and `1u16` both resolve to the branch code `001`.
+///
+/// A [`BankCode`] deliberately does not implement this trait, so that bank
+/// and branch codes cannot be swapped by accident.
+pub trait IntoBranchCode {
+    /// Converts the value into a branch code, or returns `None` if it is not one.
+    fn into_branch_code(self) -> Option<BranchCode>;
+}
+
+impl IntoBranchCode for BranchCode {
+    fn into_branch_code(self) -> Option<BranchCode> {
+        Some(self)
+    }
+}
+
+impl IntoBranchCode for &BranchCode {
+    fn into_branch_code(self) -> Option<BranchCode> {
+        Some(*self)
+    }
+}
+
+impl IntoBranchCode for &str {
+    fn into_branch_code(self) -> Option<BranchCode> {
+        pad_digits(self).map(BranchCode)
+    }
+}
+
+impl IntoBranchCode for &String {
+    fn into_branch_code(self) -> Option<BranchCode> {
+        self.as_str().into_branch_code()
+    }
+}
+
+macro_rules! impl_into_code_for_integers {
+    ($($int:ty),*) => {$(
+        impl IntoBankCode for $int {
+            fn into_bank_code(self) -> Option<BankCode> {
+                let n = u16::try_from(self).ok().filter(|&n| n <= 9999)?;
+                Some(BankCode(pad_digits(&n.to_string())?))
+            }
+        }
+
+        impl IntoBranchCode for $int {
+            fn into_branch_code(self) -> Option<BranchCode> {
+                let n = u16::try_from(self).ok().filter(|&n| n <= 999)?;
+                Some(BranchCode(pad_digits(&n.to_string())?))
+            }
+        }
+    )*};
+}
+
+impl_into_code_for_integers!(u8, u16, u32, u64, usize, i32, i64);
+
+/// Converts a range bound into a branch code bound, or returns `None` if the
+/// bound is not a branch code.
+fn branch_code_bound<C: IntoBranchCode + Clone>(bound: Bound<&C>) -> Option<Bound<BranchCode>> {
+    Some(match bound {
+        Bound::Included(code) => Bound::Included(code.clone().into_branch_code()?),
+        Bound::Excluded(code) => Bound::Excluded(code.clone().into_branch_code()?),
+        Bound::Unbounded => Bound::Unbounded,
+    })
+}
+
+impl Zengin {
+    /// Retrieves the banks whose codes start with a prefix.
+    ///
+    /// Bank codes are allocated in blocks by type of institution, so a prefix
+    /// such as `"00"` (city banks and net banks) or `"1"` (shinkin banks and
+    /// their federations) selects a segment without a regular expression. The
+    /// banks are returned in code order.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - A string slice that holds the leading digits of the codes.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// for bank in zengin.banks_with_code_prefix("00") {
+    ///     println!("Found bank: {} {}", bank.code, bank.name);
+    /// }
+    /// ```
+    pub fn banks_with_code_prefix(&self, prefix: &str) -> Vec<&Bank> {
+        let mut banks: Vec<_> = self
+            .banks
+            .iter()
+            .filter(|(code, _)| code.starts_with(prefix))
+            .map(|(_, bank)| bank)
+            .collect();
+        banks.sort_by(|a, b| a.code.cmp(&b.code));
+        banks
+    }
+}
+
+impl Bank {
+    /// Retrieves the branches whose codes fall within a range.
+    ///
+    /// Some institutions allocate blocks of branch codes by region or
+    /// product, and this function selects such a block. The branches are
+    /// returned in code order.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - A range of branch codes, whose bounds are accepted in the
+    ///   same forms as [`Bank::get_branch`], such as `"200".."300"` or
+    ///   `200..=299`. If a bound is not a branch code, no branches are returned.
+    ///
+    /// # Examples
+    /// ```
+    /// for branch in bank.branches_in_range("200".."300") {
+    ///     println!("Found branch: {} {}", branch.code, branch.name);
+    /// }
+    /// ```
+    pub fn branches_in_range<C, R>(&self, range: R) -> Vec<&Branch>
+    where
+        C: IntoBranchCode + Clone,
+        R: RangeBounds<C>,
+    {
+        let (Some(start), Some(end)) = (
+            branch_code_bound(range.start_bound()),
+            branch_code_bound(range.end_bound()),
+        ) else {
+            return Vec::new();
+        };
+        let mut branches: Vec<_> = self
+            .branches
+            .values()
+            .filter(|branch| {
+                branch
+                    .code
+                    .parse::<BranchCode>()
+                    .is_ok_and(|code| (start, end).contains(&code))
+            })
+            .collect();
+        branches.sort_by(|a, b| a.code.cmp(&b.code));
+        branches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bank_code() {
+        assert_eq!("0001".parse::<BankCode>().unwrap().as_str(), "0001");
+        assert!("001".parse::<BankCode>().is_err());
+        assert!("00001".parse::<BankCode>().is_err());
+        assert!("000a".parse::<BankCode>().is_err());
+        assert!("０００１".parse::<BankCode>().is_err());
+    }
+
+    #[test]
+    fn test_lenient_codes() {
+        assert_eq!("1".into_bank_code().unwrap().as_str(), "0001");
+        assert_eq!(5u16.into_bank_code().unwrap().as_str(), "0005");
+        assert_eq!(9900.into_bank_code().unwrap().as_str(), "9900");
+        assert_eq!("12".into_branch_code().unwrap().as_str(), "012");
+        assert!(10000.into_bank_code().is_none());
+        assert!((-1).into_bank_code().is_none());
+        assert!(1000u32.into_branch_code().is_none());
+        assert!("".into_bank_code().is_none());
+        assert!("0x1".into_bank_code().is_none());
+        assert!("1".parse::<BankCode>().is_err());
+    }
+
+    #[test]
+    fn test_banks_with_code_prefix() {
+        let zengin = crate::fixture();
+        let codes: Vec<_> = zengin
+            .banks_with_code_prefix("1")
+            .iter()
+            .map(|bank| bank.code.to_string())
+            .collect();
+        assert_eq!(codes, vec!["1000", "1310"]);
+        assert_eq!(
+            zengin.banks_with_code_prefix("").len(),
+            zengin.all_banks().len()
+        );
+        assert!(zengin.banks_with_code_prefix("5").is_empty());
+    }
+
+    #[test]
+    fn test_branches_in_range() {
+        let zengin = crate::fixture();
+        let bank = zengin.get_bank("0001").unwrap();
+        let codes = |branches: Vec<&Branch>| {
+            branches
+                .iter()
+                .map(|branch| branch.code.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            codes(bank.branches_in_range("100".."300")),
+            vec!["110", "251"]
+        );
+        assert_eq!(codes(bank.branches_in_range(1..=4)), vec!["001", "004"]);
+        assert_eq!(codes(bank.branches_in_range("251"..)), vec!["251"]);
+        assert!(bank.branches_in_range("x".."300").is_empty());
+    }
+
+    #[test]
+    fn test_parse_branch_code() {
+        let code: BranchCode = "001".parse().unwrap();
+        assert_eq!(code.to_string(), "001");
+        assert_eq!(format!("{code:?}"), r#"BranchCode("001")"#);
+        assert_eq!(
+            "01".parse::<BranchCode>().unwrap_err().to_string(),
+            "invalid branch code: expected 3 ASCII digits, got 2 digits"
+        );
+    }
+
+    #[test]
+    fn test_parse_code_error_kind() {
+        let kind = |s: &str| s.parse::<BankCode>().unwrap_err().kind().clone();
+        assert_eq!(kind(""), CodeErrorKind::Empty);
+        assert_eq!(kind("00001"), CodeErrorKind::InvalidLength(5));
+        assert_eq!(
+            kind("０００１"),
+            CodeErrorKind::InvalidCharacter {
+                position: 0,
+                character: '０'
+            }
+        );
+        assert_eq!(
+            kind("01-2"),
+            CodeErrorKind::InvalidCharacter {
+                position: 2,
+                character: '-'
+            }
+        );
+    }
+
+    #[test]
+    fn test_serialize_errors() {
+        let e = "01-2".parse::<BankCode>().unwrap_err();
+        assert_eq!(
+            serde_json::to_string(&e).unwrap(),
+            r#"{"record":"bank","len":4,"kind":{"InvalidCharacter":{"position":2,"character":"-"}}}"#
+        );
+        let e = crate::ValidationError::BranchNotFound {
+            bank: "0001".parse().unwrap(),
+            branch: "999".parse().unwrap(),
+        };
+        assert_eq!(
+            serde_json::to_string(&e).unwrap(),
+            r#"{"BranchNotFound":{"bank":"0001","branch":"999"}}"#
+        );
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_json_schema() {
+        let schema = schemars::schema_for!(Bank);
+        let properties = schema.get("properties").unwrap();
+        assert!(properties.get("kana").is_some());
+        assert!(properties.get("branches").is_some());
+        assert!(properties.get("prefix_index").is_none());
+
+        let schema = schemars::schema_for!(crate::ValidationError);
+        let code = &schema.get("$defs").unwrap()["BankCode"];
+        assert_eq!(code["pattern"], "^[0-9]{4}$");
+    }
+}
@@ -0,0 +1,116 @@
+//! Sorting of kana in gojūon (Japanese syllabary) order.
+//!
+//! Comparing kana strings by their UTF-8 bytes almost follows the syllabary,
+//! but it interleaves voiced kana with unvoiced ones, so `がか` sorts after
+//! `かき` although Japanese dictionaries list it first. [`gojuon_cmp`]
+//! compares the kana without voicing marks and small kana first, and only
+//! uses them to break ties.
+
+use crate::{
+    normalize::{katakana_to_hiragana, small_to_large},
+    Bank, Branch, Zengin,
+};
+use std::cmp::Ordering;
+use unicode_normalization::UnicodeNormalization;
+
+/// Compares two kana strings in gojūon order.
+///
+/// Katakana and hiragana are treated alike. Strings that differ only in
+/// voicing marks or in small kana are ordered unvoiced before voiced before
+/// semi-voiced, and small kana before large ones, such as `はは`, `ばば`,
+/// `ぱぱ`.
+///
+/// # Examples
+/// ```
+/// use std::cmp::Ordering;
+/// use zengin::collation::gojuon_cmp;
+/// assert_eq!(gojuon_cmp("ガカ", "カキ"), Ordering::Less);
+/// assert_eq!(gojuon_cmp("はは", "ばば"), Ordering::Less);
+/// ```
+pub fn gojuon_cmp(a: &str, b: &str) -> Ordering {
+    primary_key(a)
+        .cmp(primary_key(b))
+        .then_with(|| secondary_key(a).cmp(secondary_key(b)))
+        .then_with(|| a.cmp(b))
+}
+
+/// Folds a string into hiragana without voicing marks or small kana.
+fn primary_key(s: &str) -> impl Iterator<Item = char> + '_ {
+    secondary_key(s)
+        .nfd()
+        .filter(|&c| c != '\u{3099}' && c != '\u{309A}')
+        .map(small_to_large)
+}
+
+/// Folds a string into hiragana.
+fn secondary_key(s: &str) -> impl Iterator<Item = char> + '_ {
+    s.chars().map(katakana_to_hiragana)
+}
+
+impl Zengin {
+    /// Returns all banks sorted by their kana name in gojūon order.
+    ///
+    /// This is the order Japanese users expect in lists such as dropdowns,
+    /// which sorting by code or by the UTF-8 bytes of the name does not give.
+    /// Banks with the same kana are ordered by code.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// for bank in zengin.banks_sorted_by_kana() {
+    ///     println!("{} {}", bank.kana, bank.name);
+    /// }
+    /// ```
+    pub fn banks_sorted_by_kana(&self) -> Vec<&Bank> {
+        let mut banks: Vec<_> = self.banks.values().collect();
+        banks.sort_by(|a, b| gojuon_cmp(&a.kana, &b.kana).then_with(|| a.code.cmp(&b.code)));
+        banks
+    }
+}
+
+impl Bank {
+    /// Returns all branches of this bank sorted by their kana name in gojūon order.
+    ///
+    /// Branches with the same kana are ordered by code.
+    ///
+    /// # Examples
+    /// ```
+    /// for branch in bank.branches_sorted_by_kana() {
+    ///     println!("{} {}", branch.kana, branch.name);
+    /// }
+    /// ```
+    pub fn branches_sorted_by_kana(&self) -> Vec<&Branch> {
+        let mut branches: Vec<_> = self.branches.values().collect();
+        branches.sort_by(|a, b| gojuon_cmp(&a.kana, &b.kana).then_with(|| a.code.cmp(&b.code)));
+        branches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gojuon_cmp() {
+        let mut words = vec!["かき", "ぱぱ", "がか", "ばば", "はは", "キヤ", "きゃ", "あ"];
+        words.sort_by(|a, b| gojuon_cmp(a, b));
+        assert_eq!(
+            words,
+            vec!["あ", "がか", "かき", "きゃ", "キヤ", "はは", "ばば", "ぱぱ"]
+        );
+        assert_eq!(gojuon_cmp("ミズホ", "みずほ"), "ミズホ".cmp("みずほ"));
+    }
+
+    #[test]
+    fn test_banks_sorted_by_kana() {
+        let zengin = crate::fixture();
+        let kana: Vec<_> = zengin
+            .banks_sorted_by_kana()
+            .iter()
+            .map(|bank| bank.kana.to_string())
+            .collect();
+        assert_eq!(kana.first().map(String::as_str), Some("アサヒシンキン"));
+        assert!(kana.windows(2).all(|w| gojuon_cmp(&w[0], &w[1]).is_le()));
+    }
+}
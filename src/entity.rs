@@ -0,0 +1,128 @@
+//! sea-orm entities of banks and branches, for backends that mirror the
+//! dataset into their own database.
+//!
+//! The entities map the tables that [`Zengin::to_sql`](crate::Zengin::to_sql)
+//! creates, and [`Bank`] and [`Branch`] convert into their active models, so
+//! the dataset can be inserted with the usual sea-orm calls.
+
+use crate::{Bank, Branch, Record};
+use sea_orm::ActiveValue::Set;
+
+/// The `banks` table.
+pub mod bank {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "banks")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub code: String,
+        pub name: String,
+        pub kana: String,
+        pub hira: String,
+        pub roma: String,
+    }
+
+    #[derive(Debug, Clone, Copy, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(has_many = "super::branch::Entity")]
+        Branch,
+    }
+
+    impl Related<super::branch::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Branch.def()
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+/// The `branches` table.
+pub mod branch {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "branches")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub bank_code: String,
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub code: String,
+        pub name: String,
+        pub kana: String,
+        pub hira: String,
+        pub roma: String,
+    }
+
+    #[derive(Debug, Clone, Copy, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(
+            belongs_to = "super::bank::Entity",
+            from = "Column::BankCode",
+            to = "super::bank::Column::Code"
+        )]
+        Bank,
+    }
+
+    impl Related<super::bank::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Bank.def()
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+impl From<&Bank> for bank::ActiveModel {
+    fn from(bank: &Bank) -> Self {
+        let [name, kana, hira, roma] = bank.names().map(|name| Set(name.to_string()));
+        bank::ActiveModel {
+            code: Set(bank.code().to_string()),
+            name,
+            kana,
+            hira,
+            roma,
+        }
+    }
+}
+
+/// Converts a branch, with the bank it belongs to.
+impl From<(&Bank, &Branch)> for branch::ActiveModel {
+    fn from((bank, branch): (&Bank, &Branch)) -> Self {
+        let [name, kana, hira, roma] = branch.names().map(|name| Set(name.to_string()));
+        branch::ActiveModel {
+            bank_code: Set(bank.code().to_string()),
+            code: Set(branch.code().to_string()),
+            name,
+            kana,
+            hira,
+            roma,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{EntityName, TryIntoModel};
+
+    #[test]
+    fn test_active_models() {
+        let zengin = crate::fixture();
+        let bank = &zengin["0001"];
+        let model = bank::ActiveModel::from(bank).try_into_model().unwrap();
+        assert_eq!(model.code, "0001");
+        assert_eq!(model.name, bank.name);
+
+        let model = branch::ActiveModel::from((bank, &bank["110"]))
+            .try_into_model()
+            .unwrap();
+        assert_eq!(
+            (model.bank_code.as_str(), model.code.as_str()),
+            ("0001", "110")
+        );
+        assert_eq!(model.kana, "シンジユク");
+        assert_eq!(branch::Entity.table_name(), "branches");
+    }
+}
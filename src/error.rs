@@ -0,0 +1,239 @@
+use crate::{account::AccountNumberError, BankCode, BranchCode, ParseCodeError};
+use serde::Serialize;
+use std::{error::Error, fmt, io};
+
+/// The error type for the operations of this crate.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ZenginError {
+    /// Reading a dataset failed.
+    Io(io::Error),
+    /// A dataset could not be parsed. `file` names the data file, if the
+    /// dataset was read from one.
+    Parse {
+        file: Option<String>,
+        source: serde_json::Error,
+    },
+    /// A data file is missing from the embedded dataset.
+    MissingFile(String),
+    /// A bank or branch code of a dataset is not `expected` ASCII digits.
+    InvalidCode { code: String, expected: usize },
+    /// The dataset could not be written as JSON.
+    Encode(serde_json::Error),
+    /// A search pattern is not a valid regular expression.
+    InvalidPattern(regex::Error),
+    /// A set of search patterns could not be compiled into a matcher.
+    InvalidPatternSet(aho_corasick::BuildError),
+    /// Building or querying the full-text index failed.
+    #[cfg(feature = "tantivy")]
+    Index(tantivy::TantivyError),
+    /// A MessagePack dataset could not be decoded.
+    #[cfg(feature = "msgpack")]
+    Decode(rmp_serde::decode::Error),
+}
+
+impl ZenginError {
+    pub(crate) fn parse(file: Option<&str>, source: serde_json::Error) -> ZenginError {
+        if source.is_io() {
+            return ZenginError::Io(source.into());
+        }
+        ZenginError::Parse {
+            file: file.map(str::to_string),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for ZenginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZenginError::Io(e) => write!(f, "failed to read dataset: {e}"),
+            ZenginError::Parse {
+                file: Some(file),
+                source,
+            } => write!(f, "failed to parse {file}: {source}"),
+            ZenginError::Parse { file: None, source } => {
+                write!(f, "failed to parse dataset: {source}")
+            }
+            ZenginError::MissingFile(file) => write!(f, "missing data file: {file}"),
+            ZenginError::InvalidCode { code, expected } => {
+                write!(f, "invalid code {code:?}: expected {expected} ASCII digits")
+            }
+            ZenginError::Encode(e) => write!(f, "failed to encode dataset: {e}"),
+            ZenginError::InvalidPattern(e) => write!(f, "invalid search pattern: {e}"),
+            ZenginError::InvalidPatternSet(e) => write!(f, "invalid search patterns: {e}"),
+            #[cfg(feature = "tantivy")]
+            ZenginError::Index(e) => write!(f, "full-text index failed: {e}"),
+            #[cfg(feature = "msgpack")]
+            ZenginError::Decode(e) => write!(f, "failed to decode dataset: {e}"),
+        }
+    }
+}
+
+impl Error for ZenginError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ZenginError::Io(e) => Some(e),
+            ZenginError::Parse { source, .. } => Some(source),
+            ZenginError::MissingFile(_) | ZenginError::InvalidCode { .. } => None,
+            ZenginError::Encode(e) => Some(e),
+            ZenginError::InvalidPattern(e) => Some(e),
+            ZenginError::InvalidPatternSet(e) => Some(e),
+            #[cfg(feature = "tantivy")]
+            ZenginError::Index(e) => Some(e),
+            #[cfg(feature = "msgpack")]
+            ZenginError::Decode(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for ZenginError {
+    fn from(e: io::Error) -> Self {
+        ZenginError::Io(e)
+    }
+}
+
+impl From<regex::Error> for ZenginError {
+    fn from(e: regex::Error) -> Self {
+        ZenginError::InvalidPattern(e)
+    }
+}
+
+impl From<aho_corasick::BuildError> for ZenginError {
+    fn from(e: aho_corasick::BuildError) -> Self {
+        ZenginError::InvalidPatternSet(e)
+    }
+}
+
+#[cfg(feature = "tantivy")]
+impl From<tantivy::TantivyError> for ZenginError {
+    fn from(e: tantivy::TantivyError) -> Self {
+        ZenginError::Index(e)
+    }
+}
+
+/// The reason [`Zengin::try_get_bank`](crate::Zengin::try_get_bank) or
+/// [`Zengin::try_get_branch`](crate::Zengin::try_get_branch) found nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NotFound {
+    /// The value is not a bank code, even with its leading zeros restored.
+    InvalidBankCode,
+    /// The value is not a branch code, even with its leading zeros restored.
+    InvalidBranchCode,
+    /// There is no bank with the code. If a merger recorded in the
+    /// [`history`](crate::history) module retired the code, `retired_on` and
+    /// `successor` tell when, and which bank to use instead.
+    Bank {
+        code: BankCode,
+        /// The date the code was retired, as `YYYY-MM-DD`.
+        retired_on: Option<&'static str>,
+        /// The code of the bank that succeeded it.
+        successor: Option<BankCode>,
+    },
+    /// The bank exists, but has no branch with the code.
+    Branch { bank: BankCode, branch: BranchCode },
+}
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotFound::InvalidBankCode => f.write_str("invalid bank code"),
+            NotFound::InvalidBranchCode => f.write_str("invalid branch code"),
+            NotFound::Bank {
+                code,
+                retired_on: Some(retired_on),
+                successor: Some(successor),
+            } => write!(
+                f,
+                "bank {code} was retired on {retired_on}, consider {successor}"
+            ),
+            NotFound::Bank { code, .. } => write!(f, "bank {code} not found"),
+            NotFound::Branch { bank, branch } => {
+                write!(f, "branch {branch} not found for bank {bank}")
+            }
+        }
+    }
+}
+
+impl Error for NotFound {}
+
+/// The reason a bank and branch code pair failed [`Zengin::validate`](crate::Zengin::validate),
+/// or a bank account failed [`BankAccount::validate`](crate::account::BankAccount::validate).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// The bank code is not four ASCII digits.
+    InvalidBankCode(ParseCodeError),
+    /// The branch code is not three ASCII digits.
+    InvalidBranchCode(ParseCodeError),
+    /// There is no bank with the code, and the [`history`](crate::history)
+    /// module records no merger that retired it.
+    BankNotFound(BankCode),
+    /// There is no bank with the code, because the bank merged into another
+    /// one, as recorded in the [`history`](crate::history) module.
+    BankRetired {
+        bank: BankCode,
+        /// The date the code was retired, as `YYYY-MM-DD`.
+        retired_on: &'static str,
+        /// The code of the bank that succeeded it.
+        successor: BankCode,
+    },
+    /// The bank exists, but has no branch with the code.
+    BranchNotFound { bank: BankCode, branch: BranchCode },
+    /// The account number is malformed.
+    InvalidAccountNumber(AccountNumberError),
+    /// The account holder name contains characters that transfer data cannot
+    /// carry, as listed by [`invalid_holder_chars`](crate::account::invalid_holder_chars).
+    InvalidHolderName(Vec<char>),
+    /// The account holder name has more characters, given here, than the
+    /// widest name field of transfer data, [`WIDE_NAME_LEN`](crate::field::WIDE_NAME_LEN).
+    HolderNameTooLong(usize),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::InvalidBankCode(e) | ValidationError::InvalidBranchCode(e) => e.fmt(f),
+            ValidationError::BankNotFound(bank) => write!(f, "bank {bank} not found"),
+            ValidationError::BankRetired {
+                bank,
+                retired_on,
+                successor,
+            } => write!(
+                f,
+                "bank {bank} was retired on {retired_on}, consider {successor}"
+            ),
+            ValidationError::BranchNotFound { bank, branch } => {
+                write!(f, "branch {branch} not found for bank {bank}")
+            }
+            ValidationError::InvalidAccountNumber(e) => e.fmt(f),
+            ValidationError::InvalidHolderName(chars) => {
+                write!(
+                    f,
+                    "account holder name contains invalid characters {chars:?}"
+                )
+            }
+            ValidationError::HolderNameTooLong(len) => write!(
+                f,
+                "account holder name has {len} characters, more than {}",
+                crate::field::WIDE_NAME_LEN
+            ),
+        }
+    }
+}
+
+impl Error for ValidationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ValidationError::InvalidBankCode(e) | ValidationError::InvalidBranchCode(e) => Some(e),
+            ValidationError::InvalidAccountNumber(e) => Some(e),
+            ValidationError::BankNotFound(_)
+            | ValidationError::BankRetired { .. }
+            | ValidationError::BranchNotFound { .. }
+            | ValidationError::InvalidHolderName(_)
+            | ValidationError::HolderNameTooLong(_) => None,
+        }
+    }
+}
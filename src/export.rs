@@ -0,0 +1,253 @@
+//! Exports of the dataset into formats for other tools, such as CSV for
+//! spreadsheets, YAML for the other zengin-code libraries, SQL for databases,
+//! Parquet for analytics, or bulk requests for search engines.
+
+#[cfg(feature = "arrow")]
+mod arrow;
+mod es;
+#[cfg(feature = "parquet")]
+mod parquet;
+#[cfg(feature = "polars")]
+mod polars;
+mod sql;
+mod yaml;
+
+#[cfg(any(feature = "sqlite", all(test, feature = "diesel")))]
+pub(crate) use sql::create_tables;
+pub use sql::SqlDialect;
+
+use crate::{Field, Record, Zengin};
+use std::io::{self, Write};
+
+/// A column of an export: a field of either the bank or the branch of a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Column {
+    Bank(Field),
+    Branch(Field),
+}
+
+impl Column {
+    /// Every column, bank fields first, in the order of the dataset.
+    pub const ALL: [Column; 10] = [
+        Column::Bank(Field::Code),
+        Column::Bank(Field::Name),
+        Column::Bank(Field::Kana),
+        Column::Bank(Field::Hira),
+        Column::Bank(Field::Roma),
+        Column::Branch(Field::Code),
+        Column::Branch(Field::Name),
+        Column::Branch(Field::Kana),
+        Column::Branch(Field::Hira),
+        Column::Branch(Field::Roma),
+    ];
+
+    /// Returns the name of the column in a header row, such as `bank_code`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Column::Bank(Field::Code) => "bank_code",
+            Column::Bank(Field::Name) => "bank_name",
+            Column::Bank(Field::Kana) => "bank_kana",
+            Column::Bank(Field::Hira) => "bank_hira",
+            Column::Bank(Field::Roma) => "bank_roma",
+            Column::Branch(Field::Code) => "branch_code",
+            Column::Branch(Field::Name) => "branch_name",
+            Column::Branch(Field::Kana) => "branch_kana",
+            Column::Branch(Field::Hira) => "branch_hira",
+            Column::Branch(Field::Roma) => "branch_roma",
+        }
+    }
+}
+
+/// Options for [`Zengin::export_csv`].
+///
+/// Construct it with struct update syntax, so that options added in the
+/// future take their default values:
+///
+/// ```
+/// use zengin::{export::{Column, ExportOptions}, Field};
+/// let options = ExportOptions {
+///     delimiter: '\t',
+///     columns: vec![Column::Bank(Field::Code), Column::Branch(Field::Code)],
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportOptions {
+    /// The character between the fields of a row, `,` for CSV and `\t` for
+    /// TSV.
+    pub delimiter: char,
+    /// The columns of each row, in order. Defaults to [`Column::ALL`].
+    pub columns: Vec<Column>,
+    /// Whether the first row holds the names of the columns.
+    pub header: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions {
+            delimiter: ',',
+            columns: Column::ALL.to_vec(),
+            header: true,
+        }
+    }
+}
+
+/// The columns of a table, with their values in row order.
+#[cfg(any(feature = "arrow", feature = "polars"))]
+type Table<'a> = Vec<(Column, Vec<Option<&'a str>>)>;
+
+/// Returns the columns of the banks table, and those of the branches table,
+/// which also holds the columns of their bank. A bank without any branches
+/// has a single row with null branch columns.
+#[cfg(any(feature = "arrow", feature = "polars"))]
+fn tables(zengin: &Zengin) -> (Table<'_>, Table<'_>) {
+    let mut banks: Table = Column::ALL[..5].iter().map(|&c| (c, vec![])).collect();
+    let mut branches: Table = Column::ALL.iter().map(|&c| (c, vec![])).collect();
+    for (_, bank) in zengin {
+        for (column, values) in &mut banks {
+            if let Column::Bank(field) = column {
+                values.push(Some(bank.field(*field)));
+            }
+        }
+        let mut rows: Vec<_> = bank.iter().map(|(_, branch)| Some(branch)).collect();
+        if rows.is_empty() {
+            rows.push(None);
+        }
+        for branch in rows {
+            for (column, values) in &mut branches {
+                values.push(match column {
+                    Column::Bank(field) => Some(bank.field(*field)),
+                    Column::Branch(field) => branch.map(|branch| branch.field(*field)),
+                });
+            }
+        }
+    }
+    (banks, branches)
+}
+
+/// Writes a row, quoting the fields that hold the delimiter, a quote, or a
+/// newline.
+fn write_row<'a, W: Write>(
+    writer: &mut W,
+    delimiter: char,
+    fields: impl IntoIterator<Item = &'a str>,
+) -> io::Result<()> {
+    let mut row = String::new();
+    for (i, field) in fields.into_iter().enumerate() {
+        if i > 0 {
+            row.push(delimiter);
+        }
+        if field.contains([delimiter, '"', '\n', '\r']) {
+            row.push('"');
+            row.push_str(&field.replace('"', "\"\""));
+            row.push('"');
+        } else {
+            row.push_str(field);
+        }
+    }
+    row.push('\n');
+    writer.write_all(row.as_bytes())
+}
+
+impl Zengin {
+    /// Writes the dataset as CSV, or as TSV with a tab delimiter, with one
+    /// row per branch.
+    ///
+    /// The rows are in the order of bank and branch codes. A bank without
+    /// any branches, as with the `data-banks-only` feature, has a single row
+    /// with empty branch columns. Fields are quoted only if they hold the
+    /// delimiter, a quote, or a newline, and rows end with LF.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to write the rows to.
+    /// * `options` - The delimiter, the columns, and whether to write a
+    ///   header row.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{export::ExportOptions, Zengin};
+    /// let zengin = Zengin::new().unwrap();
+    /// let file = std::fs::File::create("zengin.csv").unwrap();
+    /// zengin.export_csv(std::io::BufWriter::new(file), ExportOptions::default()).unwrap();
+    /// ```
+    pub fn export_csv<W: Write>(&self, mut writer: W, options: ExportOptions) -> io::Result<()> {
+        let ExportOptions {
+            delimiter,
+            columns,
+            header,
+        } = options;
+        if header {
+            write_row(&mut writer, delimiter, columns.iter().map(|c| c.name()))?;
+        }
+        for (_, bank) in self {
+            let mut branches: Vec<_> = bank.iter().map(|(_, branch)| Some(branch)).collect();
+            if branches.is_empty() {
+                branches.push(None);
+            }
+            for branch in branches {
+                let fields = columns.iter().map(|column| match column {
+                    Column::Bank(field) => bank.field(*field),
+                    Column::Branch(field) => branch.map_or("", |branch| branch.field(*field)),
+                });
+                write_row(&mut writer, delimiter, fields)?;
+            }
+        }
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_csv() {
+        let zengin = crate::fixture();
+        let mut csv = vec![];
+        zengin
+            .export_csv(&mut csv, ExportOptions::default())
+            .unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("bank_code,bank_name,bank_kana,bank_hira,bank_roma,branch_code,branch_name,branch_kana,branch_hira,branch_roma")
+        );
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with("0001,みずほ,ミズホ,みずほ,mizuho,001,東京営業部,"));
+
+        let mut tsv = vec![];
+        let options = ExportOptions {
+            delimiter: '\t',
+            columns: vec![Column::Bank(Field::Code), Column::Branch(Field::Name)],
+            header: false,
+        };
+        zengin.export_csv(&mut tsv, options).unwrap();
+        let tsv = String::from_utf8(tsv).unwrap();
+        assert!(tsv.starts_with("0001\t東京営業部\n"));
+        assert!(tsv.contains("0009\t"));
+    }
+
+    #[test]
+    fn test_quoting() {
+        let zengin = Zengin::from_combined_json(
+            r#"{"0001":{"code":"0001","name":"みずほ,\"A\"","kana":"ミズホ","hira":"みずほ","roma":"mizuho"}}"#,
+        )
+        .unwrap();
+        let mut csv = vec![];
+        let options = ExportOptions {
+            columns: vec![Column::Bank(Field::Name), Column::Branch(Field::Code)],
+            header: false,
+            ..Default::default()
+        };
+        zengin.export_csv(&mut csv, options).unwrap();
+        assert_eq!(String::from_utf8(csv).unwrap(), "\"みずほ,\"\"A\"\"\",\n");
+    }
+}
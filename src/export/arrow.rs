@@ -0,0 +1,82 @@
+//! Arrow record batches of the dataset.
+
+use super::{tables, Column};
+use crate::Zengin;
+use arrow_array::{ArrayRef, RecordBatch, StringArray};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+use std::sync::Arc;
+
+/// Returns a record batch of string columns, where the branch columns may be
+/// null.
+fn string_batch(columns: Vec<(Column, Vec<Option<&str>>)>) -> Result<RecordBatch, ArrowError> {
+    let fields: Vec<_> = columns
+        .iter()
+        .map(|(column, _)| {
+            Field::new(
+                column.name(),
+                DataType::Utf8,
+                matches!(column, Column::Branch(_)),
+            )
+        })
+        .collect();
+    let arrays = columns
+        .into_iter()
+        .map(|(_, values)| Arc::new(StringArray::from(values)) as ArrayRef)
+        .collect();
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+}
+
+impl Zengin {
+    /// Returns the dataset as Arrow record batches: one of the banks, and
+    /// one of the branches that also holds the columns of their bank, so that
+    /// it can be queried without a join.
+    ///
+    /// The columns are named as [`Column::name`] names them, such as
+    /// `bank_code` and `branch_name`, and hold UTF-8 strings. Rows are in the
+    /// order of bank and branch codes. A bank without any branches, as with
+    /// the `data-banks-only` feature, has a single row in the branches batch
+    /// with null branch columns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a batch cannot be built, which does not happen
+    /// with a well-formed dataset.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let (banks, branches) = zengin.to_record_batches().unwrap();
+    /// println!("{} banks, {} branches", banks.num_rows(), branches.num_rows());
+    /// ```
+    pub fn to_record_batches(&self) -> Result<(RecordBatch, RecordBatch), ArrowError> {
+        let (banks, branches) = tables(self);
+        Ok((string_batch(banks)?, string_batch(branches)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{cast::AsArray, Array};
+
+    #[test]
+    fn test_to_record_batches() {
+        let zengin = crate::fixture();
+        let (banks, branches) = zengin.to_record_batches().unwrap();
+        assert_eq!(banks.num_rows(), zengin.all_banks().len());
+        assert_eq!(banks.num_columns(), 5);
+        assert!(!banks.schema().field(0).is_nullable());
+        assert_eq!(banks.column(1).as_string::<i32>().value(0), "みずほ");
+
+        let names: Vec<_> = branches
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect();
+        assert_eq!(names, Column::ALL.map(|column| column.name().to_string()));
+        let codes = branches.column(5).as_string::<i32>();
+        assert_eq!((codes.value(0), codes.null_count()), ("001", 0));
+    }
+}
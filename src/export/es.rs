@@ -0,0 +1,146 @@
+//! Elasticsearch and OpenSearch bulk requests.
+
+use super::Column;
+use crate::{normalize::normalize, Bank, Branch, Record, Zengin};
+use serde_json::{json, Map, Value};
+use std::io::{self, Write};
+
+/// Returns the document of a branch, or of a bank without any branches.
+fn document(bank: &Bank, branch: Option<&Branch>) -> Value {
+    let mut doc = Map::new();
+    for column in Column::ALL {
+        let value = match column {
+            Column::Bank(field) => bank.field(field),
+            Column::Branch(field) => match branch {
+                Some(branch) => branch.field(field),
+                None => continue,
+            },
+        };
+        doc.insert(column.name().to_string(), value.into());
+    }
+    let mut records: Vec<&dyn Record> = vec![bank];
+    records.extend(branch.map(|branch| branch as &dyn Record));
+    let full_name: Vec<_> = records.iter().map(|record| record.names()[0]).collect();
+    doc.insert("full_name".to_string(), full_name.join(" ").into());
+    let normalized: Vec<_> = records
+        .iter()
+        .flat_map(|record| record.names().map(normalize))
+        .collect();
+    doc.insert("normalized".to_string(), normalized.join(" ").into());
+    let mut suggest: Vec<&str> = vec![];
+    for name in records.iter().flat_map(|record| record.names()) {
+        if !suggest.contains(&name) {
+            suggest.push(name);
+        }
+    }
+    doc.insert("suggest".to_string(), json!(suggest));
+    Value::Object(doc)
+}
+
+impl Zengin {
+    /// Writes the dataset as the NDJSON body of an Elasticsearch or
+    /// OpenSearch `_bulk` request, which indexes one document per branch.
+    ///
+    /// Each document holds the fields of the branch and of its bank, named
+    /// as the columns of [`export_csv`](Zengin::export_csv), such as
+    /// `bank_name` and `branch_kana`, and has the ID `<bank>-<branch>`, such
+    /// as `0001-110`. A bank without any branches has a single document with
+    /// the ID of its code and no branch fields. Documents also hold fields
+    /// for analyzers:
+    ///
+    /// * `full_name` - The bank and branch names, such as `みずほ 新宿`, for
+    ///   a `kuromoji` or n-gram analyzer.
+    /// * `normalized` - Every name of the bank and branch, normalized with
+    ///   [`normalize`](crate::normalize::normalize), for a `keyword` or
+    ///   whitespace analyzer that matches regardless of script and width.
+    /// * `suggest` - The distinct names of the bank and branch, as the inputs
+    ///   of a `completion` field.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to write the request body to.
+    /// * `index_name` - The index to put the documents into.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let file = std::fs::File::create("bulk.ndjson").unwrap();
+    /// zengin.to_es_bulk(std::io::BufWriter::new(file), "zengin").unwrap();
+    /// // curl -H 'Content-Type: application/x-ndjson' --data-binary @bulk.ndjson localhost:9200/_bulk
+    /// ```
+    pub fn to_es_bulk<W: Write>(&self, mut writer: W, index_name: &str) -> io::Result<()> {
+        for (_, bank) in self {
+            let mut branches: Vec<_> = bank.iter().map(|(_, branch)| Some(branch)).collect();
+            if branches.is_empty() {
+                branches.push(None);
+            }
+            for branch in branches {
+                let id = match branch {
+                    Some(branch) => format!("{}-{}", bank.code(), branch.code()),
+                    None => bank.code().to_string(),
+                };
+                let action = json!({ "index": { "_index": index_name, "_id": id } });
+                serde_json::to_writer(&mut writer, &action)?;
+                writer.write_all(b"\n")?;
+                serde_json::to_writer(&mut writer, &document(bank, branch))?;
+                writer.write_all(b"\n")?;
+            }
+        }
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_es_bulk() {
+        let zengin = Zengin::from_combined_json(
+            r#"{"0001":{"code":"0001","name":"みずほ","kana":"ミズホ","hira":"みずほ","roma":"mizuho","branches":{
+                "110":{"code":"110","name":"新宿","kana":"シンジユク","hira":"しんじゆく","roma":"shinjiyuku"}
+            }},"0005":{"code":"0005","name":"三菱ＵＦＪ","kana":"ミツビシユ－エフジエイ","hira":"みつびしゆーえふじえい","roma":"mitsubishiyufujiei"}}"#,
+        )
+        .unwrap();
+        let mut bulk = vec![];
+        zengin.to_es_bulk(&mut bulk, "banks").unwrap();
+        let lines: Vec<Value> = String::from_utf8(bulk)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            json!({ "index": { "_index": "banks", "_id": "0001-110" } })
+        );
+        assert_eq!(lines[1]["bank_name"], "みずほ");
+        assert_eq!(lines[1]["branch_kana"], "シンジユク");
+        assert_eq!(lines[1]["full_name"], "みずほ 新宿");
+        assert_eq!(
+            lines[1]["normalized"],
+            "みずほ みずほ みずほ mizuho 新宿 しんじゆく しんじゆく shinjiyuku"
+        );
+        assert_eq!(
+            lines[1]["suggest"],
+            json!([
+                "みずほ",
+                "ミズホ",
+                "mizuho",
+                "新宿",
+                "シンジユク",
+                "しんじゆく",
+                "shinjiyuku"
+            ])
+        );
+
+        assert_eq!(lines[2]["index"]["_id"], "0005");
+        assert_eq!(lines[3]["full_name"], "三菱ＵＦＪ");
+        assert!(lines[3].get("branch_code").is_none());
+    }
+}
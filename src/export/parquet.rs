@@ -0,0 +1,92 @@
+//! Parquet files of the dataset, for analytics tools.
+
+use crate::Zengin;
+use parquet::{
+    arrow::ArrowWriter, basic::Compression, errors::Result, file::properties::WriterProperties,
+};
+use std::{
+    fs::{self, File},
+    path::Path,
+};
+
+impl Zengin {
+    /// Writes the dataset as Parquet files: `banks.parquet` with a row per
+    /// bank, and `branches.parquet` with a row per branch that also holds
+    /// the columns of its bank, so that it can be queried without a join.
+    ///
+    /// The files hold the record batches of [`Zengin::to_record_batches`],
+    /// compressed with Snappy.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory to write the files into. It is created if
+    ///   needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created or a file cannot
+    /// be written.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// zengin.export_parquet("parquet").unwrap();
+    /// ```
+    pub fn export_parquet<P: AsRef<Path>>(&self, dir: P) -> Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let (banks, branches) = self.to_record_batches()?;
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        for (name, batch) in [("banks.parquet", banks), ("branches.parquet", branches)] {
+            let file = File::create(dir.join(name))?;
+            let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(properties.clone()))?;
+            writer.write(&batch)?;
+            writer.close()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{cast::AsArray, Array, RecordBatch};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    #[test]
+    fn test_export_parquet() {
+        let zengin = crate::fixture();
+        let dir = std::env::temp_dir().join(format!("zengin-parquet-{}", std::process::id()));
+        zengin.export_parquet(&dir).unwrap();
+
+        let file = File::open(dir.join("branches.parquet")).unwrap();
+        let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<_> = reader.map(|batch| batch.unwrap()).collect();
+        let batch = &batches[0];
+        assert_eq!(batch.schema().field(5).name(), "branch_code");
+        assert!(batch.schema().field(5).is_nullable());
+        assert_eq!(batch.column(0).as_string::<i32>().value(0), "0001");
+        assert_eq!(batch.column(6).as_string::<i32>().value(0), "東京営業部");
+        let rows: usize = batches.iter().map(RecordBatch::num_rows).sum();
+        let branches: usize = zengin
+            .iter()
+            .map(|(_, bank)| bank.iter().len().max(1))
+            .sum();
+        assert_eq!(rows, branches);
+        assert_eq!(batch.column(5).null_count(), 0);
+
+        let file = File::open(dir.join("banks.parquet")).unwrap();
+        let metadata = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+        assert_eq!(
+            metadata.metadata().file_metadata().num_rows() as usize,
+            zengin.all_banks().len()
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
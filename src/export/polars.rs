@@ -0,0 +1,59 @@
+//! polars DataFrames of the dataset.
+
+use super::{tables, Table};
+use crate::Zengin;
+use polars::prelude::{DataFrame, NamedFrom, PolarsResult, Series};
+
+/// Returns a DataFrame of string columns.
+fn data_frame(table: Table<'_>) -> PolarsResult<DataFrame> {
+    let columns = table
+        .into_iter()
+        .map(|(column, values)| Series::new(column.name().into(), values).into())
+        .collect();
+    DataFrame::new(columns)
+}
+
+impl Zengin {
+    /// Returns the dataset as polars DataFrames: one of the banks, and one of
+    /// the branches that also holds the columns of their bank, for joins
+    /// against transaction data on `bank_code` and `branch_code`.
+    ///
+    /// The DataFrames have the columns and rows of the record batches of
+    /// [`Zengin::to_record_batches`], as string columns named as
+    /// [`Column::name`](super::Column::name) names them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a DataFrame cannot be built, which does not happen
+    /// with a well-formed dataset.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let (banks, branches) = zengin.to_polars().unwrap();
+    /// println!("{}", branches.head(Some(5)));
+    /// ```
+    pub fn to_polars(&self) -> PolarsResult<(DataFrame, DataFrame)> {
+        let (banks, branches) = tables(self);
+        Ok((data_frame(banks)?, data_frame(branches)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::export::Column;
+
+    #[test]
+    fn test_to_polars() {
+        let zengin = crate::fixture();
+        let (banks, branches) = zengin.to_polars().unwrap();
+        assert_eq!(banks.shape(), (zengin.all_banks().len(), 5));
+        assert_eq!(banks.get_column_names()[0].as_str(), "bank_code");
+        assert_eq!(branches.width(), Column::ALL.len());
+
+        let names = branches.column("branch_name").unwrap().str().unwrap();
+        assert_eq!(names.get(0), Some("東京営業部"));
+        assert_eq!(names.null_count(), 0);
+    }
+}
@@ -0,0 +1,228 @@
+//! SQL dumps of the dataset.
+
+use crate::{Bank, Branch, Record, Zengin};
+use std::fmt;
+
+/// The number of rows per `INSERT` statement.
+const ROWS_PER_INSERT: usize = 500;
+
+/// A dialect of SQL, for [`Zengin::to_sql`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SqlDialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl SqlDialect {
+    /// Returns the dialect's name, such as `PostgreSQL`.
+    pub fn name(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "PostgreSQL",
+            SqlDialect::MySql => "MySQL",
+            SqlDialect::Sqlite => "SQLite",
+        }
+    }
+
+    /// Returns a string as a literal of the dialect. MySQL also treats
+    /// backslashes as escapes by default.
+    fn literal(self, s: &str) -> String {
+        let escaped = s.replace('\'', "''");
+        match self {
+            SqlDialect::MySql => format!("'{}'", escaped.replace('\\', "\\\\")),
+            _ => format!("'{escaped}'"),
+        }
+    }
+}
+
+impl fmt::Display for SqlDialect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Returns the statements that create the `banks` and `branches` tables and
+/// their indexes, if they do not exist yet.
+pub(crate) fn create_tables(dialect: SqlDialect) -> String {
+    let (text, options) = match dialect {
+        SqlDialect::MySql => ("VARCHAR(255)", " DEFAULT CHARSET=utf8mb4"),
+        _ => ("TEXT", ""),
+    };
+    // MySQL has no IF NOT EXISTS for indexes, so they are created with the
+    // table there.
+    let (inline_indexes, indexes) = match dialect {
+        SqlDialect::MySql => (
+            ",\n  INDEX branches_name (name),\n  INDEX branches_kana (kana)",
+            "",
+        ),
+        _ => (
+            "",
+            "CREATE INDEX IF NOT EXISTS branches_name ON branches (name);
+CREATE INDEX IF NOT EXISTS branches_kana ON branches (kana);
+",
+        ),
+    };
+    format!(
+        "CREATE TABLE IF NOT EXISTS banks (
+  code CHAR(4) NOT NULL PRIMARY KEY,
+  name {text} NOT NULL,
+  kana {text} NOT NULL,
+  hira {text} NOT NULL,
+  roma {text} NOT NULL
+){options};
+CREATE TABLE IF NOT EXISTS branches (
+  bank_code CHAR(4) NOT NULL REFERENCES banks (code),
+  code CHAR(3) NOT NULL,
+  name {text} NOT NULL,
+  kana {text} NOT NULL,
+  hira {text} NOT NULL,
+  roma {text} NOT NULL,
+  PRIMARY KEY (bank_code, code){inline_indexes}
+){options};
+{indexes}"
+    )
+}
+
+/// Returns the values of a record, in the order of the columns of its table.
+fn values(dialect: SqlDialect, bank: &Bank, branch: Option<&Branch>) -> String {
+    let mut values = vec![dialect.literal(bank.code())];
+    let names = match branch {
+        Some(branch) => {
+            values.push(dialect.literal(branch.code()));
+            branch.names()
+        }
+        None => bank.names(),
+    };
+    values.extend(names.map(|name| dialect.literal(name)));
+    format!("({})", values.join(", "))
+}
+
+/// Appends `INSERT` statements of rows into a table, a few hundred rows at a
+/// time. Rows that are already in the table are skipped, or replaced in
+/// SQLite, so that the dump can be loaded again.
+fn push_inserts(
+    sql: &mut String,
+    dialect: SqlDialect,
+    table: &str,
+    columns: &str,
+    rows: &[String],
+) {
+    let (insert, on_conflict) = match dialect {
+        SqlDialect::Postgres => ("INSERT", "\nON CONFLICT DO NOTHING"),
+        SqlDialect::MySql => ("INSERT IGNORE", ""),
+        SqlDialect::Sqlite => ("INSERT OR REPLACE", ""),
+    };
+    for chunk in rows.chunks(ROWS_PER_INSERT) {
+        sql.push_str(&format!("{insert} INTO {table} ({columns}) VALUES\n"));
+        sql.push_str(&chunk.join(",\n"));
+        sql.push_str(on_conflict);
+        sql.push_str(";\n");
+    }
+}
+
+impl Zengin {
+    /// Returns a SQL dump of the dataset, which creates the `banks` and
+    /// `branches` tables and inserts every bank and branch in code order.
+    ///
+    /// The tables are only created if they do not exist yet, and the inserts
+    /// run in a single transaction and skip the rows that already exist, or
+    /// replace them in SQLite, so the dump can be loaded more than once.
+    /// Branches reference their bank by `bank_code`, and are indexed by name
+    /// and kana.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect of the database the dump is for.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{export::SqlDialect, Zengin};
+    /// let zengin = Zengin::new().unwrap();
+    /// std::fs::write("zengin.sql", zengin.to_sql(SqlDialect::Postgres)).unwrap();
+    /// ```
+    pub fn to_sql(&self, dialect: SqlDialect) -> String {
+        let mut sql = create_tables(dialect);
+        sql.push_str(match dialect {
+            SqlDialect::MySql => "START TRANSACTION;\n",
+            _ => "BEGIN;\n",
+        });
+        let banks: Vec<_> = self
+            .iter()
+            .map(|(_, bank)| values(dialect, bank, None))
+            .collect();
+        push_inserts(
+            &mut sql,
+            dialect,
+            "banks",
+            "code, name, kana, hira, roma",
+            &banks,
+        );
+        let branches: Vec<_> = self
+            .iter()
+            .flat_map(|(_, bank)| {
+                bank.iter()
+                    .map(move |(_, branch)| values(dialect, bank, Some(branch)))
+            })
+            .collect();
+        push_inserts(
+            &mut sql,
+            dialect,
+            "branches",
+            "bank_code, code, name, kana, hira, roma",
+            &branches,
+        );
+        sql.push_str("COMMIT;\n");
+        sql
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_sql() {
+        let zengin = Zengin::from_combined_json(
+            r#"{"0001":{"code":"0001","name":"み'ず\\ほ","kana":"ミズホ","hira":"みずほ","roma":"mizuho","branches":{
+                "110":{"code":"110","name":"新宿","kana":"シンジユク","hira":"しんじゆく","roma":"shinjiyuku"}
+            }}}"#,
+        )
+        .unwrap();
+        let sql = zengin.to_sql(SqlDialect::Postgres);
+        assert!(sql.starts_with("CREATE TABLE IF NOT EXISTS banks (\n  code CHAR(4)"));
+        assert!(sql.contains("CREATE INDEX IF NOT EXISTS branches_name"));
+        assert!(sql.contains(
+            "INSERT INTO banks (code, name, kana, hira, roma) VALUES\n\
+             ('0001', 'み''ず\\ほ', 'ミズホ', 'みずほ', 'mizuho')\n\
+             ON CONFLICT DO NOTHING;\n"
+        ));
+        assert!(sql.contains(
+            "('0001', '110', '新宿', 'シンジユク', 'しんじゆく', 'shinjiyuku')\n\
+             ON CONFLICT DO NOTHING;\nCOMMIT;\n"
+        ));
+
+        let sql = zengin.to_sql(SqlDialect::MySql);
+        assert!(sql.contains("name VARCHAR(255) NOT NULL"));
+        assert!(sql.contains(") DEFAULT CHARSET=utf8mb4;"));
+        assert!(sql.contains("  INDEX branches_name (name),\n"));
+        assert!(!sql.contains("CREATE INDEX"));
+        assert!(sql.contains("INSERT IGNORE INTO banks"));
+        assert!(sql.contains("'み''ず\\\\ほ'"));
+        assert!(sql.contains("START TRANSACTION;"));
+
+        let sql = zengin.to_sql(SqlDialect::Sqlite);
+        assert!(sql.contains("INSERT OR REPLACE INTO branches"));
+    }
+
+    #[test]
+    fn test_to_sql_twice() {
+        use diesel::{connection::SimpleConnection, sqlite::SqliteConnection, Connection};
+
+        let zengin = crate::fixture();
+        let sql = zengin.to_sql(SqlDialect::Sqlite);
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.batch_execute(&sql).unwrap();
+        conn.batch_execute(&sql).unwrap();
+    }
+}
@@ -0,0 +1,174 @@
+//! YAML in the layout of the zengin-code source data.
+
+use crate::{Bank, Record, Zengin};
+use std::{fmt::Write as _, fs, io, path::Path};
+
+/// Returns a string as a double-quoted YAML scalar, so that codes such as
+/// `0001` stay strings.
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{:04X}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Appends the entry of a record to a map, indented by `indent`.
+fn write_entry(yaml: &mut String, indent: &str, code: &str, record: &impl Record) {
+    let [name, kana, hira, roma] = record.names();
+    let _ = writeln!(yaml, "{indent}{}:", quote(code));
+    for (key, value) in [
+        ("code", record.code()),
+        ("name", name),
+        ("kana", kana),
+        ("hira", hira),
+        ("roma", roma),
+    ] {
+        let _ = writeln!(yaml, "{indent}  {key}: {}", quote(value));
+    }
+}
+
+/// Writes a map of codes to records, in code order.
+fn write_map<'a, R: Record + 'a>(records: impl Iterator<Item = (&'a str, &'a R)>) -> String {
+    let mut yaml = String::new();
+    for (code, record) in records {
+        write_entry(&mut yaml, "", code, record);
+    }
+    if yaml.is_empty() {
+        yaml.push_str("{}\n");
+    }
+    yaml
+}
+
+impl Zengin {
+    /// Returns the banks as YAML in the layout of `data/banks.yml` of the
+    /// zengin-code source data: a map of bank codes to the code, name, kana,
+    /// hira, and roma of each bank, without their branches.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// std::fs::write("banks.yml", zengin.banks_yaml()).unwrap();
+    /// ```
+    pub fn banks_yaml(&self) -> String {
+        write_map(self.iter())
+    }
+
+    /// Returns the dataset as a single YAML document, in the layout of
+    /// [`banks_yaml`](Zengin::banks_yaml) with the branches of each bank in
+    /// a nested `branches` map, as in [`to_json`](Zengin::to_json).
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// std::fs::write("zengin.yml", zengin.to_yaml()).unwrap();
+    /// ```
+    pub fn to_yaml(&self) -> String {
+        let mut yaml = String::new();
+        for (code, bank) in self {
+            write_entry(&mut yaml, "", code, bank);
+            if bank.iter().next().is_none() {
+                yaml.push_str("  branches: {}\n");
+                continue;
+            }
+            yaml.push_str("  branches:\n");
+            for (code, branch) in bank {
+                write_entry(&mut yaml, "    ", code, branch);
+            }
+        }
+        if yaml.is_empty() {
+            yaml.push_str("{}\n");
+        }
+        yaml
+    }
+
+    /// Writes the dataset as YAML files in the layout of the zengin-code
+    /// source data: `banks.yml` with the banks, and `branches/<code>.yml`
+    /// with the branches of each bank.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory to write the files into. It and its
+    ///   `branches` directory are created if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory cannot be created or a file cannot be
+    /// written.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// zengin.export_yaml("data").unwrap();
+    /// ```
+    pub fn export_yaml<P: AsRef<Path>>(&self, dir: P) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir.join("branches"))?;
+        fs::write(dir.join("banks.yml"), self.banks_yaml())?;
+        for (code, bank) in self {
+            let path = dir.join("branches").join(format!("{code}.yml"));
+            fs::write(path, bank.branches_yaml())?;
+        }
+        Ok(())
+    }
+}
+
+impl Bank {
+    /// Returns the branches of this bank as YAML in the layout of
+    /// `data/branches/<code>.yml` of the zengin-code source data.
+    ///
+    /// # Examples
+    /// ```
+    /// println!("{}", bank.branches_yaml());
+    /// ```
+    pub fn branches_yaml(&self) -> String {
+        write_map(self.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yaml() {
+        let zengin = Zengin::from_combined_json(
+            r#"{"0001":{"code":"0001","name":"み\"ず\\ほ","kana":"ミズホ","hira":"みずほ","roma":"mizuho","branches":{
+                "110":{"code":"110","name":"新宿","kana":"シンジユク","hira":"しんじゆく","roma":"shinjiyuku"}
+            }}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            zengin.banks_yaml(),
+            "\"0001\":\n  code: \"0001\"\n  name: \"み\\\"ず\\\\ほ\"\n  kana: \"ミズホ\"\n  hira: \"みずほ\"\n  roma: \"mizuho\"\n"
+        );
+        assert!(zengin["0001"]
+            .branches_yaml()
+            .starts_with("\"110\":\n  code: \"110\"\n  name: \"新宿\"\n"));
+
+        assert!(zengin.to_yaml().starts_with(&zengin.banks_yaml()));
+        assert!(zengin.to_yaml().ends_with(
+            "  branches:\n    \"110\":\n      code: \"110\"\n      name: \"新宿\"\n      kana: \"シンジユク\"\n      hira: \"しんじゆく\"\n      roma: \"shinjiyuku\"\n"
+        ));
+
+        let dir = std::env::temp_dir().join(format!("zengin-yaml-{}", std::process::id()));
+        zengin.export_yaml(&dir).unwrap();
+        let branches = fs::read_to_string(dir.join("branches/0001.yml")).unwrap();
+        assert_eq!(branches, zengin["0001"].branches_yaml());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
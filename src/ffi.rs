@@ -0,0 +1,235 @@
+//! A C API over the dataset, for embedding it in programs in other languages.
+//!
+//! A [`Zengin`] is created with [`zengin_new`] and freed with
+//! [`zengin_free`]. Lookups and searches return banks and branches as JSON
+//! strings, with `code`, `name`, `kana`, `hira`, and `roma` fields, which the
+//! caller frees with [`zengin_string_free`]. Every string passed in or out is
+//! NUL-terminated UTF-8.
+//!
+//! `include/zengin.h` declares these functions. Build the library with
+//! `cargo rustc --release --lib --features ffi --crate-type cdylib` (or
+//! `staticlib`), and regenerate the header after changing this module with
+//! `cbindgen --config cbindgen.toml --output include/zengin.h`.
+
+use crate::{Record, Zengin};
+use serde_json::{json, Value};
+use std::{
+    ffi::{c_char, CStr, CString},
+    ptr,
+};
+
+/// Returns a bank or branch as a JSON object.
+fn to_json(record: &impl Record) -> Value {
+    let [name, kana, hira, roma] = record.names();
+    json!({ "code": record.code(), "name": name, "kana": kana, "hira": hira, "roma": roma })
+}
+
+/// Returns a JSON value as a string owned by the caller.
+fn into_c_string(value: Value) -> *mut c_char {
+    // Serialized JSON escapes control characters, so it holds no NUL.
+    CString::new(value.to_string()).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Returns a string passed by the caller, or `None` if it is null or not
+/// UTF-8.
+///
+/// # Safety
+///
+/// `s` must be null or point to a NUL-terminated string.
+unsafe fn from_c_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok()
+}
+
+/// Loads the embedded dataset.
+///
+/// Returns null if the dataset cannot be loaded. Free the returned handle
+/// with [`zengin_free`].
+#[no_mangle]
+pub extern "C" fn zengin_new() -> *mut Zengin {
+    Zengin::new().map_or(ptr::null_mut(), |zengin| Box::into_raw(Box::new(zengin)))
+}
+
+/// Frees a dataset loaded by [`zengin_new`]. Null is ignored.
+///
+/// # Safety
+///
+/// `zengin` must be null or a handle returned by [`zengin_new`] that has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn zengin_free(zengin: *mut Zengin) {
+    if !zengin.is_null() {
+        drop(Box::from_raw(zengin));
+    }
+}
+
+/// Frees a string returned by this API. Null is ignored.
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by this API that has not been freed
+/// yet.
+#[no_mangle]
+pub unsafe extern "C" fn zengin_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Looks up a bank by its code, such as `"0001"`.
+///
+/// Returns the bank as a JSON object, or null if there is no bank with the
+/// code. Stripped leading zeros are restored, so `"1"` finds bank `0001`.
+///
+/// # Safety
+///
+/// `zengin` must be a valid handle, and `code` must be null or point to a
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn zengin_bank(zengin: *const Zengin, code: *const c_char) -> *mut c_char {
+    let (Some(zengin), Some(code)) = (zengin.as_ref(), from_c_str(code)) else {
+        return ptr::null_mut();
+    };
+    zengin
+        .get_bank(code)
+        .map_or(ptr::null_mut(), |bank| into_c_string(to_json(bank)))
+}
+
+/// Looks up a branch by the code of its bank and its own code.
+///
+/// Returns the branch as a JSON object, or null if there is no such branch.
+///
+/// # Safety
+///
+/// `zengin` must be a valid handle, and `bank_code` and `code` must be null
+/// or point to NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn zengin_branch(
+    zengin: *const Zengin,
+    bank_code: *const c_char,
+    code: *const c_char,
+) -> *mut c_char {
+    let (Some(zengin), Some(bank_code), Some(code)) =
+        (zengin.as_ref(), from_c_str(bank_code), from_c_str(code))
+    else {
+        return ptr::null_mut();
+    };
+    zengin
+        .get_branch(bank_code, code)
+        .map_or(ptr::null_mut(), |(_, branch)| {
+            into_c_string(to_json(branch))
+        })
+}
+
+/// Searches banks by a query matched against all of their names.
+///
+/// Returns a JSON array of at most `limit` banks, most relevant first, or of
+/// all matching banks if `limit` is 0. Returns null if an argument is
+/// invalid.
+///
+/// # Safety
+///
+/// `zengin` must be a valid handle, and `query` must be null or point to a
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn zengin_search_banks(
+    zengin: *const Zengin,
+    query: *const c_char,
+    limit: usize,
+) -> *mut c_char {
+    let (Some(zengin), Some(query)) = (zengin.as_ref(), from_c_str(query)) else {
+        return ptr::null_mut();
+    };
+    let limit = if limit == 0 { usize::MAX } else { limit };
+    let banks = zengin
+        .find_banks_ranked(query)
+        .into_iter()
+        .take(limit)
+        .map(|(_, bank)| to_json(bank))
+        .collect();
+    into_c_string(Value::Array(banks))
+}
+
+/// Searches the branches of a bank by a query matched against all of their
+/// names.
+///
+/// Returns a JSON array of at most `limit` branches, most relevant first, or
+/// of all matching branches if `limit` is 0. Returns null if there is no bank
+/// with the code or an argument is invalid.
+///
+/// # Safety
+///
+/// `zengin` must be a valid handle, and `bank_code` and `query` must be null
+/// or point to NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn zengin_search_branches(
+    zengin: *const Zengin,
+    bank_code: *const c_char,
+    query: *const c_char,
+    limit: usize,
+) -> *mut c_char {
+    let (Some(zengin), Some(bank_code), Some(query)) =
+        (zengin.as_ref(), from_c_str(bank_code), from_c_str(query))
+    else {
+        return ptr::null_mut();
+    };
+    let Some(bank) = zengin.get_bank(bank_code) else {
+        return ptr::null_mut();
+    };
+    let limit = if limit == 0 { usize::MAX } else { limit };
+    let branches = bank
+        .find_branches_ranked(query)
+        .into_iter()
+        .take(limit)
+        .map(|(_, branch)| to_json(branch))
+        .collect();
+    into_c_string(Value::Array(branches))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Takes a string returned by the API, and parses it as JSON.
+    unsafe fn take_json(s: *mut c_char) -> Option<Value> {
+        if s.is_null() {
+            return None;
+        }
+        let json = serde_json::from_str(CStr::from_ptr(s).to_str().unwrap()).unwrap();
+        zengin_string_free(s);
+        Some(json)
+    }
+
+    #[test]
+    fn test_ffi() {
+        unsafe {
+            let loaded = zengin_new();
+            assert!(!loaded.is_null());
+            zengin_free(loaded);
+
+            let zengin = Box::into_raw(Box::new(crate::fixture()));
+
+            let bank = take_json(zengin_bank(zengin, c"1".as_ptr())).unwrap();
+            assert_eq!(bank["code"], "0001");
+            assert_eq!(take_json(zengin_bank(zengin, c"9999".as_ptr())), None);
+            assert_eq!(take_json(zengin_bank(zengin, ptr::null())), None);
+
+            let branch = take_json(zengin_branch(zengin, c"0001".as_ptr(), c"110".as_ptr()));
+            assert_eq!(branch.unwrap()["name"], "新宿");
+
+            let banks = take_json(zengin_search_banks(zengin, c"みずほ".as_ptr(), 1)).unwrap();
+            assert_eq!(banks, json!([bank]));
+            let branches = take_json(zengin_search_branches(
+                zengin,
+                c"0001".as_ptr(),
+                c"shinjuku".as_ptr(),
+                0,
+            ));
+            assert_eq!(branches.unwrap()[0]["code"], "110");
+
+            zengin_free(zengin);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Fields of Zengin transfer data.
+//!
+//! Transfer data stores names in fixed-width fields of half-width katakana,
+//! so names have to be converted by [`to_halfwidth_kana`] and cut to the
+//! length of their field before they are written. The lengths of the fields
+//! are the ones of the Zengin record formats, counted in half-width
+//! characters, where a voicing mark such as `ﾞ` takes a character of its own.
+
+use crate::{normalize::to_halfwidth_kana, Bank, Branch};
+
+/// The length of the bank name field, in half-width characters.
+pub const BANK_KANA_LEN: usize = 15;
+
+/// The length of the branch name field, in half-width characters.
+pub const BRANCH_KANA_LEN: usize = 15;
+
+/// The length of the account holder name field of recipients and payers,
+/// in half-width characters.
+pub const HOLDER_NAME_LEN: usize = 30;
+
+/// The length of the requester name field of header records, in half-width
+/// characters.
+pub const REQUESTER_NAME_LEN: usize = 40;
+
+/// The length of the name fields of the wider formats, such as the remitter
+/// name (振込依頼人名) of 振込入金通知 and 入出金取引明細 data, in half-width
+/// characters. No record format carries a longer name.
+pub const WIDE_NAME_LEN: usize = 48;
+
+/// The length of the customer code fields, in digits.
+pub const CUSTOMER_CODE_LEN: usize = 10;
+
+/// The length of the EDI information field, in half-width characters.
+pub const EDI_INFO_LEN: usize = 20;
+
+/// The length of the amount field, in digits.
+pub const AMOUNT_LEN: usize = 10;
+
+/// The length of a record, in bytes.
+pub const RECORD_LEN: usize = 120;
+
+/// Cuts a string of half-width characters to fit a field of `len`
+/// characters.
+///
+/// The string is cut on character boundaries, and a voicing mark is never
+/// separated from its kana: if the cut would fall between them, the kana is
+/// dropped as well, so the field may end up one character shorter than `len`.
+///
+/// # Arguments
+///
+/// * `s` - A string slice that holds the value of the field.
+/// * `len` - The length of the field, such as [`HOLDER_NAME_LEN`].
+///
+/// # Examples
+/// ```
+/// use zengin::field::truncate_to_field;
+/// assert_eq!(truncate_to_field("ﾐｽﾞﾎ", 2), "ﾐ");
+/// assert_eq!(truncate_to_field("ﾐｽﾞﾎ", 3), "ﾐｽﾞ");
+/// ```
+pub fn truncate_to_field(s: &str, len: usize) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    if chars.len() <= len {
+        return s.to_string();
+    }
+    let mut end = len;
+    if matches!(chars[end], 'ﾞ' | 'ﾟ') {
+        end = end.saturating_sub(1);
+    }
+    chars[..end].iter().collect()
+}
+
+impl Bank {
+    /// Returns the kana name of the bank as written in transfer data.
+    ///
+    /// The name is converted into half-width katakana and cut to
+    /// [`BANK_KANA_LEN`] characters, so that banks accept it as is.
+    ///
+    /// # Examples
+    /// ```
+    /// println!("{}", bank.zengin_kana()); // ﾐｽﾞﾎ
+    /// ```
+    pub fn zengin_kana(&self) -> String {
+        truncate_to_field(&to_halfwidth_kana(&self.kana), BANK_KANA_LEN)
+    }
+}
+
+impl Branch {
+    /// Returns the kana name of the branch as written in transfer data.
+    ///
+    /// The name is converted into half-width katakana and cut to
+    /// [`BRANCH_KANA_LEN`] characters, so that banks accept it as is.
+    ///
+    /// # Examples
+    /// ```
+    /// println!("{}", branch.zengin_kana()); // ｼﾝｼﾞﾕｸ
+    /// ```
+    pub fn zengin_kana(&self) -> String {
+        truncate_to_field(&to_halfwidth_kana(&self.kana), BRANCH_KANA_LEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zengin_kana() {
+        let zengin = crate::fixture();
+        let (bank, branch) = zengin.get_branch("0001", "110").unwrap();
+        assert_eq!(bank.zengin_kana(), "ﾐｽﾞﾎ");
+        assert_eq!(branch.zengin_kana(), "ｼﾝｼﾞﾕｸ");
+        assert_eq!(zengin["0005"].zengin_kana(), "ﾐﾂﾋﾞｼﾕ-ｴﾌｼﾞｴｲ");
+    }
+
+    #[test]
+    fn test_truncate_to_field() {
+        assert_eq!(truncate_to_field("ｱｲｳ", 5), "ｱｲｳ");
+        assert_eq!(truncate_to_field("ｱｲｳ", 2), "ｱｲ");
+        assert_eq!(truncate_to_field("ｱｶﾞｷ", 2), "ｱ");
+        assert_eq!(truncate_to_field("ｱｶﾞｷ", 3), "ｱｶﾞ");
+    }
+}
@@ -0,0 +1,1201 @@
+//! Zengin-format transfer files.
+//!
+//! Banks take bulk transfers as fixed-width files of 120-character records:
+//! a header record describing the requester, one data record per transfer, a
+//! trailer record with the totals, and an end record. Names are written in
+//! half-width katakana, and the bank and branch names are taken from the
+//! dataset, so [`Zengin::transfer_file`] only needs the codes.
+//! [`TransferFile::parse`] reads such files back into their records.
+
+#[cfg(feature = "encoding")]
+mod encoding;
+mod record;
+mod rows;
+mod validate;
+
+pub use record::{
+    CodeDivision, DataRecord, DebitResult, EndRecord, HeaderRecord, NewCode, RecordDate,
+    TrailerRecord, TransferDesignation,
+};
+pub use rows::PaymentRow;
+pub use validate::{FileIssue, ValidationReport};
+
+use crate::{
+    account::{invalid_holder_chars, normalize_account_number, BankAccount},
+    field::{AMOUNT_LEN, CUSTOMER_CODE_LEN, HOLDER_NAME_LEN, RECORD_LEN, REQUESTER_NAME_LEN},
+    Bank, Branch, ValidationError, Zengin,
+};
+use std::{error::Error, fmt};
+
+/// The maximum number of data records in a file, as the trailer record has six
+/// digits for the count.
+const MAX_RECORDS: usize = 999_999;
+
+/// The maximum total amount of a file, as the trailer record has twelve digits
+/// for the total.
+const MAX_TOTAL: u64 = 999_999_999_999;
+
+/// An error returned when a transfer file cannot be built or parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FormatError {
+    /// A field required by the header record was not set.
+    MissingField(&'static str),
+    /// A field has a value that the format cannot carry.
+    InvalidField { field: &'static str, value: String },
+    /// The account of the record on a line of the file is invalid.
+    InvalidAccount { line: usize, error: ValidationError },
+    /// The file has more records, or a larger total amount, than the trailer
+    /// record can carry.
+    TooLarge,
+    /// The record on a line is not 120 characters long.
+    InvalidRecordLength { line: usize, len: usize },
+    /// A field of the record on a line does not have the form of the field.
+    /// The column is the position of the first character of the field in the
+    /// record, counting from 1.
+    InvalidRecord {
+        line: usize,
+        column: usize,
+        field: &'static str,
+        value: String,
+    },
+    /// The record on a line has a data division (データ区分) that does not
+    /// belong there, such as a data record after the trailer record.
+    UnexpectedRecord { line: usize, division: char },
+    /// The file ends before a required record, such as the trailer record.
+    MissingRecord(&'static str),
+    /// The record on a line cannot be encoded as, or decoded from, 120 bytes
+    /// of Shift_JIS.
+    InvalidEncoding { line: usize },
+    /// Writing a parsed file back would change a line, as found by
+    /// [`verify_roundtrip`].
+    NotRoundTrip { line: usize },
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::MissingField(field) => write!(f, "missing {field}"),
+            FormatError::InvalidField { field, value } => {
+                write!(f, "invalid {field}: {value:?}")
+            }
+            FormatError::InvalidAccount { line, error } => write!(f, "line {line}: {error}"),
+            FormatError::TooLarge => write!(f, "too many records or too large a total amount"),
+            FormatError::InvalidRecordLength { line, len } => write!(
+                f,
+                "line {line}: expected a record of {RECORD_LEN} characters, got {len}"
+            ),
+            FormatError::InvalidRecord {
+                line,
+                column,
+                field,
+                value,
+            } => write!(
+                f,
+                "line {line}, column {column}: invalid {field}: {value:?}"
+            ),
+            FormatError::UnexpectedRecord { line, division } => {
+                write!(
+                    f,
+                    "line {line}: unexpected record with data division {division:?}"
+                )
+            }
+            FormatError::MissingRecord(record) => write!(f, "missing {record} record"),
+            FormatError::InvalidEncoding { line } => {
+                write!(f, "line {line}: not a 120-byte Shift_JIS record")
+            }
+            FormatError::NotRoundTrip { line } => {
+                write!(f, "line {line}: would not be written back as it was read")
+            }
+        }
+    }
+}
+
+impl FormatError {
+    /// Returns the line the error was found on, if it concerns a single line.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            FormatError::InvalidAccount { line, .. }
+            | FormatError::InvalidRecordLength { line, .. }
+            | FormatError::InvalidRecord { line, .. }
+            | FormatError::UnexpectedRecord { line, .. }
+            | FormatError::InvalidEncoding { line }
+            | FormatError::NotRoundTrip { line } => Some(*line),
+            _ => None,
+        }
+    }
+
+    /// Returns the column of the invalid field, if the error concerns a
+    /// single field of a record.
+    pub fn column(&self) -> Option<usize> {
+        match self {
+            FormatError::InvalidRecord { column, .. } => Some(*column),
+            _ => None,
+        }
+    }
+}
+
+impl Error for FormatError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FormatError::InvalidAccount { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Checks that a name fits a field and only contains characters of the
+/// format.
+fn check_name(field: &'static str, value: &str, len: usize) -> Result<(), FormatError> {
+    if value.chars().count() > len || !invalid_holder_chars(value).is_empty() {
+        return Err(FormatError::InvalidField {
+            field,
+            value: value.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// The kind of a transfer file, as given by the type code (種別コード) of its
+/// header record.
+///
+/// The kinds share most of the record layout, but name some fields
+/// differently: the customer codes of a 総合振込 data record are the employee
+/// number (社員番号) and the department code (所属コード) in payroll files,
+/// which also leave the transfer designation and identification blank. Direct
+/// debit files have a single 20-digit customer number (顧客番号) and a result
+/// code (振替結果コード) instead, and their trailer records carry the totals
+/// of successful and failed debits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum TransferKind {
+    /// 総合振込, general bulk transfers, type code `21`.
+    #[default]
+    General,
+    /// 給与振込, salary transfers, type code `11`.
+    Salary,
+    /// 賞与振込, bonus transfers, type code `12`.
+    Bonus,
+    /// 口座振替, direct debits, type code `91`.
+    DirectDebit,
+}
+
+impl TransferKind {
+    /// Returns the type code of the kind, such as `21`.
+    pub fn code(self) -> &'static str {
+        match self {
+            TransferKind::General => "21",
+            TransferKind::Salary => "11",
+            TransferKind::Bonus => "12",
+            TransferKind::DirectDebit => "91",
+        }
+    }
+
+    /// Returns the kind with a type code, if any.
+    pub fn from_code(code: &str) -> Option<TransferKind> {
+        [
+            TransferKind::General,
+            TransferKind::Salary,
+            TransferKind::Bonus,
+            TransferKind::DirectDebit,
+        ]
+        .into_iter()
+        .find(|kind| kind.code() == code)
+    }
+
+    /// Returns the Japanese name of the kind, such as `給与振込`.
+    pub fn name(self) -> &'static str {
+        match self {
+            TransferKind::General => "総合振込",
+            TransferKind::Salary => "給与振込",
+            TransferKind::Bonus => "賞与振込",
+            TransferKind::DirectDebit => "口座振替",
+        }
+    }
+
+    /// Returns whether the kind is a payroll transfer, salary or bonus.
+    pub fn is_payroll(self) -> bool {
+        matches!(self, TransferKind::Salary | TransferKind::Bonus)
+    }
+}
+
+impl fmt::Display for TransferKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The separator written after each record of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Newline {
+    /// CRLF, which most banks expect.
+    #[default]
+    CrLf,
+    /// LF alone.
+    Lf,
+    /// No separator, with the records written back to back.
+    None,
+}
+
+impl Newline {
+    /// Returns the separator of a file, by the first newline in it.
+    pub(crate) fn detect(input: &[u8]) -> Newline {
+        match input.iter().position(|&b| b == b'\n') {
+            Some(i) if i > 0 && input[i - 1] == b'\r' => Newline::CrLf,
+            Some(_) => Newline::Lf,
+            None => Newline::None,
+        }
+    }
+
+    /// Returns the separator, such as `"\r\n"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Newline::CrLf => "\r\n",
+            Newline::Lf => "\n",
+            Newline::None => "",
+        }
+    }
+}
+
+/// A Zengin-format transfer file, as built by [`TransferFileBuilder`] or
+/// parsed by [`TransferFile::parse`].
+///
+/// It displays as the records of the file, separated by its newline. A
+/// parsed file keeps everything needed to write it back byte for byte: the
+/// padding of its fields, its unused areas, and its newlines; see
+/// [`verify_roundtrip`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferFile {
+    /// The header record.
+    pub header: HeaderRecord,
+    /// The data records, one per transfer.
+    pub data: Vec<DataRecord>,
+    /// The trailer record.
+    pub trailer: TrailerRecord,
+    /// The end record.
+    pub end: EndRecord,
+    /// The separator between records, CRLF for built files.
+    pub newline: Newline,
+    /// Whether the last record is followed by the separator too.
+    pub final_newline: bool,
+    /// The line of each record in the input, if blank lines moved them from
+    /// lines 1, 2, and so on; see [`TransferFile::record_lines`].
+    lines: Vec<usize>,
+}
+
+/// Splits a file into its records with their lines, counting from 1. The
+/// records are either separated by newlines, skipping blank lines but
+/// counting them, or written back to back, each counting as a line.
+fn split_records(input: &str) -> Vec<(&str, usize)> {
+    if input.contains('\n') {
+        return input
+            .lines()
+            .zip(1..)
+            .filter(|(line, _)| !line.is_empty())
+            .collect();
+    }
+    let mut records = Vec::with_capacity(input.len() / RECORD_LEN + 1);
+    let mut rest = input;
+    while !rest.is_empty() {
+        let end = rest
+            .char_indices()
+            .nth(RECORD_LEN)
+            .map_or(rest.len(), |(i, _)| i);
+        records.push((&rest[..end], records.len() + 1));
+        rest = &rest[end..];
+    }
+    records
+}
+
+impl TransferFile {
+    /// Parses a Zengin-format file into its records.
+    ///
+    /// The records may be separated by CRLF or LF, or written back to back
+    /// without any separator. Text fields are returned without their padding.
+    /// The bank and branch codes are only checked for their form; see
+    /// [`TransferFile::unknown_codes`] to check them against the dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - A string slice that holds the contents of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a record is not 120 characters long, a field does
+    /// not have its form, or the records are not in the order of header, data,
+    /// trailer, and end records.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::format::TransferFile;
+    /// let file = TransferFile::parse(&std::fs::read_to_string("transfers.txt").unwrap()).unwrap();
+    /// for record in &file.data {
+    ///     println!("{} {} {}", record.bank_code, record.holder_name, record.amount);
+    /// }
+    /// ```
+    pub fn parse(input: &str) -> Result<TransferFile, FormatError> {
+        TransferFile::parse_all(input).map_err(|errors| errors.into_iter().next().unwrap())
+    }
+
+    /// Parses a Zengin-format file like [`TransferFile::parse`], but reads
+    /// the whole file instead of stopping at the first problem.
+    ///
+    /// Every invalid field of every record is reported, as a
+    /// [`FormatError::InvalidRecord`] with its line, column, field name, and
+    /// value, and so is every record out of order.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - A string slice that holds the contents of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns all the errors found, in the order of the lines they were
+    /// found on.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::format::TransferFile;
+    /// if let Err(errors) = TransferFile::parse_all(&std::fs::read_to_string("transfers.txt").unwrap()) {
+    ///     for error in errors {
+    ///         println!("{error}");
+    ///     }
+    /// }
+    /// ```
+    pub fn parse_all(input: &str) -> Result<TransferFile, Vec<FormatError>> {
+        let division = |record: &str| record.chars().next().unwrap_or(' ');
+        let unexpected = |record: &str, line| FormatError::UnexpectedRecord {
+            line,
+            division: division(record),
+        };
+        let mut errors = vec![];
+        let records = split_records(input);
+        let lines: Vec<_> = records.iter().map(|&(_, line)| line).collect();
+        let mut records = records.into_iter();
+
+        let mut header = None;
+        match records.next() {
+            Some((record, line)) if division(record) == '1' => {
+                match HeaderRecord::read(record, line) {
+                    Ok(read) => header = Some(read),
+                    Err(e) => errors.extend(e),
+                }
+            }
+            Some((record, line)) => errors.push(unexpected(record, line)),
+            None => return Err(vec![FormatError::MissingRecord("header")]),
+        }
+        let kind = header
+            .as_ref()
+            .map_or_else(TransferKind::default, |header| header.kind);
+        let (mut data, mut trailer, mut end) = (vec![], None, None);
+        // The number of records seen after the data records: the trailer
+        // record, then the end record.
+        let mut closing = 0;
+        for (record, line) in records {
+            let result = match (division(record), closing) {
+                ('2', 0) => DataRecord::read(record, line, kind).map(|read| data.push(read)),
+                ('8', 0) => {
+                    closing = 1;
+                    TrailerRecord::read(record, line, kind).map(|read| trailer = Some(read))
+                }
+                ('9', 1) => {
+                    closing = 2;
+                    EndRecord::read(record, line).map(|read| end = Some(read))
+                }
+                _ => Err(vec![unexpected(record, line)]),
+            };
+            errors.extend(result.err().into_iter().flatten());
+        }
+        match closing {
+            0 => errors.push(FormatError::MissingRecord("trailer")),
+            1 => errors.push(FormatError::MissingRecord("end")),
+            _ => {}
+        }
+        match (header, trailer, end) {
+            (Some(header), Some(trailer), Some(end)) if errors.is_empty() => Ok(TransferFile {
+                header,
+                data,
+                trailer,
+                end,
+                newline: Newline::detect(input.as_bytes()),
+                final_newline: input.ends_with('\n'),
+                lines: if lines.iter().copied().eq(1..=lines.len()) {
+                    vec![]
+                } else {
+                    lines
+                },
+            }),
+            _ => Err(errors),
+        }
+    }
+
+    /// Returns the kind of the file, as given by its header record.
+    pub fn kind(&self) -> TransferKind {
+        self.header.kind
+    }
+
+    /// Returns the line of each record of the file, in the order of
+    /// [`TransferFile::records`] and counting from 1.
+    ///
+    /// For a parsed file these are the lines of the input, counting any blank
+    /// lines between records. A built file, or a parsed file whose records
+    /// were added or removed since, has its records on consecutive lines.
+    pub fn record_lines(&self) -> Vec<usize> {
+        let count = self.data.len() + 3;
+        if self.lines.len() == count {
+            self.lines.clone()
+        } else {
+            (1..=count).collect()
+        }
+    }
+
+    /// Returns the line of each data record of the file, as given by
+    /// [`TransferFile::record_lines`].
+    pub fn data_lines(&self) -> Vec<usize> {
+        let mut lines = self.record_lines();
+        lines.truncate(lines.len() - 2);
+        lines.remove(0);
+        lines
+    }
+
+    /// Returns the records of the file in order, each as its 120 characters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FormatError::InvalidField`] if a field of a record is longer
+    /// than its width.
+    pub fn records(&self) -> Result<Vec<String>, FormatError> {
+        let kind = self.kind();
+        let mut records = Vec::with_capacity(self.data.len() + 3);
+        records.push(self.header.to_fixed_width()?);
+        for data in &self.data {
+            records.push(data.to_fixed_width(kind)?);
+        }
+        records.push(self.trailer.to_fixed_width(kind)?);
+        records.push(self.end.to_fixed_width()?);
+        Ok(records)
+    }
+
+    /// Writes the file with a newline convention, such as [`Newline::Lf`] for
+    /// banks that do not take CRLF. Every record, including the last one, is
+    /// followed by the newline.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`TransferFile::records`].
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::format::Newline;
+    /// std::fs::write("transfers.txt", file.to_text(Newline::None).unwrap()).unwrap();
+    /// ```
+    pub fn to_text(&self, newline: Newline) -> Result<String, FormatError> {
+        let mut text = String::with_capacity((self.data.len() + 3) * (RECORD_LEN + 2));
+        for record in self.records()? {
+            text.push_str(&record);
+            text.push_str(newline.as_str());
+        }
+        Ok(text)
+    }
+
+    /// Writes the file with the newline convention it was read with,
+    /// leaving out the final newline if the input did.
+    fn render(&self) -> Result<String, FormatError> {
+        let mut text = self.to_text(self.newline)?;
+        if !self.final_newline {
+            text.truncate(text.len() - self.newline.as_str().len());
+        }
+        Ok(text)
+    }
+
+    /// Checks the bank and branch codes of the data records against a
+    /// dataset, returning the line and the error of every record whose bank
+    /// or branch does not exist.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{format::TransferFile, Zengin};
+    /// let zengin = Zengin::new().unwrap();
+    /// let file = TransferFile::parse(&std::fs::read_to_string("transfers.txt").unwrap()).unwrap();
+    /// for (line, error) in file.unknown_codes(&zengin) {
+    ///     println!("line {line}: {error}");
+    /// }
+    /// ```
+    pub fn unknown_codes(&self, zengin: &Zengin) -> Vec<(usize, ValidationError)> {
+        self.data
+            .iter()
+            .zip(self.data_lines())
+            .filter_map(|(record, line)| record.resolve(zengin).err().map(|e| (line, e)))
+            .collect()
+    }
+}
+
+impl DataRecord {
+    /// Looks up the recipient bank and branch of the record in a dataset.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValidationError`] if the bank or the branch does not exist.
+    pub fn resolve<'a>(
+        &self,
+        zengin: &'a Zengin,
+    ) -> Result<(&'a Bank, &'a Branch), ValidationError> {
+        zengin.validate(self.bank_code.as_str(), self.branch_code.as_str())
+    }
+}
+
+/// Parses a file and checks that writing it back reproduces the input
+/// exactly, to show that reading and writing a file does not alter it.
+///
+/// # Arguments
+///
+/// * `input` - A string slice that holds the contents of the file.
+///
+/// # Errors
+///
+/// Returns the errors of [`TransferFile::parse`], or
+/// [`FormatError::NotRoundTrip`] with the first line that would be written
+/// differently, for example a line separated by LF in a file of CRLF.
+///
+/// # Examples
+/// ```
+/// use zengin::format::verify_roundtrip;
+/// let file = verify_roundtrip(&std::fs::read_to_string("transfers.txt").unwrap()).unwrap();
+/// std::fs::write("copy.txt", file.to_string()).unwrap();
+/// ```
+pub fn verify_roundtrip(input: &str) -> Result<TransferFile, FormatError> {
+    let file = TransferFile::parse(input)?;
+    let output = file.render()?;
+    if output != input {
+        return Err(FormatError::NotRoundTrip {
+            line: first_difference(&output, input),
+        });
+    }
+    Ok(file)
+}
+
+/// Returns the first line on which two files differ, counting records written
+/// back to back as a line each.
+fn first_difference(output: &str, input: &str) -> usize {
+    if !input.contains('\n') {
+        let same = output
+            .chars()
+            .zip(input.chars())
+            .take_while(|(output, input)| output == input)
+            .count();
+        return same / RECORD_LEN + 1;
+    }
+    output
+        .split_inclusive('\n')
+        .zip(input.split_inclusive('\n'))
+        .take_while(|(output, input)| output == input)
+        .count()
+        + 1
+}
+
+/// Writes the file as [`TransferFile::to_text`] does, with the newline
+/// convention it was read with. Formatting fails if a field is longer than
+/// its width, so call [`TransferFile::to_text`] to handle that case.
+impl fmt::Display for TransferFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render().map_err(|_| fmt::Error)?)
+    }
+}
+
+/// A transfer added to a [`TransferFileBuilder`].
+struct Transfer {
+    account: BankAccount,
+    amount: u64,
+    codes: [String; 2],
+}
+
+/// A builder of transfer files, 総合振込 (bulk transfer) files by default.
+///
+/// Created by [`Zengin::transfer_file`]. The requester code, the transfer
+/// date, and the source account must be set, and every account is checked
+/// against the dataset when the file is built.
+#[must_use]
+pub struct TransferFileBuilder<'a> {
+    zengin: &'a Zengin,
+    kind: TransferKind,
+    requester_code: Option<String>,
+    date: Option<Result<RecordDate, (u32, u32)>>,
+    source: Option<BankAccount>,
+    transfers: Vec<Transfer>,
+    error: Option<FormatError>,
+}
+
+impl<'a> TransferFileBuilder<'a> {
+    /// Sets the kind of the file, such as [`TransferKind::Salary`] for a
+    /// 給与振込 file.
+    pub fn kind(mut self, kind: TransferKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets the requester code (依頼人コード, or 会社コード in payroll files)
+    /// that the source bank assigned, up to ten digits.
+    pub fn requester_code(mut self, code: &str) -> Self {
+        self.requester_code = Some(code.to_string());
+        self
+    }
+
+    /// Sets the transfer date (取組日, or 振込指定日 in payroll files).
+    pub fn transfer_date(mut self, month: u32, day: u32) -> Self {
+        self.date = Some(RecordDate::new(month, day).ok_or((month, day)));
+        self
+    }
+
+    /// Sets the account the transfers are paid from. The holder name of the
+    /// account becomes the requester name (依頼人名), which may have up to 40
+    /// characters.
+    pub fn source_account(mut self, account: BankAccount) -> Self {
+        self.source = Some(account);
+        self
+    }
+
+    /// Adds a transfer of `amount` yen to an account.
+    pub fn transfer(self, account: BankAccount, amount: u64) -> Self {
+        self.transfer_with_codes(account, amount, "", "")
+    }
+
+    /// Adds a transfer of `amount` yen to an account, with the two codes of
+    /// up to ten digits that the data record carries: the customer codes
+    /// (顧客コード) in 総合振込 files, and the employee number (社員番号) and
+    /// department code (所属コード) in payroll files. Direct debit files have
+    /// room for the first code only, so building one fails if `code2` is not
+    /// empty.
+    pub fn transfer_with_codes(
+        mut self,
+        account: BankAccount,
+        amount: u64,
+        code1: &str,
+        code2: &str,
+    ) -> Self {
+        self.transfers.push(Transfer {
+            account,
+            amount,
+            codes: [code1.to_string(), code2.to_string()],
+        });
+        self
+    }
+
+    /// Adds a direct debit of `amount` yen from an account, with the customer
+    /// number (顧客番号) of up to 20 digits that identifies the payer to the
+    /// requester. Use it with [`TransferKind::DirectDebit`].
+    pub fn debit(self, account: BankAccount, amount: u64, customer_number: &str) -> Self {
+        self.transfer_with_codes(account, amount, customer_number, "")
+    }
+
+    /// Builds the file, with the records separated by CRLF.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a header field is missing or invalid, if an
+    /// account fails [`BankAccount::validate`], if a holder name is longer
+    /// than its field, if an amount or a code does not fit its field, or if a
+    /// row added by [`TransferFileBuilder::rows`] is invalid.
+    pub fn build(self) -> Result<String, FormatError> {
+        self.build_file()?.render()
+    }
+
+    /// Builds the file as its records, for inspection before it is written.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`TransferFileBuilder::build`].
+    pub fn build_file(self) -> Result<TransferFile, FormatError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let requester_code = self
+            .requester_code
+            .ok_or(FormatError::MissingField("requester code"))?;
+        if requester_code.is_empty()
+            || requester_code.len() > CUSTOMER_CODE_LEN
+            || !requester_code.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Err(FormatError::InvalidField {
+                field: "requester code",
+                value: requester_code,
+            });
+        }
+        let date = self
+            .date
+            .ok_or(FormatError::MissingField("transfer date"))?
+            .map_err(|(month, day)| FormatError::InvalidField {
+                field: "transfer date",
+                value: format!("{month:02}{day:02}"),
+            })?;
+        let source = self
+            .source
+            .ok_or(FormatError::MissingField("source account"))?;
+        check_name("requester name", &source.holder_kana, REQUESTER_NAME_LEN)?;
+        let (bank, branch) = source
+            .validate(self.zengin)
+            .map_err(|error| FormatError::InvalidAccount { line: 1, error })?;
+        let header = HeaderRecord {
+            kind: self.kind,
+            code_division: CodeDivision::Jis,
+            requester_code: format!("{requester_code:0>CUSTOMER_CODE_LEN$}"),
+            requester_name: source.holder_kana.clone(),
+            date,
+            bank_code: source.bank_code,
+            bank_name: bank.zengin_kana(),
+            branch_code: source.branch_code,
+            branch_name: branch.zengin_kana(),
+            account_type: source.account_type,
+            account_number: normalize_account_number(&source.number)
+                .expect("validated account number"),
+            dummy: String::new(),
+        };
+
+        if self.transfers.len() > MAX_RECORDS {
+            return Err(FormatError::TooLarge);
+        }
+        let mut data = Vec::with_capacity(self.transfers.len());
+        for (transfer, line) in self.transfers.into_iter().zip(2..) {
+            let Transfer {
+                account,
+                amount,
+                codes,
+            } = transfer;
+            let (bank, branch) = account
+                .validate(self.zengin)
+                .map_err(|error| FormatError::InvalidAccount { line, error })?;
+            check_name("holder name", &account.holder_kana, HOLDER_NAME_LEN)?;
+            if amount.to_string().len() > AMOUNT_LEN {
+                return Err(FormatError::InvalidField {
+                    field: "amount",
+                    value: amount.to_string(),
+                });
+            }
+            let len = if self.kind == TransferKind::DirectDebit {
+                CUSTOMER_CODE_LEN * 2
+            } else {
+                CUSTOMER_CODE_LEN
+            };
+            if self.kind == TransferKind::DirectDebit && !codes[1].is_empty() {
+                return Err(FormatError::InvalidField {
+                    field: "customer code 2",
+                    value: codes[1].clone(),
+                });
+            }
+            let [code1, code2] = codes.map(|code| {
+                if code.len() > len || !code.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(FormatError::InvalidField {
+                        field: "customer code",
+                        value: code,
+                    });
+                }
+                Ok(if code.is_empty() {
+                    code
+                } else {
+                    format!("{code:0>len$}")
+                })
+            });
+            let designation = match self.kind {
+                TransferKind::General => Some(TransferDesignation::Telegraphic),
+                _ => None,
+            };
+            data.push(DataRecord {
+                bank_code: account.bank_code,
+                bank_name: bank.zengin_kana(),
+                branch_code: account.branch_code,
+                branch_name: branch.zengin_kana(),
+                clearing_house: String::new(),
+                account_type: account.account_type,
+                account_number: normalize_account_number(&account.number)
+                    .expect("validated account number"),
+                holder_name: account.holder_kana,
+                amount,
+                new_code: NewCode::Other,
+                customer_code1: code1?,
+                customer_code2: code2?,
+                transfer_designation: designation,
+                edi: false,
+                result_code: None,
+                dummy: String::new(),
+            });
+        }
+        let total: u64 = data.iter().map(|record| record.amount).sum();
+        if total > MAX_TOTAL {
+            return Err(FormatError::TooLarge);
+        }
+        Ok(TransferFile {
+            header,
+            trailer: TrailerRecord {
+                count: data.len() as u64,
+                total,
+                debited_count: 0,
+                debited_total: 0,
+                failed_count: 0,
+                failed_total: 0,
+                dummy: String::new(),
+            },
+            data,
+            end: EndRecord::default(),
+            newline: Newline::CrLf,
+            final_newline: true,
+            lines: vec![],
+        })
+    }
+}
+
+impl Zengin {
+    /// Starts building a 総合振込 (bulk transfer) file, or a payroll or direct
+    /// debit file with [`TransferFileBuilder::kind`].
+    ///
+    /// The bank and branch names of the file are filled in from the dataset,
+    /// converted by [`Bank::zengin_kana`](crate::Bank::zengin_kana).
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{account::{AccountType, BankAccount}, Zengin};
+    /// let zengin = Zengin::new().unwrap();
+    /// let account = |bank: &str, branch: &str, number: &str, holder: &str| BankAccount {
+    ///     bank_code: bank.parse().unwrap(),
+    ///     branch_code: branch.parse().unwrap(),
+    ///     account_type: AccountType::Ordinary,
+    ///     number: number.to_string(),
+    ///     holder_kana: holder.to_string(),
+    /// };
+    /// let file = zengin
+    ///     .transfer_file()
+    ///     .requester_code("1234567890")
+    ///     .transfer_date(4, 25)
+    ///     .source_account(account("0001", "001", "1111111", "ｶ)ｻﾝﾌﾟﾙ"))
+    ///     .transfer(account("0005", "001", "2222222", "ﾔﾏﾀﾞ ﾀﾛｳ"), 10000)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn transfer_file(&self) -> TransferFileBuilder<'_> {
+        TransferFileBuilder {
+            zengin: self,
+            kind: TransferKind::General,
+            requester_code: None,
+            date: None,
+            source: None,
+            transfers: vec![],
+            error: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::AccountType;
+
+    /// Returns an ordinary account, for the tests of the format modules.
+    pub(super) fn account(bank: &str, branch: &str, number: &str, holder: &str) -> BankAccount {
+        BankAccount {
+            bank_code: bank.parse().unwrap(),
+            branch_code: branch.parse().unwrap(),
+            account_type: AccountType::Ordinary,
+            number: number.to_string(),
+            holder_kana: holder.to_string(),
+        }
+    }
+
+    /// Returns a builder with the requester, the transfer date, and the source
+    /// account set, which the tests of the format modules start from.
+    pub(super) fn builder(zengin: &Zengin) -> TransferFileBuilder<'_> {
+        zengin
+            .transfer_file()
+            .requester_code("123")
+            .transfer_date(4, 5)
+            .source_account(account("0001", "001", "1111111", "ｶ)ｻﾝﾌﾟﾙ"))
+    }
+
+    #[test]
+    fn test_transfer_file() {
+        let zengin = crate::fixture();
+        let file = builder(&zengin)
+            .transfer(account("0005", "001", "22222", "ﾔﾏﾀﾞ ﾀﾛｳ"), 10000)
+            .transfer(account("0009", "625", "3333333", "ｽｽﾞｷ ﾊﾅｺ"), 2500)
+            .build()
+            .unwrap();
+        let records: Vec<_> = file.split_terminator("\r\n").collect();
+        assert_eq!(records.len(), 5);
+        assert!(records.iter().all(|r| r.chars().count() == RECORD_LEN));
+        assert!(records[0].starts_with("12100000000123ｶ)ｻﾝﾌﾟﾙ "));
+        assert!(records[0].contains("04050001ﾐｽﾞﾎ"));
+        assert!(records[1].starts_with("20005ﾐﾂﾋﾞｼﾕ-ｴﾌｼﾞｴｲ  001"));
+        assert!(records[1].contains("10022222ﾔﾏﾀﾞ ﾀﾛｳ"));
+        assert!(records[1].contains("00000100000"));
+        assert_eq!(&records[3][..19], "8000002000000012500");
+        assert_eq!(records[4].trim_end(), "9");
+    }
+
+    #[test]
+    fn test_transfer_file_errors() {
+        let zengin = crate::fixture();
+        assert_eq!(
+            zengin.transfer_file().build().unwrap_err(),
+            FormatError::MissingField("requester code")
+        );
+        assert!(matches!(
+            builder(&zengin)
+                .transfer(account("0005", "999", "1", "ﾀﾛｳ"), 1)
+                .build(),
+            Err(FormatError::InvalidAccount {
+                line: 2,
+                error: ValidationError::BranchNotFound { .. }
+            })
+        ));
+        assert!(matches!(
+            builder(&zengin)
+                .transfer(account("0005", "001", "1", "ﾀﾛｳ"), 10_000_000_000)
+                .build(),
+            Err(FormatError::InvalidField {
+                field: "amount",
+                ..
+            })
+        ));
+        assert!(matches!(
+            builder(&zengin).transfer_date(13, 1).build(),
+            Err(FormatError::InvalidField {
+                field: "transfer date",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_transfer_file() {
+        let zengin = crate::fixture();
+        let built = builder(&zengin)
+            .transfer(account("0005", "001", "22222", "ﾔﾏﾀﾞ ﾀﾛｳ"), 10000)
+            .build_file()
+            .unwrap();
+        let text = built.to_string();
+        let file = TransferFile::parse(&text).unwrap();
+        assert_eq!(file, built);
+        assert_eq!(file.data[0].holder_name, "ﾔﾏﾀﾞ ﾀﾛｳ");
+        assert_eq!(file.data[0].amount, 10000);
+        let back_to_back = TransferFile::parse(&text.replace("\r\n", "")).unwrap();
+        assert_eq!(back_to_back.records(), built.records());
+        assert_eq!(back_to_back.newline, Newline::None);
+
+        let unknown = text.replacen("ｴｲ  001", "ｴｲ  999", 1);
+        let file = TransferFile::parse(&unknown).unwrap();
+        assert!(matches!(
+            file.unknown_codes(&zengin)[..],
+            [(2, ValidationError::BranchNotFound { .. })]
+        ));
+        let blank_lines = unknown.replacen("\r\n", "\r\n\r\n\r\n", 1);
+        let file = TransferFile::parse(&blank_lines).unwrap();
+        assert_eq!(file.record_lines(), vec![1, 4, 5, 6]);
+        assert_eq!(file.data_lines(), vec![4]);
+        assert!(matches!(
+            file.unknown_codes(&zengin)[..],
+            [(4, ValidationError::BranchNotFound { .. })]
+        ));
+
+        assert!(matches!(
+            TransferFile::parse(&text.replacen("0000010000", "00000100x0", 1)),
+            Err(FormatError::InvalidRecord {
+                line: 2,
+                field: "amount",
+                ..
+            })
+        ));
+        let records: Vec<_> = text.lines().collect();
+        assert_eq!(
+            TransferFile::parse(&records[..3].join("\n")),
+            Err(FormatError::MissingRecord("end"))
+        );
+        assert_eq!(
+            TransferFile::parse(&[records[0], records[2], records[1]].join("\n")),
+            Err(FormatError::UnexpectedRecord {
+                line: 3,
+                division: '2'
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_all() {
+        let zengin = crate::fixture();
+        let text = builder(&zengin)
+            .transfer(account("0005", "001", "22222", "ﾔﾏﾀﾞ ﾀﾛｳ"), 10000)
+            .transfer(account("0009", "625", "3333333", "ｽｽﾞｷ ﾊﾅｺ"), 2500)
+            .build()
+            .unwrap();
+        let mut records: Vec<String> = text.lines().map(str::to_string).collect();
+        records[1].replace_range(..5, "200x5");
+        records[1] = records[1].replacen("0000010000", "00000100x0", 1);
+        records[2] = records[2].replacen("0000002500", "0000002500 ", 1);
+        records.swap(3, 4);
+        let errors = TransferFile::parse_all(&records.join("\n")).unwrap_err();
+        assert_eq!(errors.len(), 5);
+        assert_eq!(
+            errors[0],
+            FormatError::InvalidRecord {
+                line: 2,
+                column: 2,
+                field: "bank code",
+                value: "00x5".to_string()
+            }
+        );
+        assert_eq!(errors[1].column(), Some(81));
+        assert_eq!(
+            errors[1].to_string(),
+            "line 2, column 81: invalid amount: \"00000100x0\""
+        );
+        assert_eq!(errors[2].line(), Some(3));
+        assert!(matches!(
+            errors[2],
+            FormatError::InvalidRecordLength { len: 121, .. }
+        ));
+        assert!(matches!(
+            errors[3],
+            FormatError::UnexpectedRecord {
+                line: 4,
+                division: '9'
+            }
+        ));
+        assert_eq!(errors[4], FormatError::MissingRecord("end"));
+
+        records.insert(1, String::new());
+        let errors = TransferFile::parse_all(&records.join("\n")).unwrap_err();
+        assert_eq!(
+            errors.iter().map(FormatError::line).collect::<Vec<_>>(),
+            vec![Some(3), Some(3), Some(4), Some(5), None]
+        );
+    }
+
+    #[test]
+    fn test_verify_roundtrip() {
+        let zengin = crate::fixture();
+        let text = builder(&zengin)
+            .transfer(account("0005", "001", "22222", "ﾔﾏﾀﾞ ﾀﾛｳ"), 10000)
+            .build()
+            .unwrap();
+        assert_eq!(verify_roundtrip(&text).unwrap().to_string(), text);
+
+        // Unused areas and a missing final newline survive as well.
+        let mut records: Vec<String> = text.lines().map(str::to_string).collect();
+        let len = records[0].len();
+        records[0].replace_range(len - 3.., "XYZ");
+        records[3].replace_range(1..4, "END");
+        let lf = records.join("\n");
+        let file = verify_roundtrip(&lf).unwrap();
+        assert_eq!(file.header.dummy, "              XYZ");
+        assert_eq!(file.end.dummy, "END");
+        assert!(!file.final_newline);
+        assert_eq!(file.to_string(), lf);
+
+        let mixed = text.replacen("\r\n", "\n", 2).replacen("\n", "\r\n", 1);
+        assert_eq!(
+            verify_roundtrip(&mixed).unwrap_err(),
+            FormatError::NotRoundTrip { line: 2 }
+        );
+
+        let back_to_back = text.replace("\r\n", "");
+        let changed: String = back_to_back
+            .chars()
+            .enumerate()
+            .map(|(i, c)| if i == 2 * RECORD_LEN + 1 { '9' } else { c })
+            .collect();
+        assert_eq!(first_difference(&back_to_back, &changed), 3);
+        let first: String = back_to_back.chars().take(RECORD_LEN).collect();
+        assert_eq!(first_difference(&back_to_back, &first), 2);
+    }
+
+    #[test]
+    fn test_payroll_file() {
+        let zengin = crate::fixture();
+        let file = builder(&zengin)
+            .kind(TransferKind::Salary)
+            .transfer_date(4, 25)
+            .transfer_with_codes(
+                account("0005", "001", "22222", "ﾔﾏﾀﾞ ﾀﾛｳ"),
+                300000,
+                "42",
+                "7",
+            )
+            .build()
+            .unwrap();
+        let records: Vec<_> = file.lines().collect();
+        assert!(records[0].starts_with("1110"));
+        assert!(records[1].ends_with("0000300000000000000420000000007         "));
+
+        let file = TransferFile::parse(&file).unwrap();
+        assert_eq!(file.kind(), TransferKind::Salary);
+        assert_eq!(file.data[0].customer_code1, "0000000042");
+        assert_eq!(file.data[0].transfer_designation, None);
+
+        assert!(matches!(
+            builder(&zengin)
+                .transfer_with_codes(account("0005", "001", "1", "ﾀﾛｳ"), 1, "A1", "")
+                .build(),
+            Err(FormatError::InvalidField {
+                field: "customer code",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_direct_debit_file() {
+        let zengin = crate::fixture();
+        let request = builder(&zengin)
+            .kind(TransferKind::DirectDebit)
+            .transfer_date(4, 27)
+            .debit(
+                account("0005", "001", "22222", "ﾔﾏﾀﾞ ﾀﾛｳ"),
+                980,
+                "12345678901234",
+            )
+            .debit(account("0009", "625", "33333", "ｽｽﾞｷ ﾊﾅｺ"), 1980, "2")
+            .build()
+            .unwrap();
+        let records: Vec<_> = request.lines().collect();
+        assert!(records[0].starts_with("1910"));
+        assert!(records[1].ends_with("0000000980000000012345678901234         "));
+
+        // The bank returns the file with the result codes and totals filled in.
+        let mut result = TransferFile::parse(&request).unwrap();
+        result.data[0].result_code = Some(DebitResult::Debited);
+        result.data[1].result_code = Some(DebitResult::InsufficientFunds);
+        result.trailer.debited_count = 1;
+        result.trailer.debited_total = 980;
+        result.trailer.failed_count = 1;
+        result.trailer.failed_total = 1980;
+        let text = result.to_string();
+        assert!(text.contains("8000002000000002960000001000000000980000001000000001980 "));
+
+        let parsed = TransferFile::parse(&text).unwrap();
+        assert_eq!(parsed.kind(), TransferKind::DirectDebit);
+        assert_eq!(parsed.data[0].customer_code1, "00000012345678901234");
+        assert_eq!(
+            parsed.data[1].result_code,
+            Some(DebitResult::InsufficientFunds)
+        );
+        assert_eq!(parsed, result);
+
+        let with_code2 = builder(&zengin)
+            .kind(TransferKind::DirectDebit)
+            .transfer_date(4, 27)
+            .transfer_with_codes(account("0005", "001", "22222", "ﾔﾏﾀﾞ ﾀﾛｳ"), 980, "1", "2")
+            .build();
+        assert!(matches!(
+            with_code2,
+            Err(FormatError::InvalidField {
+                field: "customer code 2",
+                ..
+            })
+        ));
+        result.data[0].customer_code2 = "2".to_string();
+        assert!(matches!(
+            result.to_text(Newline::Lf),
+            Err(FormatError::InvalidField {
+                field: "customer code 2",
+                ..
+            })
+        ));
+    }
+}
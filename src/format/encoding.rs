@@ -0,0 +1,135 @@
+//! Shift_JIS encoding of Zengin-format files.
+
+use super::{FormatError, Newline, TransferFile};
+use crate::field::RECORD_LEN;
+use encoding_rs::SHIFT_JIS;
+
+/// Splits the bytes of a file into its lines, which are either separated by
+/// newlines, blank ones included, or records written back to back. Every
+/// character of the format is a single byte in Shift_JIS, and the trail bytes
+/// of double-byte characters are never LF, so splitting before decoding is
+/// safe.
+fn split_records(input: &[u8]) -> Vec<&[u8]> {
+    if input.contains(&b'\n') {
+        return input
+            .split(|&b| b == b'\n')
+            .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+            .collect();
+    }
+    input.chunks(RECORD_LEN).collect()
+}
+
+impl TransferFile {
+    /// Parses a Zengin-format file encoded in Shift_JIS (CP932), as banks
+    /// return them.
+    ///
+    /// Half-width katakana are the single bytes `0xA1`-`0xDF`. The records may
+    /// be separated by CRLF or LF, or written back to back.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - A byte slice that holds the contents of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FormatError::InvalidEncoding`] if a record is not valid
+    /// Shift_JIS, and the errors of [`TransferFile::parse`] otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::format::TransferFile;
+    /// let file = TransferFile::parse_shift_jis(&std::fs::read("result.txt").unwrap()).unwrap();
+    /// ```
+    pub fn parse_shift_jis(input: &[u8]) -> Result<TransferFile, FormatError> {
+        let mut text = String::with_capacity(input.len() * 3);
+        for (record, line) in split_records(input).into_iter().zip(1..) {
+            let record = SHIFT_JIS
+                .decode_without_bom_handling_and_without_replacement(record)
+                .ok_or(FormatError::InvalidEncoding { line })?;
+            text.push_str(&record);
+            text.push('\n');
+        }
+        let mut file = TransferFile::parse(&text)?;
+        file.newline = Newline::detect(input);
+        file.final_newline = input.ends_with(b"\n");
+        Ok(file)
+    }
+
+    /// Encodes the file in Shift_JIS (CP932), with a newline convention.
+    ///
+    /// # Arguments
+    ///
+    /// * `newline` - The separator written after each record.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FormatError::InvalidEncoding`] if a record has a character
+    /// that Shift_JIS lacks, or a full-width character that would make the
+    /// record longer than 120 bytes, and the errors of [`TransferFile::records`].
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::format::Newline;
+    /// std::fs::write("transfers.txt", file.to_shift_jis(Newline::CrLf).unwrap()).unwrap();
+    /// ```
+    pub fn to_shift_jis(&self, newline: Newline) -> Result<Vec<u8>, FormatError> {
+        let records = self.records()?;
+        let mut bytes = Vec::with_capacity(records.len() * (RECORD_LEN + 2));
+        for (record, line) in records.iter().zip(1..) {
+            let (encoded, _, had_errors) = SHIFT_JIS.encode(record);
+            if had_errors || encoded.len() != RECORD_LEN {
+                return Err(FormatError::InvalidEncoding { line });
+            }
+            bytes.extend_from_slice(&encoded);
+            bytes.extend_from_slice(newline.as_str().as_bytes());
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::tests::{account, builder};
+
+    #[test]
+    fn test_shift_jis() {
+        let zengin = crate::fixture();
+        let file = builder(&zengin)
+            .transfer(account("0005", "001", "22222", "ﾔﾏﾀﾞ ﾀﾛｳ"), 10000)
+            .build_file()
+            .unwrap();
+        let bytes = file.to_shift_jis(Newline::CrLf).unwrap();
+        assert_eq!(bytes.len(), 4 * (RECORD_LEN + 2));
+        // ｶ is 0xB6 and the voiced ﾀﾞ is ﾀ followed by ﾞ, one byte each.
+        assert_eq!(&bytes[14..16], b"\xB6)");
+        assert!(bytes.windows(2).any(|w| w == b"\xC0\xDE"));
+        assert_eq!(TransferFile::parse_shift_jis(&bytes).unwrap(), file);
+
+        let bytes = file.to_shift_jis(Newline::None).unwrap();
+        assert_eq!(bytes.len(), 4 * RECORD_LEN);
+        let parsed = TransferFile::parse_shift_jis(&bytes).unwrap();
+        assert_eq!(parsed.records(), file.records());
+        assert_eq!(parsed.newline, Newline::None);
+
+        // A lead byte without its trail byte at the end of the second record.
+        let mut invalid = file.to_shift_jis(Newline::Lf).unwrap();
+        invalid[2 * RECORD_LEN] = 0x81;
+        assert_eq!(
+            TransferFile::parse_shift_jis(&invalid),
+            Err(FormatError::InvalidEncoding { line: 2 })
+        );
+        invalid.insert(RECORD_LEN + 1, b'\n');
+        assert_eq!(
+            TransferFile::parse_shift_jis(&invalid),
+            Err(FormatError::InvalidEncoding { line: 3 })
+        );
+
+        let mut wide = file.clone();
+        wide.data[0].holder_name = "山田".to_string();
+        assert_eq!(
+            wide.to_shift_jis(Newline::CrLf),
+            Err(FormatError::InvalidEncoding { line: 2 })
+        );
+    }
+}
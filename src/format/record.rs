@@ -0,0 +1,981 @@
+//! The records of Zengin-format files, and their fixed-width layout.
+
+use super::{FormatError, TransferKind};
+use crate::{
+    account::{AccountType, ACCOUNT_NUMBER_LEN},
+    field::{
+        AMOUNT_LEN, BANK_KANA_LEN, BRANCH_KANA_LEN, CUSTOMER_CODE_LEN, HOLDER_NAME_LEN, RECORD_LEN,
+        REQUESTER_NAME_LEN,
+    },
+    BankCode, BranchCode,
+};
+use std::{fmt, str::FromStr};
+
+/// The code division (コード区分) of a header record, the character set of
+/// the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum CodeDivision {
+    /// JIS, code `0`.
+    #[default]
+    Jis,
+    /// EBCDIC, code `1`.
+    Ebcdic,
+}
+
+impl CodeDivision {
+    /// Returns the code of the division, such as `'0'`.
+    pub fn code(self) -> char {
+        match self {
+            CodeDivision::Jis => '0',
+            CodeDivision::Ebcdic => '1',
+        }
+    }
+
+    /// Returns the division with a code, if any.
+    pub fn from_code(code: char) -> Option<CodeDivision> {
+        match code {
+            '0' => Some(CodeDivision::Jis),
+            '1' => Some(CodeDivision::Ebcdic),
+            _ => None,
+        }
+    }
+}
+
+/// The new code (新規コード) of a data record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum NewCode {
+    /// Any transfer but the first one to a recipient, code `0`.
+    #[default]
+    Other,
+    /// The first transfer to a recipient, code `1`.
+    First,
+    /// A transfer to a recipient whose account has changed, code `2`.
+    Changed,
+}
+
+impl NewCode {
+    /// Returns the code, such as `'1'`.
+    pub fn code(self) -> char {
+        match self {
+            NewCode::Other => '0',
+            NewCode::First => '1',
+            NewCode::Changed => '2',
+        }
+    }
+
+    /// Returns the new code with a code, if any.
+    pub fn from_code(code: char) -> Option<NewCode> {
+        match code {
+            '0' => Some(NewCode::Other),
+            '1' => Some(NewCode::First),
+            '2' => Some(NewCode::Changed),
+            _ => None,
+        }
+    }
+}
+
+/// The transfer designation (振込指定区分) of a 総合振込 data record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TransferDesignation {
+    /// 電信振込, telegraphic transfer, code `7`.
+    Telegraphic,
+    /// 文書振込, documentary transfer, code `8`.
+    Documentary,
+}
+
+impl TransferDesignation {
+    /// Returns the code of the designation, such as `'7'`.
+    pub fn code(self) -> char {
+        match self {
+            TransferDesignation::Telegraphic => '7',
+            TransferDesignation::Documentary => '8',
+        }
+    }
+
+    /// Returns the designation with a code, if any.
+    pub fn from_code(code: char) -> Option<TransferDesignation> {
+        match code {
+            '7' => Some(TransferDesignation::Telegraphic),
+            '8' => Some(TransferDesignation::Documentary),
+            _ => None,
+        }
+    }
+}
+
+/// The result code (振替結果コード) of a data record in a direct debit result
+/// file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DebitResult {
+    /// 振替済, debited, code `0`.
+    Debited,
+    /// 資金不足, insufficient funds, code `1`.
+    InsufficientFunds,
+    /// 取引なし, no such account, code `2`.
+    NoAccount,
+    /// 預金者都合による振替停止, stopped by the payer, code `3`.
+    StoppedByPayer,
+    /// 振替依頼書なし, no direct debit authorization, code `4`.
+    NoAuthorization,
+    /// 委託者都合による振替停止, stopped by the requester, code `8`.
+    StoppedByRequester,
+    /// その他, any other reason, code `9`.
+    Other,
+}
+
+impl DebitResult {
+    /// Returns the code of the result, such as `'1'`.
+    pub fn code(self) -> char {
+        match self {
+            DebitResult::Debited => '0',
+            DebitResult::InsufficientFunds => '1',
+            DebitResult::NoAccount => '2',
+            DebitResult::StoppedByPayer => '3',
+            DebitResult::NoAuthorization => '4',
+            DebitResult::StoppedByRequester => '8',
+            DebitResult::Other => '9',
+        }
+    }
+
+    /// Returns the result with a code, if any.
+    pub fn from_code(code: char) -> Option<DebitResult> {
+        match code {
+            '0' => Some(DebitResult::Debited),
+            '1' => Some(DebitResult::InsufficientFunds),
+            '2' => Some(DebitResult::NoAccount),
+            '3' => Some(DebitResult::StoppedByPayer),
+            '4' => Some(DebitResult::NoAuthorization),
+            '8' => Some(DebitResult::StoppedByRequester),
+            '9' => Some(DebitResult::Other),
+            _ => None,
+        }
+    }
+
+    /// Returns the Japanese name of the result, such as `資金不足`.
+    pub fn name(self) -> &'static str {
+        match self {
+            DebitResult::Debited => "振替済",
+            DebitResult::InsufficientFunds => "資金不足",
+            DebitResult::NoAccount => "取引なし",
+            DebitResult::StoppedByPayer => "預金者都合",
+            DebitResult::NoAuthorization => "依頼書なし",
+            DebitResult::StoppedByRequester => "委託者都合",
+            DebitResult::Other => "その他",
+        }
+    }
+
+    /// Returns whether the amount was debited.
+    pub fn is_debited(self) -> bool {
+        self == DebitResult::Debited
+    }
+}
+
+impl fmt::Display for DebitResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The date of a header record, such as the transfer date (取組日), which the
+/// format carries as `MMDD` without a year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RecordDate {
+    month: u8,
+    day: u8,
+}
+
+impl RecordDate {
+    /// Returns the date of a day in a month, or `None` if the month does not
+    /// have that day in any year.
+    pub fn new(month: u32, day: u32) -> Option<RecordDate> {
+        let days = match month {
+            2 => 29,
+            4 | 6 | 9 | 11 => 30,
+            1..=12 => 31,
+            _ => return None,
+        };
+        if !(1..=days).contains(&day) {
+            return None;
+        }
+        Some(RecordDate {
+            month: month as u8,
+            day: day as u8,
+        })
+    }
+
+    /// Returns the month, from 1 to 12.
+    pub fn month(self) -> u32 {
+        self.month.into()
+    }
+
+    /// Returns the day of the month, from 1 to 31.
+    pub fn day(self) -> u32 {
+        self.day.into()
+    }
+}
+
+impl fmt::Display for RecordDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}{:02}", self.month, self.day)
+    }
+}
+
+impl FromStr for RecordDate {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 4 || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(());
+        }
+        RecordDate::new(s[..2].parse().unwrap(), s[2..].parse().unwrap()).ok_or(())
+    }
+}
+
+/// The header record (ヘッダー・レコード), describing the requester and the
+/// account the transfers are paid from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderRecord {
+    /// The kind of the file, given by the type code (種別コード).
+    pub kind: TransferKind,
+    /// The code division (コード区分).
+    pub code_division: CodeDivision,
+    /// The requester code (依頼人コード).
+    pub requester_code: String,
+    /// The requester name (依頼人名).
+    pub requester_name: String,
+    /// The transfer date (取組日).
+    pub date: RecordDate,
+    /// The code of the source bank.
+    pub bank_code: BankCode,
+    /// The name of the source bank.
+    pub bank_name: String,
+    /// The code of the source branch.
+    pub branch_code: BranchCode,
+    /// The name of the source branch.
+    pub branch_name: String,
+    /// The type of the source account.
+    pub account_type: AccountType,
+    /// The number of the source account.
+    pub account_number: String,
+    /// The unused area at the end of the record (ダミー), usually blank.
+    pub dummy: String,
+}
+
+/// A data record (データ・レコード), describing a single transfer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataRecord {
+    /// The code of the recipient bank.
+    pub bank_code: BankCode,
+    /// The name of the recipient bank.
+    pub bank_name: String,
+    /// The code of the recipient branch.
+    pub branch_code: BranchCode,
+    /// The name of the recipient branch.
+    pub branch_name: String,
+    /// The clearing house number (手形交換所番号), usually blank.
+    pub clearing_house: String,
+    /// The type of the recipient account.
+    pub account_type: AccountType,
+    /// The number of the recipient account.
+    pub account_number: String,
+    /// The name of the recipient (受取人名).
+    pub holder_name: String,
+    /// The amount in yen.
+    pub amount: u64,
+    /// The new code (新規コード).
+    pub new_code: NewCode,
+    /// The first customer code (顧客コード1), the employee number (社員番号)
+    /// in payroll files, or the whole 20-character customer number (顧客番号)
+    /// in direct debit files.
+    pub customer_code1: String,
+    /// The second customer code (顧客コード2), the department code (所属コード)
+    /// in payroll files. It must be blank in direct debit files, which have no
+    /// room for it.
+    pub customer_code2: String,
+    /// The transfer designation (振込指定区分), `None` if blank as in payroll
+    /// and direct debit files.
+    pub transfer_designation: Option<TransferDesignation>,
+    /// Whether the identification (識別表示) is `Y`, which means that the
+    /// customer codes hold EDI information instead.
+    pub edi: bool,
+    /// The result code (振替結果コード) of direct debit result files, `None`
+    /// in requests and in other kinds of files.
+    pub result_code: Option<DebitResult>,
+    /// The unused area at the end of the record (ダミー), usually blank.
+    pub dummy: String,
+}
+
+/// The trailer record (トレーラ・レコード), with the totals of the data
+/// records.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrailerRecord {
+    /// The number of data records.
+    pub count: u64,
+    /// The total amount of the data records in yen.
+    pub total: u64,
+    /// The number of successful debits (振替済件数) of direct debit result
+    /// files. Zero in other files.
+    pub debited_count: u64,
+    /// The total amount of successful debits (振替済金額) of direct debit
+    /// result files. Zero in other files.
+    pub debited_total: u64,
+    /// The number of failed debits (振替不能件数) of direct debit result
+    /// files. Zero in other files.
+    pub failed_count: u64,
+    /// The total amount of failed debits (振替不能金額) of direct debit result
+    /// files. Zero in other files.
+    pub failed_total: u64,
+    /// The unused area at the end of the record (ダミー), usually blank.
+    pub dummy: String,
+}
+
+/// The end record (エンド・レコード), closing the file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EndRecord {
+    /// The unused area after the data division (ダミー), usually blank.
+    pub dummy: String,
+}
+
+/// Checks that a value fits a field of `len` characters, as a longer one
+/// would shift the fields after it.
+fn check_width(field: &'static str, value: &str, len: usize) -> Result<(), FormatError> {
+    if value.chars().count() > len {
+        return Err(FormatError::InvalidField {
+            field,
+            value: value.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Appends a numeric field, right-aligned and padded with zeros.
+fn push_numeric(
+    record: &mut String,
+    field: &'static str,
+    value: &str,
+    len: usize,
+) -> Result<(), FormatError> {
+    check_width(field, value, len)?;
+    record.push_str(&format!("{value:0>len$}"));
+    Ok(())
+}
+
+/// Appends a text field, left-aligned and padded with spaces.
+fn push_text(
+    record: &mut String,
+    field: &'static str,
+    value: &str,
+    len: usize,
+) -> Result<(), FormatError> {
+    check_width(field, value, len)?;
+    record.push_str(&format!("{value:<len$}"));
+    Ok(())
+}
+
+/// Returns the first of the errors found in a record.
+fn first_error(errors: Vec<FormatError>) -> FormatError {
+    errors.into_iter().next().expect("at least one error")
+}
+
+/// Appends a single-character code, or a space if there is none.
+fn push_code(record: &mut String, code: Option<char>) {
+    record.push(code.unwrap_or(' '));
+}
+
+/// Reads the fields of a record one after another, collecting an error for
+/// every field that does not have its form.
+struct Fields {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    errors: Vec<FormatError>,
+}
+
+impl Fields {
+    fn new(record: &str, line: usize) -> Result<Self, Vec<FormatError>> {
+        let chars: Vec<char> = record.chars().collect();
+        if chars.len() != RECORD_LEN {
+            return Err(vec![FormatError::InvalidRecordLength {
+                line,
+                len: chars.len(),
+            }]);
+        }
+        Ok(Fields {
+            chars,
+            pos: 0,
+            line,
+            errors: vec![],
+        })
+    }
+
+    fn raw(&mut self, len: usize) -> String {
+        let field = self.chars[self.pos..self.pos + len].iter().collect();
+        self.pos += len;
+        field
+    }
+
+    /// Reads a text field without its padding.
+    fn text(&mut self, len: usize) -> String {
+        self.raw(len).trim_end_matches(' ').to_string()
+    }
+
+    /// Records that the field just read, of `value`, is invalid.
+    fn invalid<T>(&mut self, field: &'static str, value: String) -> Option<T> {
+        self.errors.push(FormatError::InvalidRecord {
+            line: self.line,
+            column: self.pos - value.chars().count() + 1,
+            field,
+            value,
+        });
+        None
+    }
+
+    /// Reads a field of ASCII digits.
+    fn digits(&mut self, field: &'static str, len: usize) -> Option<String> {
+        let value = self.raw(len);
+        if !value.bytes().all(|b| b.is_ascii_digit()) {
+            return self.invalid(field, value);
+        }
+        Some(value)
+    }
+
+    fn number(&mut self, field: &'static str, len: usize) -> Option<u64> {
+        // At most twelve digits, which always fit.
+        self.digits(field, len)?.parse().ok()
+    }
+
+    fn parse<T: FromStr>(&mut self, field: &'static str, len: usize) -> Option<T> {
+        let value = self.raw(len);
+        match value.parse() {
+            Ok(parsed) => Some(parsed),
+            Err(_) => self.invalid(field, value),
+        }
+    }
+
+    /// Reads a single-character code.
+    fn code<T>(&mut self, field: &'static str, from_code: fn(char) -> Option<T>) -> Option<T> {
+        let value = self.raw(1);
+        match value.chars().next().and_then(from_code) {
+            Some(code) => Some(code),
+            None => self.invalid(field, value),
+        }
+    }
+
+    /// Reads a single-character code that may be left blank.
+    fn optional_code<T>(
+        &mut self,
+        field: &'static str,
+        from_code: fn(char) -> Option<T>,
+    ) -> Option<Option<T>> {
+        if self.chars[self.pos] == ' ' {
+            self.pos += 1;
+            return Some(None);
+        }
+        self.code(field, from_code).map(Some)
+    }
+
+    fn account_type(&mut self) -> Option<AccountType> {
+        self.code("account type", |c| {
+            AccountType::from_code(c.to_digit(10)? as u8)
+        })
+    }
+
+    /// Returns the record read from the fields, or every error found in them.
+    fn finish<T>(self, record: Option<T>) -> Result<T, Vec<FormatError>> {
+        match record {
+            Some(record) if self.errors.is_empty() => Ok(record),
+            _ => Err(self.errors),
+        }
+    }
+}
+
+impl HeaderRecord {
+    /// Writes the record as its 120 characters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FormatError::InvalidField`] if a field is longer than its
+    /// width in the record.
+    ///
+    /// # Examples
+    /// ```
+    /// let record = file.header.to_fixed_width().unwrap();
+    /// assert!(record.starts_with("121"));
+    /// ```
+    pub fn to_fixed_width(&self) -> Result<String, FormatError> {
+        let mut record = String::with_capacity(RECORD_LEN);
+        self.write(&mut record)?;
+        Ok(record)
+    }
+
+    /// Reads a record from its 120 characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - A string slice that holds the record, without a newline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record is not 120 characters long or a field
+    /// does not have its form. The error reports the record as line 1.
+    pub fn from_fixed_width(record: &str) -> Result<Self, FormatError> {
+        HeaderRecord::read(record, 1).map_err(first_error)
+    }
+
+    pub(super) fn write(&self, record: &mut String) -> Result<(), FormatError> {
+        record.push('1');
+        record.push_str(self.kind.code());
+        record.push(self.code_division.code());
+        push_numeric(
+            record,
+            "requester code",
+            &self.requester_code,
+            CUSTOMER_CODE_LEN,
+        )?;
+        push_text(
+            record,
+            "requester name",
+            &self.requester_name,
+            REQUESTER_NAME_LEN,
+        )?;
+        record.push_str(&self.date.to_string());
+        record.push_str(self.bank_code.as_str());
+        push_text(record, "bank name", &self.bank_name, BANK_KANA_LEN)?;
+        record.push_str(self.branch_code.as_str());
+        push_text(record, "branch name", &self.branch_name, BRANCH_KANA_LEN)?;
+        record.push_str(&self.account_type.code().to_string());
+        push_numeric(
+            record,
+            "account number",
+            &self.account_number,
+            ACCOUNT_NUMBER_LEN,
+        )?;
+        push_text(record, "dummy", &self.dummy, 17)
+    }
+
+    pub(super) fn read(record: &str, line: usize) -> Result<Self, Vec<FormatError>> {
+        let mut fields = Fields::new(record, line)?;
+        fields.raw(1);
+        let type_code = fields.raw(2);
+        let kind = match TransferKind::from_code(&type_code) {
+            Some(kind) => Some(kind),
+            None => fields.invalid("type code", type_code),
+        };
+        let code_division = fields.code("code division", CodeDivision::from_code);
+        let requester_code = fields.digits("requester code", CUSTOMER_CODE_LEN);
+        let requester_name = fields.text(REQUESTER_NAME_LEN);
+        let date = fields.parse("date", 4);
+        let bank_code = fields.parse("bank code", 4);
+        let bank_name = fields.text(BANK_KANA_LEN);
+        let branch_code = fields.parse("branch code", 3);
+        let branch_name = fields.text(BRANCH_KANA_LEN);
+        let account_type = fields.account_type();
+        let account_number = fields.digits("account number", ACCOUNT_NUMBER_LEN);
+        let dummy = fields.text(RECORD_LEN - fields.pos);
+        let header = (|| {
+            Some(HeaderRecord {
+                kind: kind?,
+                code_division: code_division?,
+                requester_code: requester_code?,
+                requester_name,
+                date: date?,
+                bank_code: bank_code?,
+                bank_name,
+                branch_code: branch_code?,
+                branch_name,
+                account_type: account_type?,
+                account_number: account_number?,
+                dummy,
+            })
+        })();
+        fields.finish(header)
+    }
+}
+
+impl DataRecord {
+    /// Writes the record as its 120 characters, in the layout of a kind of
+    /// file.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the file, which decides the layout of the
+    ///   fields after the new code.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FormatError::InvalidField`] if a field is longer than its
+    /// width in the record, or if `customer_code2` is set in a direct debit
+    /// record.
+    pub fn to_fixed_width(&self, kind: TransferKind) -> Result<String, FormatError> {
+        let mut record = String::with_capacity(RECORD_LEN);
+        self.write(&mut record, kind)?;
+        Ok(record)
+    }
+
+    /// Reads a record from its 120 characters, in the layout of a kind of
+    /// file.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - A string slice that holds the record, without a newline.
+    /// * `kind` - The kind of the file, as given by its header record.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record is not 120 characters long or a field
+    /// does not have its form. The error reports the record as line 1.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::format::{DataRecord, TransferKind};
+    /// let record = DataRecord::from_fixed_width(line, TransferKind::General).unwrap();
+    /// println!("{} {}", record.holder_name, record.amount);
+    /// ```
+    pub fn from_fixed_width(record: &str, kind: TransferKind) -> Result<Self, FormatError> {
+        DataRecord::read(record, 1, kind).map_err(first_error)
+    }
+
+    pub(super) fn write(&self, record: &mut String, kind: TransferKind) -> Result<(), FormatError> {
+        record.push('2');
+        record.push_str(self.bank_code.as_str());
+        push_text(record, "bank name", &self.bank_name, BANK_KANA_LEN)?;
+        record.push_str(self.branch_code.as_str());
+        push_text(record, "branch name", &self.branch_name, BRANCH_KANA_LEN)?;
+        push_text(record, "clearing house", &self.clearing_house, 4)?;
+        record.push_str(&self.account_type.code().to_string());
+        push_numeric(
+            record,
+            "account number",
+            &self.account_number,
+            ACCOUNT_NUMBER_LEN,
+        )?;
+        push_text(record, "holder name", &self.holder_name, HOLDER_NAME_LEN)?;
+        push_numeric(record, "amount", &self.amount.to_string(), AMOUNT_LEN)?;
+        record.push(self.new_code.code());
+        if kind == TransferKind::DirectDebit {
+            // The layout has no room for a second code.
+            check_width("customer code 2", &self.customer_code2, 0)?;
+            push_text(
+                record,
+                "customer code",
+                &self.customer_code1,
+                CUSTOMER_CODE_LEN * 2,
+            )?;
+            push_code(record, self.result_code.map(DebitResult::code));
+            push_text(record, "dummy", &self.dummy, 8)?;
+            return Ok(());
+        }
+        push_text(
+            record,
+            "customer code",
+            &self.customer_code1,
+            CUSTOMER_CODE_LEN,
+        )?;
+        push_text(
+            record,
+            "customer code",
+            &self.customer_code2,
+            CUSTOMER_CODE_LEN,
+        )?;
+        push_code(
+            record,
+            self.transfer_designation.map(TransferDesignation::code),
+        );
+        push_code(record, self.edi.then_some('Y'));
+        push_text(record, "dummy", &self.dummy, 7)
+    }
+
+    pub(super) fn read(
+        record: &str,
+        line: usize,
+        kind: TransferKind,
+    ) -> Result<Self, Vec<FormatError>> {
+        let mut fields = Fields::new(record, line)?;
+        fields.raw(1);
+        let bank_code = fields.parse("bank code", 4);
+        let bank_name = fields.text(BANK_KANA_LEN);
+        let branch_code = fields.parse("branch code", 3);
+        let branch_name = fields.text(BRANCH_KANA_LEN);
+        let clearing_house = fields.text(4);
+        let account_type = fields.account_type();
+        let account_number = fields.digits("account number", ACCOUNT_NUMBER_LEN);
+        let holder_name = fields.text(HOLDER_NAME_LEN);
+        let amount = fields.number("amount", AMOUNT_LEN);
+        let new_code = fields.code("new code", NewCode::from_code);
+        let (customer_code1, customer_code2, transfer_designation, edi, result_code) =
+            if kind == TransferKind::DirectDebit {
+                (
+                    fields.text(CUSTOMER_CODE_LEN * 2),
+                    String::new(),
+                    Some(None),
+                    Some(None),
+                    fields.optional_code("result code", DebitResult::from_code),
+                )
+            } else {
+                (
+                    fields.text(CUSTOMER_CODE_LEN),
+                    fields.text(CUSTOMER_CODE_LEN),
+                    fields.optional_code("transfer designation", TransferDesignation::from_code),
+                    fields.optional_code("identification", |c| (c == 'Y').then_some(())),
+                    Some(None),
+                )
+            };
+        let dummy = fields.text(RECORD_LEN - fields.pos);
+        let data = (|| {
+            Some(DataRecord {
+                bank_code: bank_code?,
+                bank_name,
+                branch_code: branch_code?,
+                branch_name,
+                clearing_house,
+                account_type: account_type?,
+                account_number: account_number?,
+                holder_name,
+                amount: amount?,
+                new_code: new_code?,
+                customer_code1,
+                customer_code2,
+                transfer_designation: transfer_designation?,
+                edi: edi?.is_some(),
+                result_code: result_code?,
+                dummy,
+            })
+        })();
+        fields.finish(data)
+    }
+}
+
+impl TrailerRecord {
+    /// Writes the record as its 120 characters, in the layout of a kind of
+    /// file.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the file. Only direct debit files carry the
+    ///   totals of successful and failed debits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FormatError::InvalidField`] if a field is longer than its
+    /// width in the record.
+    pub fn to_fixed_width(&self, kind: TransferKind) -> Result<String, FormatError> {
+        let mut record = String::with_capacity(RECORD_LEN);
+        self.write(&mut record, kind)?;
+        Ok(record)
+    }
+
+    /// Reads a record from its 120 characters, in the layout of a kind of
+    /// file.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - A string slice that holds the record, without a newline.
+    /// * `kind` - The kind of the file, as given by its header record.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record is not 120 characters long or a field
+    /// does not have its form. The error reports the record as line 1.
+    pub fn from_fixed_width(record: &str, kind: TransferKind) -> Result<Self, FormatError> {
+        TrailerRecord::read(record, 1, kind).map_err(first_error)
+    }
+
+    pub(super) fn write(&self, record: &mut String, kind: TransferKind) -> Result<(), FormatError> {
+        record.push('8');
+        push_numeric(record, "count", &self.count.to_string(), 6)?;
+        push_numeric(record, "total", &self.total.to_string(), 12)?;
+        if kind == TransferKind::DirectDebit {
+            push_numeric(record, "debited count", &self.debited_count.to_string(), 6)?;
+            push_numeric(record, "debited total", &self.debited_total.to_string(), 12)?;
+            push_numeric(record, "failed count", &self.failed_count.to_string(), 6)?;
+            push_numeric(record, "failed total", &self.failed_total.to_string(), 12)?;
+            push_text(record, "dummy", &self.dummy, 65)?;
+        } else {
+            push_text(record, "dummy", &self.dummy, 101)?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn read(
+        record: &str,
+        line: usize,
+        kind: TransferKind,
+    ) -> Result<Self, Vec<FormatError>> {
+        let mut fields = Fields::new(record, line)?;
+        fields.raw(1);
+        let count = fields.number("count", 6);
+        let total = fields.number("total", 12);
+        let mut debit_totals = [Some(0); 4];
+        if kind == TransferKind::DirectDebit {
+            debit_totals = [
+                fields.number("debited count", 6),
+                fields.number("debited total", 12),
+                fields.number("failed count", 6),
+                fields.number("failed total", 12),
+            ];
+        }
+        let [debited_count, debited_total, failed_count, failed_total] = debit_totals;
+        let dummy = fields.text(RECORD_LEN - fields.pos);
+        let trailer = (|| {
+            Some(TrailerRecord {
+                count: count?,
+                total: total?,
+                debited_count: debited_count?,
+                debited_total: debited_total?,
+                failed_count: failed_count?,
+                failed_total: failed_total?,
+                dummy,
+            })
+        })();
+        fields.finish(trailer)
+    }
+}
+
+impl EndRecord {
+    /// Writes the record as its 120 characters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FormatError::InvalidField`] if a field is longer than its
+    /// width in the record.
+    pub fn to_fixed_width(&self) -> Result<String, FormatError> {
+        let mut record = String::with_capacity(RECORD_LEN);
+        self.write(&mut record)?;
+        Ok(record)
+    }
+
+    /// Reads a record from its 120 characters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record is not 120 characters long. The error
+    /// reports the record as line 1.
+    pub fn from_fixed_width(record: &str) -> Result<Self, FormatError> {
+        EndRecord::read(record, 1).map_err(first_error)
+    }
+
+    pub(super) fn write(&self, record: &mut String) -> Result<(), FormatError> {
+        record.push('9');
+        push_text(record, "dummy", &self.dummy, RECORD_LEN - 1)
+    }
+
+    pub(super) fn read(record: &str, line: usize) -> Result<Self, Vec<FormatError>> {
+        let mut fields = Fields::new(record, line)?;
+        fields.raw(1);
+        Ok(EndRecord {
+            dummy: fields.text(RECORD_LEN - 1),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_width_records() {
+        let header = HeaderRecord {
+            kind: TransferKind::Salary,
+            code_division: CodeDivision::Jis,
+            requester_code: "0000000123".to_string(),
+            requester_name: "ｶ)ｻﾝﾌﾟﾙ".to_string(),
+            date: RecordDate::new(4, 25).unwrap(),
+            bank_code: "0001".parse().unwrap(),
+            bank_name: "ﾐｽﾞﾎ".to_string(),
+            branch_code: "001".parse().unwrap(),
+            branch_name: "ﾄｳｷﾖｳ".to_string(),
+            account_type: AccountType::Ordinary,
+            account_number: "1111111".to_string(),
+            dummy: String::new(),
+        };
+        let record = header.to_fixed_width().unwrap();
+        assert_eq!(record.chars().count(), RECORD_LEN);
+        assert!(record.starts_with("11100000000123ｶ)ｻﾝﾌﾟﾙ"));
+        assert_eq!(HeaderRecord::from_fixed_width(&record).unwrap(), header);
+        assert!(matches!(
+            HeaderRecord::from_fixed_width(&record.replacen("0425", "0230", 1)),
+            Err(FormatError::InvalidRecord {
+                line: 1,
+                field: "date",
+                ..
+            })
+        ));
+
+        let data = DataRecord {
+            bank_code: "0005".parse().unwrap(),
+            bank_name: "ﾐﾂﾋﾞｼﾕ-ｴﾌｼﾞｴｲ".to_string(),
+            branch_code: "001".parse().unwrap(),
+            branch_name: "ﾎﾝﾃﾝ".to_string(),
+            clearing_house: String::new(),
+            account_type: AccountType::Checking,
+            account_number: "0022222".to_string(),
+            holder_name: "ﾔﾏﾀﾞ ﾀﾛｳ".to_string(),
+            amount: 5000,
+            new_code: NewCode::First,
+            customer_code1: "0000000042".to_string(),
+            customer_code2: String::new(),
+            transfer_designation: Some(TransferDesignation::Documentary),
+            edi: true,
+            result_code: None,
+            dummy: String::new(),
+        };
+        let record = data.to_fixed_width(TransferKind::General).unwrap();
+        assert!(record.ends_with("000000500010000000042          8Y       "));
+        assert_eq!(
+            DataRecord::from_fixed_width(&record, TransferKind::General).unwrap(),
+            data
+        );
+
+        let trailer = TrailerRecord::from_fixed_width(
+            &format!("{:<120}", "8000001000000005000"),
+            TransferKind::General,
+        )
+        .unwrap();
+        assert_eq!((trailer.count, trailer.total), (1, 5000));
+        assert_eq!(
+            EndRecord::default().to_fixed_width().unwrap().trim_end(),
+            "9"
+        );
+
+        // An over-long field would shift the fields after it.
+        let too_large = DataRecord {
+            amount: 10_000_000_000,
+            ..data
+        };
+        assert_eq!(
+            too_large.to_fixed_width(TransferKind::General),
+            Err(FormatError::InvalidField {
+                field: "amount",
+                value: "10000000000".to_string()
+            })
+        );
+        let long_name = HeaderRecord {
+            requester_name: "ｱ".repeat(41),
+            ..header
+        };
+        assert!(matches!(
+            long_name.to_fixed_width(),
+            Err(FormatError::InvalidField {
+                field: "requester name",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_record_date() {
+        assert_eq!(RecordDate::new(2, 29).unwrap().to_string(), "0229");
+        assert_eq!(RecordDate::new(4, 31), None);
+        assert_eq!(RecordDate::new(13, 1), None);
+        assert_eq!("1231".parse::<RecordDate>().unwrap().month(), 12);
+        assert_eq!(
+            DebitResult::from_code('1'),
+            Some(DebitResult::InsufficientFunds)
+        );
+        assert_eq!(DebitResult::from_code('5'), None);
+        assert_eq!(DebitResult::StoppedByPayer.to_string(), "預金者都合");
+    }
+}
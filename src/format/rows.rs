@@ -0,0 +1,324 @@
+//! Transfer files from payment rows, as kept in spreadsheets.
+
+use super::{FormatError, TransferFileBuilder};
+use crate::{
+    account::{AccountType, BankAccount},
+    normalize::to_holder_kana,
+    BankCode, BranchCode,
+};
+
+/// A payment as a row of a spreadsheet or a CSV file, with its columns as
+/// typed by people rather than as the format carries them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentRow {
+    /// The bank code, such as `0001`.
+    pub bank_code: String,
+    /// The branch code, such as `001`.
+    pub branch_code: String,
+    /// The account type, in any form [`AccountType`] parses, such as `普通`
+    /// or `1`.
+    pub account_type: String,
+    /// The account number, up to seven digits.
+    pub number: String,
+    /// The name of the account holder, in katakana or hiragana of either
+    /// width.
+    pub holder_kana: String,
+    /// The amount in yen.
+    pub amount: u64,
+}
+
+/// Splits a line of CSV into its fields, with the column each field starts
+/// at, counting characters from 1. Fields may be double-quoted to hold commas,
+/// with `""` standing for a quote.
+fn split_csv_line(line: &str) -> Vec<(usize, String)> {
+    let mut fields = vec![(1, String::new())];
+    let mut quoted = false;
+    let mut chars = line.chars().zip(1..).peekable();
+    while let Some((c, column)) = chars.next() {
+        let (_, field) = fields.last_mut().unwrap();
+        match c {
+            '"' if quoted && chars.peek().map(|&(c, _)| c) == Some('"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push((column + 1, String::new())),
+            _ => field.push(c),
+        }
+    }
+    fields
+}
+
+impl PaymentRow {
+    /// Parses CSV into payment rows.
+    ///
+    /// Each line has the columns bank code, branch code, account type,
+    /// account number, holder name, and amount, in this order. Empty lines
+    /// are skipped, and so is a first line whose amount is not a number, such
+    /// as a line of column names. The amount may have thousands separators
+    /// if it is quoted, as in `"10,000"`.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - A string slice that holds the CSV.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error that [`PaymentRow::parse_csv_all`] finds.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::format::PaymentRow;
+    /// let rows = PaymentRow::parse_csv("0001,001,普通,1234567,ヤマダ タロウ,10000\n").unwrap();
+    /// assert_eq!(rows[0].amount, 10000);
+    /// ```
+    pub fn parse_csv(input: &str) -> Result<Vec<PaymentRow>, FormatError> {
+        PaymentRow::parse_csv_all(input).map_err(|errors| errors.into_iter().next().unwrap())
+    }
+
+    /// Parses CSV into payment rows like [`PaymentRow::parse_csv`], but reads
+    /// the whole input instead of stopping at the first problem.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FormatError::InvalidRecord`] for every row that does not
+    /// have six columns, and for every bank code, branch code, account type,
+    /// or amount that does not parse, with the line and the column the field
+    /// starts at.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::format::PaymentRow;
+    /// if let Err(errors) = PaymentRow::parse_csv_all(&std::fs::read_to_string("payments.csv").unwrap()) {
+    ///     for error in errors {
+    ///         println!("{error}");
+    ///     }
+    /// }
+    /// ```
+    pub fn parse_csv_all(input: &str) -> Result<Vec<PaymentRow>, Vec<FormatError>> {
+        let mut rows = vec![];
+        let mut errors = vec![];
+        for (line, number) in input.lines().zip(1..) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut invalid = |field, column, value: &str| {
+                errors.push(FormatError::InvalidRecord {
+                    line: number,
+                    column,
+                    field,
+                    value: value.to_string(),
+                })
+            };
+            let fields = split_csv_line(line);
+            let [bank_code, branch_code, account_type, account_number, holder_kana, amount] =
+                &fields[..]
+            else {
+                invalid("row", 1, line);
+                continue;
+            };
+            let digits: String = amount.1.trim().chars().filter(|&c| c != ',').collect();
+            let parsed_amount = digits.parse();
+            if parsed_amount.is_err() && number == 1 {
+                continue;
+            }
+            let mut valid = true;
+            let mut check = |field, (column, value): &(usize, String), ok: bool| {
+                if !ok {
+                    invalid(field, *column, value);
+                    valid = false;
+                }
+            };
+            check(
+                "bank code",
+                bank_code,
+                bank_code.1.trim().parse::<BankCode>().is_ok(),
+            );
+            check(
+                "branch code",
+                branch_code,
+                branch_code.1.trim().parse::<BranchCode>().is_ok(),
+            );
+            check(
+                "account type",
+                account_type,
+                account_type.1.parse::<AccountType>().is_ok(),
+            );
+            check("amount", amount, parsed_amount.is_ok());
+            if let (true, Ok(amount)) = (valid, parsed_amount) {
+                rows.push(PaymentRow {
+                    bank_code: bank_code.1.trim().to_string(),
+                    branch_code: branch_code.1.trim().to_string(),
+                    account_type: account_type.1.trim().to_string(),
+                    number: account_number.1.trim().to_string(),
+                    holder_kana: holder_kana.1.trim().to_string(),
+                    amount,
+                });
+            }
+        }
+        if errors.is_empty() {
+            Ok(rows)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Converts the row into an account, with the holder name converted by
+    /// [`to_holder_kana`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FormatError::InvalidField`] if the bank code, the branch
+    /// code, or the account type does not parse. The account itself is only
+    /// checked by [`BankAccount::validate`].
+    pub fn to_account(&self) -> Result<BankAccount, FormatError> {
+        let invalid = |field, value: &str| FormatError::InvalidField {
+            field,
+            value: value.to_string(),
+        };
+        Ok(BankAccount {
+            bank_code: self
+                .bank_code
+                .parse()
+                .map_err(|_| invalid("bank code", &self.bank_code))?,
+            branch_code: self
+                .branch_code
+                .parse()
+                .map_err(|_| invalid("branch code", &self.branch_code))?,
+            account_type: self
+                .account_type
+                .parse::<AccountType>()
+                .map_err(|_| invalid("account type", &self.account_type))?,
+            number: self.number.clone(),
+            holder_kana: to_holder_kana(&self.holder_kana),
+        })
+    }
+}
+
+impl TransferFileBuilder<'_> {
+    /// Adds a transfer for every payment row, with the bank and branch names
+    /// filled in from the dataset as for [`TransferFileBuilder::transfer`].
+    ///
+    /// A row that fails [`PaymentRow::to_account`] makes
+    /// [`TransferFileBuilder::build`] return its error.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::format::PaymentRow;
+    /// let rows = PaymentRow::parse_csv(&std::fs::read_to_string("payments.csv").unwrap()).unwrap();
+    /// let file = zengin
+    ///     .transfer_file()
+    ///     .requester_code("1234567890")
+    ///     .transfer_date(4, 25)
+    ///     .source_account(source)
+    ///     .rows(rows)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn rows<I: IntoIterator<Item = PaymentRow>>(mut self, rows: I) -> Self {
+        for row in rows {
+            match row.to_account() {
+                Ok(account) => self = self.transfer(account, row.amount),
+                Err(error) => {
+                    self.error.get_or_insert(error);
+                }
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::tests::builder;
+
+    #[test]
+    fn test_parse_csv() {
+        let rows = PaymentRow::parse_csv(
+            "銀行,支店,種別,番号,名義,金額\n\
+             0005,001,普通,22222,ヤマダ　タロウ,\"10,000\"\n\
+             \n\
+             0009,625,当座,3333333,\"ｶ)ｻﾝﾌﾟﾙ,ｼﾃﾝ\",2500\n",
+        )
+        .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].amount, 10000);
+        assert_eq!(rows[1].holder_kana, "ｶ)ｻﾝﾌﾟﾙ,ｼﾃﾝ");
+        assert_eq!(
+            PaymentRow::parse_csv("0005,001,普通,22222,ﾀﾛｳ,1\n0005,001,普通,22222,ﾀﾛｳ,x"),
+            Err(FormatError::InvalidRecord {
+                line: 2,
+                column: 23,
+                field: "amount",
+                value: "x".to_string()
+            })
+        );
+        assert!(matches!(
+            PaymentRow::parse_csv("0005,001,22222,ﾀﾛｳ,1"),
+            Err(FormatError::InvalidRecord {
+                line: 1,
+                field: "row",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_csv_all() {
+        let errors = PaymentRow::parse_csv_all(
+            "0005,001,普通,22222,ﾀﾛｳ,1\n\
+             5,001,定期,22222,ﾀﾛｳ,1\n\
+             0005,001,普通,22222\n\
+             0005,01,普通,22222,ﾀﾛｳ,x\n",
+        )
+        .unwrap_err();
+        let found: Vec<_> = errors
+            .iter()
+            .map(|e| match e {
+                FormatError::InvalidRecord {
+                    line,
+                    column,
+                    field,
+                    ..
+                } => (*line, *column, *field),
+                _ => panic!("unexpected error {e:?}"),
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (2, 1, "bank code"),
+                (2, 7, "account type"),
+                (3, 1, "row"),
+                (4, 6, "branch code"),
+                (4, 22, "amount"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rows() {
+        let zengin = crate::fixture();
+        let rows = PaymentRow::parse_csv(
+            "0005,001,普通,22222,やまだ　たろう,10000\n\
+             0005,001,普通,33333,キャロル　ジョー,500\n",
+        )
+        .unwrap();
+        let file = builder(&zengin).rows(rows.clone()).build_file().unwrap();
+        assert_eq!(file.data[0].bank_name, "ﾐﾂﾋﾞｼﾕ-ｴﾌｼﾞｴｲ");
+        assert_eq!(file.data[0].holder_name, "ﾔﾏﾀﾞ ﾀﾛｳ");
+        assert_eq!(file.data[1].holder_name, "ｷﾔﾛﾙ ｼﾞﾖ-");
+        assert_eq!(file.trailer.total, 10500);
+
+        let mut invalid = rows[0].clone();
+        invalid.account_type = "定期".to_string();
+        assert_eq!(
+            builder(&zengin).rows([invalid]).build(),
+            Err(FormatError::InvalidField {
+                field: "account type",
+                value: "定期".to_string()
+            })
+        );
+    }
+}
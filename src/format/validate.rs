@@ -0,0 +1,231 @@
+//! Cross-validation of transfer files against the dataset.
+
+use super::{DebitResult, TransferFile, TransferKind};
+use crate::{normalize::normalize, ValidationError, Zengin};
+use std::fmt;
+
+/// A problem found by [`Zengin::validate_transfer_file`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FileIssue {
+    /// The bank or branch of the record on a line does not exist.
+    UnknownCode { line: usize, error: ValidationError },
+    /// The bank or branch name of the record on a line is not the registered
+    /// name, as returned by [`Bank::zengin_kana`](crate::Bank::zengin_kana).
+    NameMismatch {
+        line: usize,
+        field: &'static str,
+        expected: String,
+        actual: String,
+    },
+    /// A total of the trailer record does not match the data records.
+    TotalMismatch {
+        field: &'static str,
+        expected: u64,
+        actual: u64,
+    },
+}
+
+impl FileIssue {
+    /// Returns the line of the record with the problem, as given by
+    /// [`TransferFile::record_lines`].
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            FileIssue::UnknownCode { line, .. } | FileIssue::NameMismatch { line, .. } => {
+                Some(*line)
+            }
+            FileIssue::TotalMismatch { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for FileIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileIssue::UnknownCode { line, error } => write!(f, "line {line}: {error}"),
+            FileIssue::NameMismatch {
+                line,
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "line {line}: {field} {actual:?} does not match the registered {expected:?}"
+            ),
+            FileIssue::TotalMismatch {
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "trailer {field} is {actual}, but the data records add up to {expected}"
+            ),
+        }
+    }
+}
+
+/// The result of [`Zengin::validate_transfer_file`], with every problem found
+/// in the file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// The problems, in the order of the records they were found in.
+    pub issues: Vec<FileIssue>,
+}
+
+impl ValidationReport {
+    /// Returns whether the file has no problems.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl Zengin {
+    /// Checks a transfer file against the dataset before it is submitted.
+    ///
+    /// Every record, including the header, must name an existing bank and
+    /// branch, and its bank and branch names must match the registered ones
+    /// once [`normalize`]d, so small kana such as `ｼｭ` match `ｼﾕ`. The count
+    /// and totals of the trailer record must add up to the data records, and
+    /// in direct debit files the debited and failed totals must match the
+    /// result codes.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The transfer file, as parsed by [`TransferFile::parse`].
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{format::TransferFile, Zengin};
+    /// let zengin = Zengin::new().unwrap();
+    /// let file = TransferFile::parse(&std::fs::read_to_string("transfers.txt").unwrap()).unwrap();
+    /// for issue in zengin.validate_transfer_file(&file).issues {
+    ///     println!("{issue}");
+    /// }
+    /// ```
+    pub fn validate_transfer_file(&self, file: &TransferFile) -> ValidationReport {
+        let mut issues = vec![];
+        let records = std::iter::once((
+            &file.header.bank_code,
+            &file.header.branch_code,
+            &file.header.bank_name,
+            &file.header.branch_name,
+        ))
+        .chain(file.data.iter().map(|data| {
+            (
+                &data.bank_code,
+                &data.branch_code,
+                &data.bank_name,
+                &data.branch_name,
+            )
+        }));
+        for ((bank_code, branch_code, bank_name, branch_name), line) in
+            records.zip(file.record_lines())
+        {
+            let (bank, branch) = match self.validate(bank_code.as_str(), branch_code.as_str()) {
+                Ok(found) => found,
+                Err(error) => {
+                    issues.push(FileIssue::UnknownCode { line, error });
+                    continue;
+                }
+            };
+            for (field, expected, actual) in [
+                ("bank name", bank.zengin_kana(), bank_name),
+                ("branch name", branch.zengin_kana(), branch_name),
+            ] {
+                if normalize(&expected) != normalize(actual) {
+                    issues.push(FileIssue::NameMismatch {
+                        line,
+                        field,
+                        expected,
+                        actual: actual.clone(),
+                    });
+                }
+            }
+        }
+
+        let sum = |filter: &dyn Fn(Option<DebitResult>) -> bool| {
+            let records = file.data.iter().filter(|data| filter(data.result_code));
+            records.fold((0, 0), |(count, total), data| {
+                (count + 1, total + data.amount)
+            })
+        };
+        let (count, total) = sum(&|_| true);
+        let mut totals = vec![
+            ("count", count, file.trailer.count),
+            ("total", total, file.trailer.total),
+        ];
+        if file.kind() == TransferKind::DirectDebit {
+            let (debited_count, debited_total) =
+                sum(&|result| result == Some(DebitResult::Debited));
+            let (failed_count, failed_total) =
+                sum(&|result| result.is_some_and(|result| !result.is_debited()));
+            totals.extend([
+                ("debited count", debited_count, file.trailer.debited_count),
+                ("debited total", debited_total, file.trailer.debited_total),
+                ("failed count", failed_count, file.trailer.failed_count),
+                ("failed total", failed_total, file.trailer.failed_total),
+            ]);
+        }
+        for (field, expected, actual) in totals {
+            if expected != actual {
+                issues.push(FileIssue::TotalMismatch {
+                    field,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        ValidationReport { issues }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::tests::{account, builder};
+
+    #[test]
+    fn test_validate_transfer_file() {
+        let zengin = crate::fixture();
+        let mut file = builder(&zengin)
+            .kind(TransferKind::DirectDebit)
+            .transfer_date(4, 27)
+            .debit(account("0005", "001", "22222", "ﾔﾏﾀﾞ ﾀﾛｳ"), 980, "1")
+            .debit(account("0009", "625", "33333", "ｽｽﾞｷ ﾊﾅｺ"), 1980, "2")
+            .build_file()
+            .unwrap();
+        assert!(zengin.validate_transfer_file(&file).is_ok());
+
+        file.data[0].bank_name = "ﾐﾂﾋﾞｼｭ-ｴﾌｼﾞｴｲ".to_string();
+        file.data[0].result_code = Some(DebitResult::Debited);
+        file.trailer.debited_count = 1;
+        file.trailer.debited_total = 980;
+        assert!(zengin.validate_transfer_file(&file).is_ok());
+
+        file.data[1].branch_code = "999".parse().unwrap();
+        file.header.branch_name = "ｼﾝｼﾞﾕｸ".to_string();
+        file.trailer.total = 3000;
+        let issues = zengin.validate_transfer_file(&file).issues;
+        assert_eq!(issues.len(), 3);
+        assert_eq!(
+            issues[0],
+            FileIssue::NameMismatch {
+                line: 1,
+                field: "branch name",
+                expected: "ﾄｳｷﾖｳ".to_string(),
+                actual: "ｼﾝｼﾞﾕｸ".to_string(),
+            }
+        );
+        assert!(matches!(
+            issues[1],
+            FileIssue::UnknownCode {
+                line: 3,
+                error: ValidationError::BranchNotFound { .. }
+            }
+        ));
+        assert_eq!(
+            issues[2].to_string(),
+            "trailer total is 3000, but the data records add up to 2960"
+        );
+    }
+}
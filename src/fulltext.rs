@@ -0,0 +1,211 @@
+use crate::{normalize::normalize, Bank, Branch, Record, Zengin, ZenginError};
+use tantivy::{
+    collector::TopDocs,
+    query::{BooleanQuery, Occur, Query, TermQuery},
+    schema::{
+        Field, IndexRecordOption, Schema, TantivyDocument, TextFieldIndexing, TextOptions, Value,
+        STORED,
+    },
+    tokenizer::{NgramTokenizer, TextAnalyzer},
+    Index, IndexWriter, Searcher, Term,
+};
+
+/// The name the bigram tokenizer is registered under.
+const TOKENIZER: &str = "zengin_ngram";
+
+/// The smallest memory budget tantivy accepts for an index writer.
+const WRITER_MEMORY_BUDGET: usize = 15_000_000;
+
+/// A full-text search result of [`Zengin::search`].
+#[derive(Debug, Clone, Copy)]
+pub struct SearchHit<'a> {
+    /// The relevance score of the hit. Higher is more relevant.
+    pub score: f32,
+    /// The matching bank, or the bank of the matching branch.
+    pub bank: &'a Bank,
+    /// The matching branch, or `None` if the bank itself matched.
+    pub branch: Option<&'a Branch>,
+}
+
+/// An in-memory tantivy index with one document per bank and one per branch.
+///
+/// Japanese text has no spaces between words, so the names are normalized
+/// and split into overlapping character bigrams rather than into words.
+/// Branch documents also hold the names of their bank, so that a query can
+/// name both the bank and the branch.
+pub(crate) struct FullTextIndex {
+    searcher: Searcher,
+    text: Field,
+    bank_code: Field,
+    branch_code: Field,
+}
+
+impl FullTextIndex {
+    pub(crate) fn new(zengin: &Zengin) -> Result<FullTextIndex, ZenginError> {
+        let mut schema = Schema::builder();
+        let indexing = TextFieldIndexing::default()
+            .set_tokenizer(TOKENIZER)
+            .set_index_option(IndexRecordOption::WithFreqs);
+        let text = schema.add_text_field(
+            "text",
+            TextOptions::default().set_indexing_options(indexing),
+        );
+        let bank_code = schema.add_text_field("bank_code", STORED);
+        let branch_code = schema.add_text_field("branch_code", STORED);
+
+        let index = Index::create_in_ram(schema.build());
+        index.tokenizers().register(
+            TOKENIZER,
+            TextAnalyzer::builder(NgramTokenizer::new(1, 2, false)?).build(),
+        );
+
+        let mut writer: IndexWriter = index.writer_with_num_threads(1, WRITER_MEMORY_BUDGET)?;
+        for bank in zengin.banks.values() {
+            let bank_text = normalized_names(bank);
+            let mut doc = TantivyDocument::new();
+            doc.add_text(text, &bank_text);
+            doc.add_text(bank_code, &bank.code);
+            writer.add_document(doc)?;
+
+            for branch in bank.branches.values() {
+                let mut doc = TantivyDocument::new();
+                doc.add_text(text, format!("{bank_text} {}", normalized_names(branch)));
+                doc.add_text(bank_code, &bank.code);
+                doc.add_text(branch_code, &branch.code);
+                writer.add_document(doc)?;
+            }
+        }
+        writer.commit()?;
+
+        Ok(FullTextIndex {
+            searcher: index.reader()?.searcher(),
+            text,
+            bank_code,
+            branch_code,
+        })
+    }
+
+    /// Builds a query that requires every whitespace-separated word of `query`.
+    fn parse(&self, query: &str) -> Option<BooleanQuery> {
+        let words: Vec<(Occur, Box<dyn Query>)> = query
+            .split_whitespace()
+            .map(|word| {
+                let grams: Vec<(Occur, Box<dyn Query>)> = grams(&normalize(word))
+                    .into_iter()
+                    .map(|gram| {
+                        let term = Term::from_field_text(self.text, &gram);
+                        let query: Box<dyn Query> =
+                            Box::new(TermQuery::new(term, IndexRecordOption::WithFreqs));
+                        (Occur::Must, query)
+                    })
+                    .collect();
+                let query: Box<dyn Query> = Box::new(BooleanQuery::new(grams));
+                (Occur::Must, query)
+            })
+            .collect();
+        (!words.is_empty()).then(|| BooleanQuery::new(words))
+    }
+}
+
+fn normalized_names(record: &impl Record) -> String {
+    record.names().map(normalize).join(" ")
+}
+
+/// Splits a normalized word into the terms to look up: its bigrams, or the
+/// word itself if it is a single character.
+fn grams(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    if chars.len() < 2 {
+        return vec![word.to_string()];
+    }
+    chars.windows(2).map(|pair| pair.iter().collect()).collect()
+}
+
+impl Zengin {
+    /// Searches banks and branches by full text, most relevant first.
+    ///
+    /// Every whitespace-separated word of the query has to appear in the
+    /// name, kana, hiragana, or romanized name of a bank, or of a branch and
+    /// its bank, so `みずほ 新宿` finds the 新宿 branch of みずほ. Words are
+    /// compared after [`normalize`](crate::normalize::normalize), so script
+    /// and character width do not matter. Hits are scored with BM25, which
+    /// ranks banks and branches with shorter names higher.
+    ///
+    /// This function is available with the `tantivy` feature. The index is
+    /// built in memory on the first call, which takes a moment for the full
+    /// dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A string slice that holds the words to search for.
+    /// * `limit` - The maximum number of hits to return.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if building or searching the index fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// for hit in zengin.search("みずほ 新宿", 10).unwrap() {
+    ///     match hit.branch {
+    ///         Some(branch) => println!("{} {}", hit.bank.name, branch.name),
+    ///         None => println!("{}", hit.bank.name),
+    ///     }
+    /// }
+    /// ```
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchHit<'_>>, ZenginError> {
+        let index = match self.fulltext_index.get() {
+            Some(index) => index,
+            None => {
+                let index = FullTextIndex::new(self)?;
+                self.fulltext_index.get_or_init(|| index)
+            }
+        };
+        let Some(query) = index.parse(query) else {
+            return Ok(Vec::new());
+        };
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut hits = Vec::new();
+        for (score, address) in index.searcher.search(&query, &TopDocs::with_limit(limit))? {
+            let doc: TantivyDocument = index.searcher.doc(address)?;
+            let code = |field| doc.get_first(field).and_then(|value| value.as_str());
+            let Some(bank) = code(index.bank_code).and_then(|code| self.banks.get(code)) else {
+                continue;
+            };
+            let branch = code(index.branch_code).and_then(|code| bank.branches.get(code));
+            hits.push(SearchHit {
+                score,
+                bank,
+                branch,
+            });
+        }
+        Ok(hits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture;
+
+    #[test]
+    fn test_search() {
+        let zengin = fixture();
+
+        let hits = zengin.search("みずほ 新宿", 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].bank.code, "0001");
+        assert_eq!(hits[0].branch.map(|b| b.code.as_str()), Some("110"));
+
+        let hits = zengin.search("ミズホ", 10).unwrap();
+        assert_eq!(hits[0].bank.code, "0001");
+        assert!(hits[0].branch.is_none());
+
+        assert!(zengin.search("  ", 10).unwrap().is_empty());
+        assert!(zengin.search("存在しない", 10).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+//! async-graphql objects of the dataset, for GraphQL servers and gateways.
+//!
+//! [`Bank`] and [`Branch`] are GraphQL objects with the fields of the
+//! dataset, and [`Query`] is a query root over a [`Zengin`] that can serve as
+//! the root of a schema as is, or be merged into a larger one with
+//! `MergedObject`.
+//!
+//! # Examples
+//! ```
+//! use async_graphql::{EmptyMutation, EmptySubscription, Schema};
+//! use std::sync::Arc;
+//! use zengin::{graphql::Query, Zengin};
+//! let query = Query::new(Arc::new(Zengin::new().unwrap()));
+//! let schema = Schema::new(query, EmptyMutation, EmptySubscription);
+//! let response = schema
+//!     .execute(r#"{ branch(bankCode: "0001", code: "110") { name } }"#)
+//!     .await;
+//! ```
+
+use crate::{Bank, Branch, Zengin};
+use async_graphql::Object;
+use std::sync::Arc;
+
+/// A bank of the dataset.
+#[Object]
+impl Bank {
+    /// The four-digit bank code, such as "0001".
+    async fn code(&self) -> &str {
+        &self.code
+    }
+
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The name in full-width katakana.
+    async fn kana(&self) -> &str {
+        &self.kana
+    }
+
+    /// The name in hiragana.
+    async fn hira(&self) -> &str {
+        &self.hira
+    }
+
+    /// The romanized name.
+    async fn roma(&self) -> &str {
+        &self.roma
+    }
+
+    /// A branch of the bank by its code.
+    #[graphql(name = "branch")]
+    async fn branch_by_code(&self, code: String) -> Option<&Branch> {
+        self.get_branch(code.as_str())
+    }
+
+    /// The branches of the bank in code order, or those matching a search
+    /// in any of their names, most relevant first.
+    async fn branches(&self, search: Option<String>) -> Vec<&Branch> {
+        match search {
+            Some(search) => self
+                .find_branches_ranked(&search)
+                .into_iter()
+                .map(|(_, branch)| branch)
+                .collect(),
+            None => self.iter().map(|(_, branch)| branch).collect(),
+        }
+    }
+}
+
+/// A branch of a bank.
+#[Object]
+impl Branch {
+    /// The three-digit branch code, such as "001".
+    async fn code(&self) -> &str {
+        &self.code
+    }
+
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The name in full-width katakana.
+    async fn kana(&self) -> &str {
+        &self.kana
+    }
+
+    /// The name in hiragana.
+    async fn hira(&self) -> &str {
+        &self.hira
+    }
+
+    /// The romanized name.
+    async fn roma(&self) -> &str {
+        &self.roma
+    }
+}
+
+/// A query root with the `bank`, `banks`, and `branch` fields.
+pub struct Query {
+    zengin: Arc<Zengin>,
+}
+
+impl Query {
+    /// Creates a query root over a dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `zengin` - The dataset, which can be shared with the rest of the
+    ///   application.
+    pub fn new(zengin: Arc<Zengin>) -> Self {
+        Query { zengin }
+    }
+}
+
+#[Object]
+impl Query {
+    /// A bank by its code. Stripped leading zeros are restored, so "1" finds
+    /// bank 0001.
+    async fn bank(&self, code: String) -> Option<&Bank> {
+        self.zengin.get_bank(code.as_str())
+    }
+
+    /// Every bank in code order, or the banks matching a search in any of
+    /// their names, most relevant first.
+    async fn banks(&self, search: Option<String>) -> Vec<&Bank> {
+        match search {
+            Some(search) => self
+                .zengin
+                .find_banks_ranked(&search)
+                .into_iter()
+                .map(|(_, bank)| bank)
+                .collect(),
+            None => self.zengin.iter().map(|(_, bank)| bank).collect(),
+        }
+    }
+
+    /// A branch by the code of its bank and its own code.
+    async fn branch(&self, bank_code: String, code: String) -> Option<&Branch> {
+        self.zengin
+            .get_branch(bank_code.as_str(), code.as_str())
+            .map(|(_, branch)| branch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::{value, EmptyMutation, EmptySubscription, Schema};
+
+    #[test]
+    fn test_query() {
+        let query = Query::new(Arc::new(crate::fixture()));
+        let schema = Schema::new(query, EmptyMutation, EmptySubscription);
+        let execute = |request: &str| futures_executor::block_on(schema.execute(request)).data;
+
+        assert_eq!(
+            execute(r#"{ bank(code: "1") { code name branch(code: "110") { name } } }"#),
+            value!({ "bank": { "code": "0001", "name": "みずほ", "branch": { "name": "新宿" } } })
+        );
+        assert_eq!(
+            execute(r#"{ branch(bankCode: "0005", code: "001") { kana } }"#),
+            value!({ "branch": { "kana": "ホンテン" } })
+        );
+        assert_eq!(
+            execute(r#"{ banks(search: "みずほ") { code } }"#),
+            value!({ "banks": [{ "code": "0001" }, { "code": "0289" }] })
+        );
+        assert_eq!(
+            execute(r#"{ bank(code: "9999") { code } }"#),
+            value!({ "bank": null })
+        );
+    }
+}
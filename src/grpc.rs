@@ -0,0 +1,204 @@
+//! A tonic implementation of the `ZenginService` gRPC service of
+//! `proto/zengin.proto`.
+//!
+//! # Examples
+//! ```
+//! use std::sync::Arc;
+//! use zengin::{grpc::ZenginGrpc, Zengin};
+//! let service = ZenginGrpc::new(Arc::new(Zengin::new().unwrap()));
+//! tonic::transport::Server::builder()
+//!     .add_service(service.into_server())
+//!     .serve("0.0.0.0:50051".parse().unwrap())
+//!     .await
+//!     .unwrap();
+//! ```
+
+use crate::{
+    proto::{
+        self,
+        zengin_service_server::{ZenginService, ZenginServiceServer},
+    },
+    Record, Zengin,
+};
+use std::{pin::Pin, sync::Arc};
+use tonic::{codegen::tokio_stream, Request, Response, Status};
+
+/// The `ZenginService` service over a dataset.
+#[derive(Clone)]
+pub struct ZenginGrpc {
+    zengin: Arc<Zengin>,
+}
+
+impl ZenginGrpc {
+    /// Creates the service over a dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `zengin` - The dataset, which can be shared with the rest of the
+    ///   application.
+    pub fn new(zengin: Arc<Zengin>) -> Self {
+        ZenginGrpc { zengin }
+    }
+
+    /// Returns a tower service that routes gRPC requests to this service,
+    /// for `tonic::transport::Server::add_service`.
+    pub fn into_server(self) -> ZenginServiceServer<Self> {
+        ZenginServiceServer::new(self)
+    }
+}
+
+/// Returns the message of a bank, with or without its branches.
+fn bank_message(bank: &crate::Bank, include_branches: bool) -> proto::Bank {
+    if include_branches {
+        return bank.into();
+    }
+    let [name, kana, hira, roma] = bank.names().map(str::to_string);
+    proto::Bank {
+        code: bank.code().to_string(),
+        name,
+        kana,
+        hira,
+        roma,
+        branches: vec![],
+    }
+}
+
+#[tonic::async_trait]
+impl ZenginService for ZenginGrpc {
+    async fn lookup_bank(
+        &self,
+        request: Request<proto::LookupBankRequest>,
+    ) -> Result<Response<proto::Bank>, Status> {
+        let request = request.into_inner();
+        match self.zengin.get_bank(request.code.as_str()) {
+            Some(bank) => Ok(Response::new(bank_message(bank, request.include_branches))),
+            None => Err(Status::not_found(format!(
+                "no bank with code {:?}",
+                request.code
+            ))),
+        }
+    }
+
+    async fn lookup_branch(
+        &self,
+        request: Request<proto::LookupBranchRequest>,
+    ) -> Result<Response<proto::Branch>, Status> {
+        let request = request.into_inner();
+        match self
+            .zengin
+            .get_branch(request.bank_code.as_str(), request.code.as_str())
+        {
+            Some((_, branch)) => Ok(Response::new(branch.into())),
+            None => Err(Status::not_found(format!(
+                "no branch with code {:?} of bank {:?}",
+                request.code, request.bank_code
+            ))),
+        }
+    }
+
+    async fn search_banks(
+        &self,
+        request: Request<proto::SearchBanksRequest>,
+    ) -> Result<Response<proto::SearchBanksResponse>, Status> {
+        let request = request.into_inner();
+        let limit = match request.limit {
+            0 => usize::MAX,
+            limit => limit as usize,
+        };
+        let banks = self
+            .zengin
+            .find_banks_ranked(&request.query)
+            .into_iter()
+            .take(limit)
+            .map(|(_, bank)| bank_message(bank, false))
+            .collect();
+        Ok(Response::new(proto::SearchBanksResponse { banks }))
+    }
+
+    type StreamAllBanksStream =
+        Pin<Box<dyn tokio_stream::Stream<Item = Result<proto::Bank, Status>> + Send>>;
+
+    async fn stream_all_banks(
+        &self,
+        request: Request<proto::StreamAllBanksRequest>,
+    ) -> Result<Response<Self::StreamAllBanksStream>, Status> {
+        let include_branches = request.into_inner().include_branches;
+        // Only the codes are collected up front, for their order; each bank
+        // is converted when the client is ready for it.
+        let zengin = Arc::clone(&self.zengin);
+        let codes: Vec<_> = zengin
+            .bank_codes()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let banks = codes
+            .into_iter()
+            .map(move |code| bank_message(&zengin[code.as_str()], include_branches))
+            .map(Ok);
+        Ok(Response::new(Box::pin(tokio_stream::iter(banks))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_stream::StreamExt;
+    use tonic::{codegen::Service, Code};
+
+    #[test]
+    fn test_service() {
+        let service = ZenginGrpc::new(Arc::new(crate::fixture()));
+        futures_executor::block_on(async {
+            let bank = service
+                .lookup_bank(Request::new(proto::LookupBankRequest {
+                    code: "1".to_string(),
+                    include_branches: false,
+                }))
+                .await
+                .unwrap()
+                .into_inner();
+            assert_eq!((bank.code.as_str(), bank.branches.len()), ("0001", 0));
+
+            let status = service
+                .lookup_branch(Request::new(proto::LookupBranchRequest {
+                    bank_code: "0001".to_string(),
+                    code: "999".to_string(),
+                }))
+                .await
+                .unwrap_err();
+            assert_eq!(status.code(), Code::NotFound);
+
+            let found = service
+                .search_banks(Request::new(proto::SearchBanksRequest {
+                    query: "みずほ".to_string(),
+                    limit: 1,
+                }))
+                .await
+                .unwrap()
+                .into_inner();
+            assert_eq!(found.banks.len(), 1);
+
+            let stream = service
+                .stream_all_banks(Request::new(proto::StreamAllBanksRequest {
+                    include_branches: true,
+                }))
+                .await
+                .unwrap()
+                .into_inner();
+            let banks: Vec<_> = stream.collect().await;
+            assert_eq!(banks.len(), service.zengin.all_banks().len());
+            assert!(!banks[0].as_ref().unwrap().branches.is_empty());
+            let codes: Vec<_> = banks
+                .iter()
+                .map(|bank| &bank.as_ref().unwrap().code)
+                .collect();
+            assert!(codes.windows(2).all(|pair| pair[0] < pair[1]));
+
+            let request = tonic::codegen::http::Request::post("/zengin.v1.ZenginService/Unknown")
+                .body(String::new())
+                .unwrap();
+            let response = service.into_server().call(request).await.unwrap();
+            assert_eq!(response.headers()["grpc-status"], "12");
+        });
+    }
+}
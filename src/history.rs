@@ -0,0 +1,217 @@
+//! History of merged and renamed institutions.
+//!
+//! The dataset only lists current institutions, while old records still refer
+//! to banks that merged into others years ago, such as UFJ銀行 (`0008`). This
+//! module embeds a table of such changes, so that an obsolete code can be
+//! resolved to the institution that succeeded it.
+//!
+//! The table covers the mergers of the city banks, such as 富士銀行 (`0006`)
+//! into みずほ銀行 and 大和銀行 (`0011`) into りそな銀行, but not every
+//! regional institution, so a code without changes here may still have been
+//! retired.
+
+use crate::{Bank, BankCode, IntoBankCode, ValidationError, Zengin};
+
+/// The kind of a change of an institution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ChangeKind {
+    /// The institution merged into another one, and its code was retired.
+    Merger,
+    /// The institution was renamed and kept its code.
+    Rename,
+}
+
+/// A change of an institution, such as a merger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Change {
+    /// The code of the institution before the change.
+    pub code: &'static str,
+    /// The name of the institution before the change.
+    pub name: &'static str,
+    /// The code of the institution after the change.
+    pub successor: &'static str,
+    /// The date the change took effect, as `YYYY-MM-DD`.
+    pub date: &'static str,
+    /// The kind of the change.
+    pub kind: ChangeKind,
+}
+
+/// The changes, in date order.
+const CHANGES: &[Change] = &[
+    Change {
+        code: "0012",
+        name: "埼玉",
+        successor: "0010",
+        date: "1991-04-01",
+        kind: ChangeKind::Merger,
+    },
+    Change {
+        code: "0007",
+        name: "東海",
+        successor: "0008",
+        date: "2002-01-15",
+        kind: ChangeKind::Merger,
+    },
+    Change {
+        code: "0008",
+        name: "三和",
+        successor: "0008",
+        date: "2002-01-15",
+        kind: ChangeKind::Rename,
+    },
+    Change {
+        code: "0006",
+        name: "富士",
+        successor: "0001",
+        date: "2002-04-01",
+        kind: ChangeKind::Merger,
+    },
+    Change {
+        code: "0011",
+        name: "大和",
+        successor: "0010",
+        date: "2003-03-03",
+        kind: ChangeKind::Merger,
+    },
+    Change {
+        code: "0008",
+        name: "ＵＦＪ",
+        successor: "0005",
+        date: "2006-01-01",
+        kind: ChangeKind::Merger,
+    },
+    Change {
+        code: "0033",
+        name: "ジャパンネット",
+        successor: "0033",
+        date: "2021-04-05",
+        kind: ChangeKind::Rename,
+    },
+];
+
+/// Returns the changes of the institution with a code, in date order.
+///
+/// # Arguments
+///
+/// * `code` - A string slice that holds the four-digit bank code.
+///
+/// # Examples
+/// ```
+/// use zengin::history::changes_of;
+/// for change in changes_of("0008") {
+///     println!("{} {:?} into {} on {}", change.name, change.kind, change.successor, change.date);
+/// }
+/// ```
+pub fn changes_of(code: &str) -> impl Iterator<Item = &'static Change> + '_ {
+    CHANGES.iter().filter(move |change| change.code == code)
+}
+
+/// Returns the merger that retired a bank code, if any.
+pub fn retirement_of(code: &str) -> Option<&'static Change> {
+    changes_of(code).find(|change| change.kind == ChangeKind::Merger)
+}
+
+/// Returns the date a bank code was retired on and the code of its successor.
+pub(crate) fn retirement(code: BankCode) -> Option<(&'static str, BankCode)> {
+    retirement_of(code.as_str())
+        .and_then(|change| Some((change.date, change.successor.parse().ok()?)))
+}
+
+/// Returns the error for a bank code that is not in the dataset, telling
+/// whether the code was retired.
+pub(crate) fn bank_not_found(code: BankCode) -> ValidationError {
+    match retirement(code) {
+        Some((retired_on, successor)) => ValidationError::BankRetired {
+            bank: code,
+            retired_on,
+            successor,
+        },
+        None => ValidationError::BankNotFound(code),
+    }
+}
+
+impl Zengin {
+    /// Resolves a bank code, current or obsolete, to the current bank.
+    ///
+    /// A code that was retired by a merger is followed to the bank it merged
+    /// into, through any number of later mergers. A current code resolves to
+    /// its own bank.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The bank code to resolve.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// if let Some(bank) = zengin.successor_of("0008") {
+    ///     println!("UFJ is now {} {}", bank.code, bank.name); // 0005 三菱ＵＦＪ
+    /// }
+    /// ```
+    pub fn successor_of<C: IntoBankCode>(&self, code: C) -> Option<&Bank> {
+        let mut code = code.into_bank_code()?.as_str().to_string();
+        // Each merger moves to a later change, so the chain is finite.
+        for _ in 0..=CHANGES.len() {
+            match retirement_of(&code) {
+                Some(change) => code = change.successor.to_string(),
+                None => return self.get_bank(&*code),
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_successor_of() {
+        let zengin = crate::fixture();
+        let code = |code| zengin.successor_of(code).map(|bank| bank.code.as_str());
+        assert_eq!(code("0008"), Some("0005"));
+        assert_eq!(
+            zengin.successor_of(8).map(|bank| bank.code.as_str()),
+            Some("0005")
+        );
+        assert_eq!(code("0006"), Some("0001"));
+        assert_eq!(code("0007"), Some("0005"));
+        assert_eq!(code("0011"), Some("0010"));
+        assert_eq!(code("0012"), Some("0010"));
+        assert_eq!(code("0033"), Some("0033"));
+        assert_eq!(retirement_of("0033"), None);
+        assert_eq!(changes_of("0033").count(), 1);
+        assert_eq!(
+            changes_of("0008")
+                .map(|change| change.name)
+                .collect::<Vec<_>>(),
+            vec!["三和", "ＵＦＪ"]
+        );
+        assert!(CHANGES.windows(2).all(|w| w[0].date <= w[1].date));
+    }
+
+    #[test]
+    fn test_retired_bank() {
+        let zengin = crate::fixture();
+        let e = zengin.validate("0008", "001").unwrap_err();
+        assert_eq!(
+            e,
+            ValidationError::BankRetired {
+                bank: "0008".parse().unwrap(),
+                retired_on: "2006-01-01",
+                successor: "0005".parse().unwrap(),
+            }
+        );
+        assert_eq!(
+            e.to_string(),
+            "bank 0008 was retired on 2006-01-01, consider 0005"
+        );
+        assert_eq!(retirement_of("0999"), None);
+        assert!(matches!(
+            zengin.validate("0999", "001"),
+            Err(ValidationError::BankNotFound(_))
+        ));
+    }
+}
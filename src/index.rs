@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+/// The code of a record in an index. With the `intern` feature, every entry
+/// of a record shares one allocation of its code. The records themselves are
+/// left alone.
+#[cfg(not(feature = "intern"))]
+type Code = String;
+#[cfg(feature = "intern")]
+type Code = std::sync::Arc<str>;
+
+/// A sorted index of the name, kana, hira, and roma fields of a set of
+/// records, used to answer prefix queries without scanning every record.
+///
+/// Keys are stored with ASCII letters lowercased, so that romanized prefixes
+/// match regardless of case.
+#[derive(Debug, Default)]
+pub(crate) struct PrefixIndex {
+    entries: Vec<(String, Code)>,
+}
+
+impl PrefixIndex {
+    /// Builds an index from `(code, keys)` pairs.
+    pub(crate) fn new<'a, I>(records: I) -> PrefixIndex
+    where
+        I: IntoIterator<Item = (&'a str, [&'a str; 4])>,
+    {
+        let mut entries: Vec<_> = records
+            .into_iter()
+            .flat_map(|(code, keys)| {
+                let code = Code::from(code);
+                keys.map(move |key| (key.to_ascii_lowercase(), code.clone()))
+            })
+            .collect();
+        entries.sort_unstable();
+        entries.dedup();
+        PrefixIndex { entries }
+    }
+
+    /// Returns the codes of the records with a key that starts with `prefix`,
+    /// at most `limit` of them.
+    ///
+    /// Codes are ordered by the matching key, so shorter keys come before the
+    /// longer keys they are a prefix of, and each code appears only once.
+    pub(crate) fn search(&self, prefix: &str, limit: usize) -> Vec<&str> {
+        let prefix = prefix.to_ascii_lowercase();
+        let start = self
+            .entries
+            .partition_point(|(key, _)| key.as_str() < prefix.as_str());
+
+        let mut codes: Vec<&str> = Vec::new();
+        for (key, code) in &self.entries[start..] {
+            if codes.len() >= limit || !key.starts_with(&prefix) {
+                break;
+            }
+            if !codes.contains(&&**code) {
+                codes.push(code);
+            }
+        }
+        codes
+    }
+}
+
+/// An index of the character bigrams of the name, kana, hira, and roma
+/// fields of a set of records, used to narrow substring queries down to the
+/// few records that can match without scanning every record.
+///
+/// Keys are stored with ASCII letters lowercased, like [`PrefixIndex`].
+#[derive(Debug, Default)]
+pub(crate) struct NgramIndex {
+    postings: HashMap<[char; 2], Vec<Code>>,
+}
+
+impl NgramIndex {
+    /// Builds an index from `(code, keys)` pairs.
+    pub(crate) fn new<'a, I>(records: I) -> NgramIndex
+    where
+        I: IntoIterator<Item = (&'a str, [&'a str; 4])>,
+    {
+        let mut postings: HashMap<[char; 2], Vec<Code>> = HashMap::new();
+        for (code, keys) in records {
+            let code = Code::from(code);
+            for key in keys {
+                for bigram in bigrams(&key.to_ascii_lowercase()) {
+                    postings.entry(bigram).or_default().push(code.clone());
+                }
+            }
+        }
+        for codes in postings.values_mut() {
+            codes.sort_unstable();
+            codes.dedup();
+        }
+        NgramIndex { postings }
+    }
+
+    /// Returns the codes of the records with keys that contain every bigram
+    /// of `needle`, in code order.
+    ///
+    /// This is a superset of the records with a key containing `needle`
+    /// regardless of ASCII case, so callers still have to check the
+    /// candidates. Returns `None` if `needle` is too short to have a bigram,
+    /// in which case every record is a candidate.
+    pub(crate) fn candidates(&self, needle: &str) -> Option<Vec<&str>> {
+        let mut lists = Vec::new();
+        for bigram in bigrams(&needle.to_ascii_lowercase()) {
+            match self.postings.get(&bigram) {
+                Some(codes) => lists.push(codes),
+                None => return Some(Vec::new()),
+            }
+        }
+        lists.sort_by_key(|codes| codes.len());
+        let (shortest, rest) = lists.split_first()?;
+        Some(
+            shortest
+                .iter()
+                .filter(|code| rest.iter().all(|codes| codes.binary_search(code).is_ok()))
+                .map(|code| &**code)
+                .collect(),
+        )
+    }
+}
+
+fn bigrams(s: &str) -> impl Iterator<Item = [char; 2]> + '_ {
+    s.chars().zip(s.chars().skip(1)).map(|(a, b)| [a, b])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search() {
+        let index = PrefixIndex::new([
+            (
+                "0289",
+                [
+                    "みずほ信託",
+                    "ミズホシンタク",
+                    "みずほしんたく",
+                    "mizuhoshintaku",
+                ],
+            ),
+            ("0001", ["みずほ", "ミズホ", "みずほ", "mizuho"]),
+            ("0005", ["三菱ＵＦＪ", "ミツビシ", "みつびし", "mitsubishi"]),
+        ]);
+        assert_eq!(index.search("みずほ", 10), vec!["0001", "0289"]);
+        assert_eq!(index.search("みずほ", 1), vec!["0001"]);
+        assert_eq!(index.search("Mi", 10), vec!["0005", "0001", "0289"]);
+        assert!(index.search("ゆうちょ", 10).is_empty());
+    }
+
+    #[test]
+    fn test_ngram_candidates() {
+        let index = NgramIndex::new([
+            (
+                "0289",
+                [
+                    "みずほ信託",
+                    "ミズホシンタク",
+                    "みずほしんたく",
+                    "mizuhoshintaku",
+                ],
+            ),
+            ("0001", ["みずほ", "ミズホ", "みずほ", "mizuho"]),
+            ("0005", ["三菱ＵＦＪ", "ミツビシ", "みつびし", "mitsubishi"]),
+        ]);
+        assert_eq!(index.candidates("ずほ"), Some(vec!["0001", "0289"]));
+        assert_eq!(index.candidates("HOSHIN"), Some(vec!["0289"]));
+        assert_eq!(index.candidates("ほず"), Some(vec![]));
+        assert_eq!(index.candidates("み"), None);
+    }
+
+    #[cfg(feature = "intern")]
+    #[test]
+    fn test_shared_codes() {
+        let index = NgramIndex::new([("0001", ["みずほ", "ミズホ", "みずほ", "mizuho"])]);
+        let first = &index.postings[&['み', 'ず']][0];
+        let last = &index.postings[&['h', 'o']][0];
+        assert!(std::sync::Arc::ptr_eq(first, last));
+    }
+}
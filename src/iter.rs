@@ -0,0 +1,119 @@
+use crate::{Bank, Branch, Zengin};
+use std::collections::HashMap;
+
+/// An iterator over `(code, record)` pairs in ascending code order.
+///
+/// This struct is created by [`Zengin::iter`] and [`Bank::iter`]. Unlike
+/// iterating over [`Zengin::all_banks`], the order is the same in every
+/// process.
+#[derive(Debug, Clone)]
+pub struct Iter<'a, T> {
+    inner: std::vec::IntoIter<(&'a str, &'a T)>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn new(records: &'a HashMap<String, T>) -> Self {
+        let mut entries: Vec<_> = records
+            .iter()
+            .map(|(code, record)| (code.as_str(), record))
+            .collect();
+        entries.sort_unstable_by_key(|&(code, _)| code);
+        Iter {
+            inner: entries.into_iter(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (&'a str, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl Zengin {
+    /// Returns an iterator over all banks and their codes, in code order.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// for (code, bank) in zengin.iter() {
+    ///     println!("Bank code: {}, Bank name: {}", code, bank.name);
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, Bank> {
+        Iter::new(&self.banks)
+    }
+}
+
+impl Bank {
+    /// Returns an iterator over all branches of this bank and their codes, in
+    /// code order.
+    ///
+    /// # Examples
+    /// ```
+    /// for (code, branch) in bank.iter() {
+    ///     println!("Branch code: {}, Branch name: {}", code, branch.name);
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, Branch> {
+        Iter::new(&self.branches)
+    }
+}
+
+impl<'a> IntoIterator for &'a Zengin {
+    type Item = (&'a str, &'a Bank);
+    type IntoIter = Iter<'a, Bank>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Bank {
+    type Item = (&'a str, &'a Branch);
+    type IntoIter = Iter<'a, Branch>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture;
+
+    #[test]
+    fn test_iter_in_code_order() {
+        let zengin = fixture();
+        let codes: Vec<_> = (&zengin).into_iter().map(|(code, _)| code).collect();
+        assert_eq!(codes, zengin.bank_codes());
+        assert_eq!(zengin.iter().len(), zengin.all_banks().len());
+        assert_eq!(
+            zengin.iter().next_back().map(|(code, _)| code),
+            Some("9900")
+        );
+
+        let bank = zengin.get_bank("0005").unwrap();
+        let mut codes = vec![];
+        for (code, branch) in bank {
+            assert_eq!(code, branch.code);
+            codes.push(code);
+        }
+        assert_eq!(codes, bank.branch_codes());
+    }
+}
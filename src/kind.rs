@@ -0,0 +1,169 @@
+use crate::{normalize::normalize, Bank, Branch};
+use std::{collections::BTreeMap, fmt};
+
+/// The kind of a branch, as indicated by the suffix of its name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum BranchKind {
+    /// 本店, the head office.
+    HeadOffice,
+    /// 営業部, the main business department, such as `本店営業部` or `東京営業部`.
+    BusinessDepartment,
+    /// 支店, an ordinary branch.
+    Branch,
+    /// 支所, a branch office of a cooperative.
+    Office,
+    /// 出張所, a sub-branch depending on another branch.
+    SubBranch,
+    /// 代理店, an agency.
+    Agency,
+}
+
+impl BranchKind {
+    /// Returns the Japanese name of the kind, such as `出張所`.
+    pub fn name(self) -> &'static str {
+        match self {
+            BranchKind::HeadOffice => "本店",
+            BranchKind::BusinessDepartment => "営業部",
+            BranchKind::Branch => "支店",
+            BranchKind::Office => "支所",
+            BranchKind::SubBranch => "出張所",
+            BranchKind::Agency => "代理店",
+        }
+    }
+}
+
+impl fmt::Display for BranchKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Branch {
+    /// Returns the kind of the branch, parsed from its name.
+    ///
+    /// The dataset leaves out the `支店` suffix of ordinary branches, so the
+    /// 新宿 branch is named just `新宿`. Names are therefore classified by the
+    /// other suffixes, such as `出張所` or `営業部`, and names without one are
+    /// ordinary branches. `本店営業部` is a business department rather than
+    /// the head office.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::BranchKind;
+    /// for branch in bank.all_branches().values() {
+    ///     if branch.kind() == BranchKind::SubBranch {
+    ///         println!("{} is a sub-branch", branch.name);
+    ///     }
+    /// }
+    /// ```
+    pub fn kind(&self) -> BranchKind {
+        const SUFFIXES: [(&str, BranchKind); 6] = [
+            ("営業部", BranchKind::BusinessDepartment),
+            ("出張所", BranchKind::SubBranch),
+            ("代理店", BranchKind::Agency),
+            ("本店", BranchKind::HeadOffice),
+            ("支所", BranchKind::Office),
+            ("支店", BranchKind::Branch),
+        ];
+        SUFFIXES
+            .iter()
+            .find(|(suffix, _)| self.name.ends_with(suffix))
+            .map_or(BranchKind::Branch, |&(_, kind)| kind)
+    }
+}
+
+/// Returns the name of a branch without the suffixes that distinguish
+/// otherwise equal names, such as `支店` or `出張所`, normalized for comparison.
+fn base_name(name: &str) -> String {
+    const SUFFIXES: [&str; 4] = ["支店", "支所", "出張所", "代理店"];
+    let name = normalize(name);
+    SUFFIXES
+        .iter()
+        .find_map(|suffix| name.strip_suffix(suffix))
+        .filter(|base| !base.is_empty())
+        .map_or_else(|| name.clone(), str::to_string)
+}
+
+impl Bank {
+    /// Groups the branches of this bank by their normalized name.
+    ///
+    /// Names are passed through [`normalize`] and stripped of the suffixes
+    /// `支店`, `支所`, `出張所`, and `代理店`, so `新宿` and `新宿出張所` end
+    /// up in the same group. Within a group, branches are in code order.
+    ///
+    /// # Examples
+    /// ```
+    /// for (name, branches) in bank.branches_grouped_by_name() {
+    ///     println!("{}: {} branches", name, branches.len());
+    /// }
+    /// ```
+    pub fn branches_grouped_by_name(&self) -> BTreeMap<String, Vec<&Branch>> {
+        let mut groups: BTreeMap<String, Vec<&Branch>> = BTreeMap::new();
+        for branch in self.branches.values() {
+            groups
+                .entry(base_name(&branch.name))
+                .or_default()
+                .push(branch);
+        }
+        for branches in groups.values_mut() {
+            branches.sort_by(|a, b| a.code.cmp(&b.code));
+        }
+        groups
+    }
+
+    /// Returns the groups of branches of this bank that share a normalized
+    /// name, as grouped by [`Bank::branches_grouped_by_name`].
+    ///
+    /// A name in this map is ambiguous, so a UI should ask the user to confirm
+    /// the branch code when they pick one of these branches by name.
+    ///
+    /// # Examples
+    /// ```
+    /// for (name, branches) in bank.ambiguous_branch_names() {
+    ///     let codes: Vec<_> = branches.iter().map(|branch| &branch.code).collect();
+    ///     println!("{} is ambiguous: {:?}", name, codes);
+    /// }
+    /// ```
+    pub fn ambiguous_branch_names(&self) -> BTreeMap<String, Vec<&Branch>> {
+        let mut groups = self.branches_grouped_by_name();
+        groups.retain(|_, branches| branches.len() > 1);
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Zengin;
+
+    #[test]
+    fn test_branch_kind() {
+        let zengin = crate::fixture();
+        let kind = |bank, branch| zengin.get_branch(bank, branch).unwrap().1.kind();
+        assert_eq!(kind("0001", "001"), BranchKind::BusinessDepartment);
+        assert_eq!(kind("0001", "110"), BranchKind::Branch);
+        assert_eq!(kind("0005", "001"), BranchKind::HeadOffice);
+        assert_eq!(kind("0009", "625"), BranchKind::SubBranch);
+        assert_eq!(BranchKind::SubBranch.to_string(), "出張所");
+    }
+
+    #[test]
+    fn test_ambiguous_branch_names() {
+        let zengin = Zengin::from_combined_json(
+            r#"{"0001":{"code":"0001","name":"みずほ","kana":"ミズホ","hira":"みずほ","roma":"mizuho","branches":{
+                "110":{"code":"110","name":"新宿","kana":"シンジユク","hira":"しんじゆく","roma":"shinjiyuku"},
+                "111":{"code":"111","name":"新宿出張所","kana":"シンジユク","hira":"しんじゆく","roma":"shinjiyuku"},
+                "251":{"code":"251","name":"渋谷","kana":"シブヤ","hira":"しぶや","roma":"shibuya"}
+            }}}"#,
+        )
+        .unwrap();
+        let bank = &zengin["0001"];
+        assert_eq!(bank.branches_grouped_by_name().len(), 2);
+
+        let ambiguous = bank.ambiguous_branch_names();
+        let codes: Vec<_> = ambiguous["新宿"].iter().map(|b| b.code.as_str()).collect();
+        assert_eq!(ambiguous.len(), 1);
+        assert_eq!(codes, vec!["110", "111"]);
+    }
+}
@@ -1,15 +1,109 @@
+pub mod account;
+#[cfg(feature = "calendar")]
+pub mod calendar;
+mod category;
+mod code;
+pub mod collation;
+#[cfg(feature = "sea-orm")]
+pub mod entity;
+mod error;
+pub mod export;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod field;
+pub mod format;
+#[cfg(feature = "tantivy")]
+mod fulltext;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod history;
+mod index;
+mod iter;
+mod kind;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+#[cfg(feature = "uniffi")]
+pub mod mobile;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+pub mod normalize;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "proto")]
+pub mod proto;
+#[cfg(feature = "python")]
+pub mod python;
+mod query;
+#[cfg(feature = "redis")]
+mod redis;
+#[cfg(feature = "romaji")]
+pub mod romaji;
+#[cfg(feature = "diesel")]
+pub mod schema;
+mod search;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+#[cfg(feature = "swift")]
+pub mod swift;
+pub mod yucho;
+
+pub use category::Category;
+pub use code::{BankCode, BranchCode, CodeErrorKind, IntoBankCode, IntoBranchCode, ParseCodeError};
+pub use error::{NotFound, ValidationError, ZenginError};
+#[cfg(feature = "tantivy")]
+pub use fulltext::SearchHit;
+pub use iter::Iter;
+pub use kind::BranchKind;
+pub use query::Query;
+pub use regex;
+pub use search::{Field, Record, SearchOptions, SortOrder};
+
 use include_dir::{include_dir, Dir};
-use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, error::Error};
+use index::{NgramIndex, PrefixIndex};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize, Serializer};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Read,
+    ops::Index,
+    sync::OnceLock,
+};
 
-static DATA_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/source-data/data");
+// The embedded dataset is staged by `build.rs`, which drops the files excluded
+// by the `data-banks-only` and `data-major-banks` features.
+static DATA_DIR: Dir<'_> = include_dir!("$OUT_DIR/data");
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
 
 /// The `Zengin` struct represents a collection of banks and their branches.
 ///
 /// This struct provides methods to load bank and branch data from JSON files,
 /// and to retrieve information about banks and branches.
+///
+/// It serializes as a single map of bank codes to banks, with each bank
+/// carrying its branches in a nested `branches` map.
+#[derive(Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(transparent)]
 pub struct Zengin {
+    #[serde(serialize_with = "serialize_sorted")]
     banks: BankMap,
+    #[serde(skip)]
+    codes_by_name: HashMap<String, String>,
+    #[serde(skip)]
+    prefix_index: OnceLock<PrefixIndex>,
+    #[serde(skip)]
+    ngram_index: OnceLock<NgramIndex>,
+    #[cfg(feature = "tantivy")]
+    #[serde(skip)]
+    fulltext_index: OnceLock<fulltext::FullTextIndex>,
 }
 
 impl Zengin {
@@ -22,21 +116,101 @@ impl Zengin {
     ///
     /// Returns an error if any of the JSON files cannot be read or parsed.
     ///
+    /// # Features
+    ///
+    /// With the `data-major-banks` feature only city banks and net banks
+    /// (codes `0001`-`0099`) are loaded. With the `data-banks-only` feature
+    /// no branch data is embedded, so every bank has an empty branch list.
+    /// With both, only the bank records of those banks are loaded.
+    /// With the `rayon` feature the per-bank branch files are parsed in parallel.
+    ///
     /// # Examples
     /// ```
     /// use zengin::Zengin;
     /// let zengin = Zengin::new().unwrap();
     /// ```
-    pub fn new() -> Result<Zengin, Box<dyn Error>> {
-        let mut banks = load_banks_from_file("banks.json")?;
+    pub fn new() -> Result<Zengin, ZenginError> {
+        let banks = load_embedded_banks()?;
+        check_codes(&banks)?;
+        Ok(Zengin::from_banks(banks))
+    }
+
+    /// Creates a new instance of `Zengin` from a single combined JSON document.
+    ///
+    /// The document maps bank codes to banks, and each bank may carry its
+    /// branches in a nested `branches` map. This is the format produced by
+    /// [`Zengin::to_json`]. Banks without a `branches` key have no branches.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - A string slice that holds the combined JSON document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document cannot be parsed, or a bank or branch
+    /// code in it is malformed.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let json = std::fs::read_to_string("zengin.json").unwrap();
+    /// let zengin = Zengin::from_combined_json(&json).unwrap();
+    /// ```
+    pub fn from_combined_json(json: &str) -> Result<Zengin, ZenginError> {
+        let banks = serde_json::from_str(json).map_err(|e| ZenginError::parse(None, e))?;
+        check_codes(&banks)?;
+        Ok(Zengin::from_banks(banks))
+    }
+
+    /// Creates a new instance of `Zengin` by stream-parsing a combined JSON document.
+    ///
+    /// This accepts the same format as [`Zengin::from_combined_json`], but parses
+    /// the document incrementally from `reader` instead of requiring it to be
+    /// read into memory first. Wrap unbuffered readers such as `File` in a
+    /// `BufReader`.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - A reader that yields the combined JSON document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document cannot be read or parsed, or a bank
+    /// or branch code in it is malformed.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::{fs::File, io::BufReader};
+    /// use zengin::Zengin;
+    /// let file = BufReader::new(File::open("zengin.json").unwrap());
+    /// let zengin = Zengin::from_combined_reader(file).unwrap();
+    /// ```
+    pub fn from_combined_reader<R: Read>(reader: R) -> Result<Zengin, ZenginError> {
+        let banks = serde_json::from_reader(reader).map_err(|e| ZenginError::parse(None, e))?;
+        check_codes(&banks)?;
+        Ok(Zengin::from_banks(banks))
+    }
 
-        for bank in banks.values_mut() {
-            let branches =
-                load_branches_from_file(format!("branches/{}.json", bank.code).as_str())?;
-            bank.branches = branches;
+    fn from_banks(banks: BankMap) -> Zengin {
+        // Index names in code order, so that a name shared by several banks
+        // resolves to the one with the lowest code.
+        let mut codes: Vec<_> = banks.keys().collect();
+        codes.sort_unstable();
+        let mut codes_by_name = HashMap::with_capacity(codes.len());
+        for code in codes {
+            codes_by_name
+                .entry(banks[code].name.clone())
+                .or_insert_with(|| code.clone());
         }
 
-        Ok(Zengin { banks })
+        Zengin {
+            banks,
+            codes_by_name,
+            prefix_index: OnceLock::new(),
+            ngram_index: OnceLock::new(),
+            #[cfg(feature = "tantivy")]
+            fulltext_index: OnceLock::new(),
+        }
     }
 
     /// Retrieves a reference to a bank by its code.
@@ -46,38 +220,439 @@ impl Zengin {
     ///
     /// # Arguments
     ///
-    /// * `code` - A string slice that holds the bank code.
+    /// * `code` - The bank code as a [`BankCode`], a string slice, or an integer.
+    ///   Stripped leading zeros are restored, so `"1"` and `1` find bank `0001`.
+    ///
+    /// Use [`Zengin::try_get_bank`] instead to learn why there is no bank,
+    /// such as that its code was retired by a merger.
     ///
     /// # Examples
     /// ```
-    /// use zengin::Zengin;
+    /// use zengin::{BankCode, Zengin};
     /// let zengin = Zengin::new().unwrap();
     /// if let Some(bank) = zengin.get_bank("0001") {
     ///     println!("Found bank: {}", bank.name);
     /// }
+    ///
+    /// let code: BankCode = "0001".parse().unwrap();
+    /// let bank = zengin.get_bank(code).unwrap();
+    /// ```
+    pub fn get_bank<C: IntoBankCode>(&self, code: C) -> Option<&Bank> {
+        self.banks.get(code.into_bank_code()?.as_str())
+    }
+
+    /// Retrieves a reference to a bank by its code, explaining why there is
+    /// none.
+    ///
+    /// This function works like [`Zengin::get_bank`], but a code that is not
+    /// in the dataset returns [`NotFound::Bank`], which carries the date the
+    /// code was retired on and the code of its successor if a merger recorded
+    /// in the [`history`] module retired it.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The bank code, accepted in the same forms as [`Zengin::get_bank`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the code is malformed or there is no such bank.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{NotFound, Zengin};
+    /// let zengin = Zengin::new().unwrap();
+    /// match zengin.try_get_bank("0008") {
+    ///     Ok(bank) => println!("Found bank: {}", bank.name),
+    ///     Err(e) => println!("{e}"), // bank 0008 was retired on 2006-01-01, consider 0005
+    /// }
+    /// ```
+    pub fn try_get_bank<C: IntoBankCode>(&self, code: C) -> Result<&Bank, NotFound> {
+        let code = code.into_bank_code().ok_or(NotFound::InvalidBankCode)?;
+        self.banks.get(code.as_str()).ok_or_else(|| {
+            let (retired_on, successor) = history::retirement(code).unzip();
+            NotFound::Bank {
+                code,
+                retired_on,
+                successor,
+            }
+        })
+    }
+
+    /// Retrieves a reference to a branch and its bank by their codes.
+    ///
+    /// This function returns `None` if either the bank or the branch does not
+    /// exist, so validating a bank and branch code pair takes a single call.
+    /// Use [`Zengin::try_get_branch`] instead to learn which one is missing.
+    ///
+    /// # Arguments
+    ///
+    /// * `bank_code` - The bank code, accepted in the same forms as [`Zengin::get_bank`].
+    /// * `branch_code` - The branch code, accepted in the same forms as [`Bank::get_branch`].
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// if let Some((bank, branch)) = zengin.get_branch("0001", "001") {
+    ///     println!("Found branch: {} {}", bank.name, branch.name);
+    /// }
+    /// ```
+    pub fn get_branch<B: IntoBankCode, C: IntoBranchCode>(
+        &self,
+        bank_code: B,
+        branch_code: C,
+    ) -> Option<(&Bank, &Branch)> {
+        let bank = self.get_bank(bank_code)?;
+        Some((bank, bank.get_branch(branch_code)?))
+    }
+
+    /// Retrieves a reference to a branch and its bank by their codes,
+    /// explaining why there is none.
+    ///
+    /// This function works like [`Zengin::get_branch`], but reports whether a
+    /// code is malformed, the bank does not exist, as by
+    /// [`Zengin::try_get_bank`], or the bank has no such branch.
+    ///
+    /// # Arguments
+    ///
+    /// * `bank_code` - The bank code, accepted in the same forms as [`Zengin::get_bank`].
+    /// * `branch_code` - The branch code, accepted in the same forms as [`Bank::get_branch`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a code is malformed, or there is no such bank or
+    /// branch.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// match zengin.try_get_branch("0001", "001") {
+    ///     Ok((bank, branch)) => println!("Found branch: {} {}", bank.name, branch.name),
+    ///     Err(e) => println!("{e}"),
+    /// }
+    /// ```
+    pub fn try_get_branch<B: IntoBankCode, C: IntoBranchCode>(
+        &self,
+        bank_code: B,
+        branch_code: C,
+    ) -> Result<(&Bank, &Branch), NotFound> {
+        let bank_code = bank_code
+            .into_bank_code()
+            .ok_or(NotFound::InvalidBankCode)?;
+        let bank = self.try_get_bank(bank_code)?;
+        let branch_code = branch_code
+            .into_branch_code()
+            .ok_or(NotFound::InvalidBranchCode)?;
+        let branch = bank.get_branch(branch_code).ok_or(NotFound::Branch {
+            bank: bank_code,
+            branch: branch_code,
+        })?;
+        Ok((bank, branch))
+    }
+
+    /// Validates a bank and branch code pair, explaining why it is invalid.
+    ///
+    /// Unlike [`Zengin::get_branch`], this function parses the codes strictly,
+    /// so they must be zero-padded, and reports whether a code is malformed,
+    /// the bank does not exist, or the bank has no such branch. A bank code
+    /// that was retired by a merger is reported as
+    /// [`ValidationError::BankRetired`] with the code of its successor.
+    ///
+    /// # Arguments
+    ///
+    /// * `bank_code` - A string slice that holds the four-digit bank code.
+    /// * `branch_code` - A string slice that holds the three-digit branch code.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValidationError`] describing the first problem found.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{ValidationError, Zengin};
+    /// let zengin = Zengin::new().unwrap();
+    /// match zengin.validate("0001", "999") {
+    ///     Ok((bank, branch)) => println!("{} {}", bank.name, branch.name),
+    ///     Err(ValidationError::BranchNotFound { .. }) => println!("unknown branch"),
+    ///     Err(e) => println!("{e}"),
+    /// }
+    /// ```
+    pub fn validate(
+        &self,
+        bank_code: &str,
+        branch_code: &str,
+    ) -> Result<(&Bank, &Branch), ValidationError> {
+        let bank_code: BankCode = bank_code
+            .parse()
+            .map_err(ValidationError::InvalidBankCode)?;
+        let branch_code: BranchCode = branch_code
+            .parse()
+            .map_err(ValidationError::InvalidBranchCode)?;
+        let bank = self
+            .get_bank(bank_code)
+            .ok_or_else(|| history::bank_not_found(bank_code))?;
+        let branch = bank
+            .get_branch(branch_code)
+            .ok_or(ValidationError::BranchNotFound {
+                bank: bank_code,
+                branch: branch_code,
+            })?;
+        Ok((bank, branch))
+    }
+
+    /// Retrieves several banks by their codes at once.
+    ///
+    /// This function returns one entry per input code, in input order, pairing
+    /// each code with its bank or with `None` if there is no such bank, so that
+    /// callers can report exactly which codes failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `codes` - The bank codes, accepted in the same string forms as [`Zengin::get_bank`].
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// for (code, bank) in zengin.get_banks(&["0001", "0005", "9999"]) {
+    ///     match bank {
+    ///         Some(bank) => println!("{}: {}", code, bank.name),
+    ///         None => println!("{}: not found", code),
+    ///     }
+    /// }
+    /// ```
+    pub fn get_banks<'a, C: AsRef<str>>(&self, codes: &'a [C]) -> Vec<(&'a str, Option<&Bank>)> {
+        codes
+            .iter()
+            .map(|code| (code.as_ref(), self.get_bank(code.as_ref())))
+            .collect()
+    }
+
+    /// Retrieves a reference to a bank by its exact name.
+    ///
+    /// This function looks the name up in an index built at load time, so it
+    /// does not scan all banks. The name must match the `name` field exactly,
+    /// which does not include suffixes such as `銀行`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A string slice that holds the bank name.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// if let Some(bank) = zengin.get_bank_by_name("みずほ") {
+    ///     println!("Found bank: {}", bank.code);
+    /// }
+    /// ```
+    pub fn get_bank_by_name(&self, name: &str) -> Option<&Bank> {
+        self.banks.get(self.codes_by_name.get(name)?)
+    }
+
+    /// Finds banks whose name, kana, hiragana, or romanized name starts with a prefix.
+    ///
+    /// This function returns at most `limit` banks, ordered lexicographically
+    /// by the matching name, so a name comes before the longer names it is a
+    /// prefix of, but not before every longer name. Romanized names match
+    /// regardless of case. A sorted index is built on the first call, which
+    /// makes this cheap enough to run on every keystroke.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - A string slice that holds the prefix to complete.
+    /// * `limit` - The maximum number of banks to return.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// for bank in zengin.autocomplete_banks("みず", 10) {
+    ///     println!("Found bank: {}", bank.name);
+    /// }
     /// ```
-    pub fn get_bank(&self, code: &str) -> Option<&Bank> {
-        self.banks.get(code)
+    pub fn autocomplete_banks(&self, prefix: &str, limit: usize) -> Vec<&Bank> {
+        let index = self.prefix_index.get_or_init(|| {
+            PrefixIndex::new(self.banks.values().map(|bank| (bank.code(), bank.names())))
+        });
+        index
+            .search(prefix, limit)
+            .into_iter()
+            .map(|code| &self.banks[code])
+            .collect()
     }
 
-    fn find_banks_by<F>(&self, pattern: &str, key_extractor: F) -> Result<Vec<&Bank>, regex::Error>
+    fn find_banks_by<F>(&self, re: &Regex, key_extractor: F) -> Vec<&Bank>
     where
         F: Fn(&Bank) -> &str,
     {
-        let re = regex::Regex::new(pattern)?;
         let mut matched = vec![];
         for bank in self.banks.values() {
             if re.is_match(key_extractor(bank)) {
                 matched.push(bank);
             }
         }
-        Ok(matched)
+        // HashMap iteration order changes between runs, so sort for stable output.
+        matched.sort_by(|a, b| a.code.cmp(&b.code));
+        matched
     }
 
     /// Finds banks by their name using a regular expression pattern.
     ///
     /// This function takes a regular expression pattern as input and returns a vector
-    /// of references to the banks whose names match the pattern.
+    /// of references to the banks whose names match the pattern, in code order.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - A string slice that holds the regular expression pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regular expression pattern is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let banks = zengin.find_banks_by_name(".*みずほ.*").unwrap();
+    /// for bank in banks {
+    ///     println!("Found bank: {}", bank.name);
+    /// }
+    /// ```
+    pub fn find_banks_by_name(&self, pattern: &str) -> Result<Vec<&Bank>, ZenginError> {
+        let re = Regex::new(pattern)?;
+        Ok(self.find_banks_by(&re, |bank| &bank.name))
+    }
+
+    /// Finds banks by their name using a precompiled regular expression.
+    ///
+    /// This works like [`Zengin::find_banks_by_name`], but takes a compiled
+    /// [`Regex`], so that searching with the same pattern many times does not
+    /// compile it again on every call.
+    ///
+    /// # Arguments
+    ///
+    /// * `re` - The compiled regular expression.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{regex::Regex, Zengin};
+    /// let zengin = Zengin::new().unwrap();
+    /// let re = Regex::new(".*みずほ.*").unwrap();
+    /// for bank in zengin.find_banks_by_name_re(&re) {
+    ///     println!("Found bank: {}", bank.name);
+    /// }
+    /// ```
+    pub fn find_banks_by_name_re(&self, re: &Regex) -> Vec<&Bank> {
+        self.find_banks_by(re, |bank| &bank.name)
+    }
+
+    /// Finds banks by their kana name using a regular expression pattern.
+    ///
+    /// This function takes a regular expression pattern as input and returns a vector
+    /// of references to the banks whose names match the pattern, in code order.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - A string slice that holds the regular expression pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regular expression pattern is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let banks = zengin.find_banks_by_kana(".*ミズホ.*").unwrap();
+    /// for bank in banks {
+    ///     println!("Found bank: {}", bank.name);
+    /// }
+    /// ```
+    pub fn find_banks_by_kana(&self, pattern: &str) -> Result<Vec<&Bank>, ZenginError> {
+        let re = Regex::new(pattern)?;
+        Ok(self.find_banks_by(&re, |bank| &bank.kana))
+    }
+
+    /// Finds banks by their kana name using a precompiled regular expression.
+    ///
+    /// This works like [`Zengin::find_banks_by_kana`], but takes a compiled
+    /// [`Regex`], so that searching with the same pattern many times does not
+    /// compile it again on every call.
+    ///
+    /// # Arguments
+    ///
+    /// * `re` - The compiled regular expression.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{regex::Regex, Zengin};
+    /// let zengin = Zengin::new().unwrap();
+    /// let re = Regex::new(".*ミズホ.*").unwrap();
+    /// for bank in zengin.find_banks_by_kana_re(&re) {
+    ///     println!("Found bank: {}", bank.name);
+    /// }
+    /// ```
+    pub fn find_banks_by_kana_re(&self, re: &Regex) -> Vec<&Bank> {
+        self.find_banks_by(re, |bank| &bank.kana)
+    }
+
+    /// Finds banks by their hiragana name using a regular expression pattern.
+    ///
+    /// This function takes a regular expression pattern as input and returns a vector
+    /// of references to the banks whose names match the pattern, in code order.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - A string slice that holds the regular expression pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regular expression pattern is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let banks = zengin.find_banks_by_hira(".*みずほ.*").unwrap();
+    /// for bank in banks {
+    ///     println!("Found bank: {}", bank.name);
+    /// }
+    /// ```
+    pub fn find_banks_by_hira(&self, pattern: &str) -> Result<Vec<&Bank>, ZenginError> {
+        let re = Regex::new(pattern)?;
+        Ok(self.find_banks_by(&re, |bank| &bank.hira))
+    }
+
+    /// Finds banks by their hiragana name using a precompiled regular expression.
+    ///
+    /// This works like [`Zengin::find_banks_by_hira`], but takes a compiled
+    /// [`Regex`], so that searching with the same pattern many times does not
+    /// compile it again on every call.
+    ///
+    /// # Arguments
+    ///
+    /// * `re` - The compiled regular expression.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{regex::Regex, Zengin};
+    /// let zengin = Zengin::new().unwrap();
+    /// let re = Regex::new(".*みずほ.*").unwrap();
+    /// for bank in zengin.find_banks_by_hira_re(&re) {
+    ///     println!("Found bank: {}", bank.name);
+    /// }
+    /// ```
+    pub fn find_banks_by_hira_re(&self, re: &Regex) -> Vec<&Bank> {
+        self.find_banks_by(re, |bank| &bank.hira)
+    }
+
+    /// Finds banks by their romanized name using a regular expression pattern.
+    ///
+    /// This function takes a regular expression pattern as input and returns a vector
+    /// of references to the banks whose names match the pattern, in code order.
+    /// The pattern is matched case-insensitively, so `Mizuho` finds `mizuho`.
     ///
     /// # Arguments
     ///
@@ -85,25 +660,509 @@ impl Zengin {
     ///
     /// # Errors
     ///
-    /// Returns an error if the regular expression pattern is invalid.
+    /// Returns an error if the regular expression pattern is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let banks = zengin.find_banks_by_roma(".*mizuho.*").unwrap();
+    /// for bank in banks {
+    ///     println!("Found bank: {}", bank.name);
+    /// }
+    /// ```
+    pub fn find_banks_by_roma(&self, pattern: &str) -> Result<Vec<&Bank>, ZenginError> {
+        let re = roma_regex(pattern)?;
+        Ok(self.find_banks_by(&re, |bank| &bank.roma))
+    }
+
+    /// Finds banks by their romanized name using a precompiled regular expression.
+    ///
+    /// This works like [`Zengin::find_banks_by_roma`], but takes a compiled
+    /// [`Regex`], so that searching with the same pattern many times does not
+    /// compile it again on every call.
+    ///
+    /// The regular expression is used as compiled, so unlike
+    /// [`Zengin::find_banks_by_roma`] it is case-sensitive unless it was
+    /// built with the `(?i)` flag or [`RegexBuilder::case_insensitive`].
+    ///
+    /// # Arguments
+    ///
+    /// * `re` - The compiled regular expression.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{regex::Regex, Zengin};
+    /// let zengin = Zengin::new().unwrap();
+    /// let re = Regex::new("(?i)mizuho").unwrap();
+    /// for bank in zengin.find_banks_by_roma_re(&re) {
+    ///     println!("Found bank: {}", bank.name);
+    /// }
+    /// ```
+    pub fn find_banks_by_roma_re(&self, re: &Regex) -> Vec<&Bank> {
+        self.find_banks_by(re, |bank| &bank.roma)
+    }
+
+    fn find_banks_by_iter<F>(&self, re: Regex, key_extractor: F) -> impl Iterator<Item = &Bank>
+    where
+        F: Fn(&Bank) -> &str,
+    {
+        self.banks
+            .values()
+            .filter(move |bank| re.is_match(key_extractor(bank)))
+    }
+
+    /// Lazily finds banks by their name using a regular expression pattern.
+    ///
+    /// This works like [`Zengin::find_banks_by_name`], but returns an iterator
+    /// that tests the banks as it is advanced, so taking the first few matches
+    /// does not scan every bank. Because nothing is collected, the banks are
+    /// yielded in no particular order.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - A string slice that holds the regular expression pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regular expression pattern is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let first: Vec<_> = zengin.find_banks_by_name_iter("みずほ").unwrap().take(3).collect();
+    /// ```
+    pub fn find_banks_by_name_iter(
+        &self,
+        pattern: &str,
+    ) -> Result<impl Iterator<Item = &Bank>, ZenginError> {
+        Ok(self.find_banks_by_iter(Regex::new(pattern)?, |bank| &bank.name))
+    }
+
+    /// Lazily finds banks by their kana name using a regular expression pattern.
+    ///
+    /// This works like [`Zengin::find_banks_by_kana`], but returns an iterator
+    /// that tests the banks as it is advanced, so taking the first few matches
+    /// does not scan every bank. Because nothing is collected, the banks are
+    /// yielded in no particular order.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - A string slice that holds the regular expression pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regular expression pattern is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let first: Vec<_> = zengin.find_banks_by_kana_iter("ミズホ").unwrap().take(3).collect();
+    /// ```
+    pub fn find_banks_by_kana_iter(
+        &self,
+        pattern: &str,
+    ) -> Result<impl Iterator<Item = &Bank>, ZenginError> {
+        Ok(self.find_banks_by_iter(Regex::new(pattern)?, |bank| &bank.kana))
+    }
+
+    /// Lazily finds banks by their hiragana name using a regular expression pattern.
+    ///
+    /// This works like [`Zengin::find_banks_by_hira`], but returns an iterator
+    /// that tests the banks as it is advanced, so taking the first few matches
+    /// does not scan every bank. Because nothing is collected, the banks are
+    /// yielded in no particular order.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - A string slice that holds the regular expression pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regular expression pattern is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let first: Vec<_> = zengin.find_banks_by_hira_iter("みずほ").unwrap().take(3).collect();
+    /// ```
+    pub fn find_banks_by_hira_iter(
+        &self,
+        pattern: &str,
+    ) -> Result<impl Iterator<Item = &Bank>, ZenginError> {
+        Ok(self.find_banks_by_iter(Regex::new(pattern)?, |bank| &bank.hira))
+    }
+
+    /// Lazily finds banks by their romanized name using a regular expression pattern.
+    ///
+    /// This works like [`Zengin::find_banks_by_roma`], but returns an iterator
+    /// that tests the banks as it is advanced, so taking the first few matches
+    /// does not scan every bank. Because nothing is collected, the banks are
+    /// yielded in no particular order.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - A string slice that holds the regular expression pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regular expression pattern is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let first: Vec<_> = zengin.find_banks_by_roma_iter("mizuho").unwrap().take(3).collect();
+    /// ```
+    pub fn find_banks_by_roma_iter(
+        &self,
+        pattern: &str,
+    ) -> Result<impl Iterator<Item = &Bank>, ZenginError> {
+        Ok(self.find_banks_by_iter(roma_regex(pattern)?, |bank| &bank.roma))
+    }
+
+    /// Finds branches of every bank by their name using a regular expression pattern.
+    ///
+    /// This function searches the branches of all banks, which helps when only
+    /// the branch name is known, and returns each matching branch together
+    /// with its bank. The pairs are ordered by bank code, then branch code.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - A string slice that holds the regular expression pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regular expression pattern is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// for (bank, branch) in zengin.find_branches_by_name("^新宿").unwrap() {
+    ///     println!("Found branch: {} {}", bank.name, branch.name);
+    /// }
+    /// ```
+    pub fn find_branches_by_name(
+        &self,
+        pattern: &str,
+    ) -> Result<Vec<(&Bank, &Branch)>, ZenginError> {
+        let re = Regex::new(pattern)?;
+        let mut matched: Vec<_> = self
+            .banks
+            .values()
+            .flat_map(|bank| {
+                bank.find_branches_by(&re, |branch| &branch.name)
+                    .into_iter()
+                    .map(move |branch| (bank, branch))
+            })
+            .collect();
+        matched.sort_by(|(a, x), (b, y)| (&a.code, &x.code).cmp(&(&b.code, &y.code)));
+        Ok(matched)
+    }
+
+    /// Retrieves a reference to all banks.
+    ///
+    /// This function returns a reference to the internal `HashMap` containing all banks.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let banks = zengin.all_banks();
+    /// for (code, bank) in banks {
+    ///     println!("Bank code: {}, Bank name: {}", code, bank.name);
+    /// }
+    /// ```
+    pub fn all_banks(&self) -> &BankMap {
+        &self.banks
+    }
+
+    /// Retrieves the codes of all banks in ascending order.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// for code in zengin.bank_codes() {
+    ///     println!("Bank code: {}", code);
+    /// }
+    /// ```
+    pub fn bank_codes(&self) -> Vec<&str> {
+        sorted_codes(&self.banks)
+    }
+
+    /// Serializes the entire dataset into a single JSON document.
+    ///
+    /// The document maps bank codes to banks, and each bank holds its branches
+    /// in a nested `branches` map. Keys are emitted in code order so that the
+    /// output is stable across runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let json = zengin.to_json().unwrap();
+    /// std::fs::write("zengin.json", json).unwrap();
+    /// ```
+    pub fn to_json(&self) -> Result<String, ZenginError> {
+        serde_json::to_string(self).map_err(ZenginError::Encode)
+    }
+}
+
+type BranchMap = HashMap<String, Branch>;
+type BankMap = HashMap<String, Bank>;
+
+/// Checks that every bank and branch code of a dataset has the form of a
+/// [`BankCode`] or [`BranchCode`].
+fn check_codes(banks: &BankMap) -> Result<(), ZenginError> {
+    let invalid = |code: &str, expected| ZenginError::InvalidCode {
+        code: code.to_string(),
+        expected,
+    };
+    for (key, bank) in banks {
+        for code in [key, &bank.code] {
+            code.parse::<BankCode>()
+                .map_err(|e| invalid(code, e.expected_len()))?;
+        }
+        for (key, branch) in &bank.branches {
+            for code in [key, &branch.code] {
+                code.parse::<BranchCode>()
+                    .map_err(|e| invalid(code, e.expected_len()))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The `Bank` struct represents a bank with its associated branches.
+///
+/// This struct contains information about the bank, including its code, name,
+/// kana, hiragana, and romanized name. It also holds a collection of branches
+/// associated with the bank.
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Bank {
+    pub code: String,
+    pub name: String,
+    pub kana: String,
+    pub hira: String,
+    pub roma: String,
+
+    #[serde(default, serialize_with = "serialize_sorted")]
+    branches: BranchMap,
+
+    #[serde(skip)]
+    prefix_index: OnceLock<PrefixIndex>,
+    #[serde(skip)]
+    ngram_index: OnceLock<NgramIndex>,
+}
+
+impl Bank {
+    /// Retrieves a reference to a branch by its code.
+    ///
+    /// This function takes a branch code as input and returns an `Option` containing
+    /// a reference to the corresponding `Branch` if it exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The branch code as a [`BranchCode`], a string slice, or an integer.
+    ///   Stripped leading zeros are restored, so `"1"` and `1` find branch `001`.
+    ///
+    /// # Examples
+    /// ```
+    /// if let Some(branch) = bank.get_branch("001") {
+    ///     println!("Found branch: {}", branch.name);
+    /// }
+    /// ```
+    pub fn get_branch<C: IntoBranchCode>(&self, code: C) -> Option<&Branch> {
+        self.branches.get(code.into_branch_code()?.as_str())
+    }
+
+    /// Retrieves several branches of this bank by their codes at once.
+    ///
+    /// This works like [`Zengin::get_banks`], returning one entry per input
+    /// code, in input order.
+    ///
+    /// # Arguments
+    ///
+    /// * `codes` - The branch codes, accepted in the same string forms as [`Bank::get_branch`].
+    ///
+    /// # Examples
+    /// ```
+    /// let missing: Vec<_> = bank
+    ///     .get_branches(&["001", "999"])
+    ///     .into_iter()
+    ///     .filter(|(_, branch)| branch.is_none())
+    ///     .map(|(code, _)| code)
+    ///     .collect();
+    /// ```
+    pub fn get_branches<'a, C: AsRef<str>>(
+        &self,
+        codes: &'a [C],
+    ) -> Vec<(&'a str, Option<&Branch>)> {
+        codes
+            .iter()
+            .map(|code| (code.as_ref(), self.get_branch(code.as_ref())))
+            .collect()
+    }
+
+    /// Finds branches whose name, kana, hiragana, or romanized name starts with a prefix.
+    ///
+    /// This function returns at most `limit` branches, ordered lexicographically
+    /// by the matching name, so a name comes before the longer names it is a
+    /// prefix of, but not before every longer name. Romanized names match
+    /// regardless of case. A sorted index is built on the first call, which
+    /// makes this cheap enough to run on every keystroke.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - A string slice that holds the prefix to complete.
+    /// * `limit` - The maximum number of branches to return.
+    ///
+    /// # Examples
+    /// ```
+    /// for branch in bank.autocomplete_branches("しん", 10) {
+    ///     println!("Found branch: {}", branch.name);
+    /// }
+    /// ```
+    pub fn autocomplete_branches(&self, prefix: &str, limit: usize) -> Vec<&Branch> {
+        let index = self.prefix_index.get_or_init(|| {
+            PrefixIndex::new(
+                self.branches
+                    .values()
+                    .map(|branch| (branch.code(), branch.names())),
+            )
+        });
+        index
+            .search(prefix, limit)
+            .into_iter()
+            .map(|code| &self.branches[code])
+            .collect()
+    }
+
+    fn find_branches_by<F>(&self, re: &Regex, key_extractor: F) -> Vec<&Branch>
+    where
+        F: Fn(&Branch) -> &str,
+    {
+        let mut matched = vec![];
+        for branch in self.branches.values() {
+            if re.is_match(key_extractor(branch)) {
+                matched.push(branch);
+            }
+        }
+        // HashMap iteration order changes between runs, so sort for stable output.
+        matched.sort_by(|a, b| a.code.cmp(&b.code));
+        matched
+    }
+
+    /// Finds branches by their name using a regular expression pattern.
+    ///
+    /// This function takes a regular expression pattern as input and returns a vector
+    /// of references to the branches whose names match the pattern, in code order.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - A string slice that holds the regular expression pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regular expression pattern is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// let branches = bank.find_branches_by_name(".*東京.*").unwrap();
+    /// for branch in branches {
+    ///     println!("Found branch: {}", branch.name);
+    /// }
+    /// ```
+    pub fn find_branches_by_name(&self, pattern: &str) -> Result<Vec<&Branch>, ZenginError> {
+        let re = Regex::new(pattern)?;
+        Ok(self.find_branches_by(&re, |branch| &branch.name))
+    }
+
+    /// Finds branches by their name using a precompiled regular expression.
+    ///
+    /// This works like [`Bank::find_branches_by_name`], but takes a compiled
+    /// [`Regex`], so that searching with the same pattern many times does not
+    /// compile it again on every call.
+    ///
+    /// # Arguments
+    ///
+    /// * `re` - The compiled regular expression.
+    ///
+    /// # Examples
+    /// ```
+    /// let re = zengin::regex::Regex::new(".*東京.*").unwrap();
+    /// for bank in zengin.all_banks().values() {
+    ///     for branch in bank.find_branches_by_name_re(&re) {
+    ///         println!("Found branch: {} {}", bank.name, branch.name);
+    ///     }
+    /// }
+    /// ```
+    pub fn find_branches_by_name_re(&self, re: &Regex) -> Vec<&Branch> {
+        self.find_branches_by(re, |branch| &branch.name)
+    }
+
+    /// Finds branches by their hiragana name using a regular expression pattern.
+    ///
+    /// This function takes a regular expression pattern as input and returns a vector
+    /// of references to the branches whose names match the pattern, in code order.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - A string slice that holds the regular expression pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regular expression pattern is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// let branches = bank.find_branches_by_hira(".*とうきよう.*").unwrap();
+    /// for branch in branches {
+    ///    println!("Found branch: {}", branch.name);
+    /// }
+    /// ```
+    pub fn find_branches_by_hira(&self, pattern: &str) -> Result<Vec<&Branch>, ZenginError> {
+        let re = Regex::new(pattern)?;
+        Ok(self.find_branches_by(&re, |branch| &branch.hira))
+    }
+
+    /// Finds branches by their hiragana name using a precompiled regular expression.
+    ///
+    /// This works like [`Bank::find_branches_by_hira`], but takes a compiled
+    /// [`Regex`], so that searching with the same pattern many times does not
+    /// compile it again on every call.
+    ///
+    /// # Arguments
+    ///
+    /// * `re` - The compiled regular expression.
     ///
     /// # Examples
     /// ```
-    /// use zengin::Zengin;
-    /// let zengin = Zengin::new().unwrap();
-    /// let banks = zengin.find_banks_by_name(".*みずほ.*").unwrap();
-    /// for bank in banks {
-    ///     println!("Found bank: {}", bank.name);
+    /// let re = zengin::regex::Regex::new(".*とうきよう.*").unwrap();
+    /// for bank in zengin.all_banks().values() {
+    ///     for branch in bank.find_branches_by_hira_re(&re) {
+    ///         println!("Found branch: {} {}", bank.name, branch.name);
+    ///     }
     /// }
     /// ```
-    pub fn find_banks_by_name(&self, pattern: &str) -> Result<Vec<&Bank>, regex::Error> {
-        self.find_banks_by(pattern, |bank| &bank.name)
+    pub fn find_branches_by_hira_re(&self, re: &Regex) -> Vec<&Branch> {
+        self.find_branches_by(re, |branch| &branch.hira)
     }
 
-    /// Finds banks by their kana name using a regular expression pattern.
+    /// Finds branches by their kana name using a regular expression pattern.
     ///
     /// This function takes a regular expression pattern as input and returns a vector
-    /// of references to the banks whose names match the pattern.
+    /// of references to the branches whose names match the pattern, in code order.
     ///
     /// # Arguments
     ///
@@ -115,47 +1174,44 @@ impl Zengin {
     ///
     /// # Examples
     /// ```
-    /// use zengin::Zengin;
-    /// let zengin = Zengin::new().unwrap();
-    /// let banks = zengin.find_banks_by_kana(".*ミズホ.*").unwrap();
-    /// for bank in banks {
-    ///     println!("Found bank: {}", bank.name);
+    /// let branches = bank.find_branches_by_kana(".*トウキヨウ.*").unwrap();
+    /// for branch in branches {
+    ///    println!("Found branch: {}", branch.name);
     /// }
     /// ```
-    pub fn find_banks_by_kana(&self, pattern: &str) -> Result<Vec<&Bank>, regex::Error> {
-        self.find_banks_by(pattern, |bank| &bank.kana)
+    pub fn find_branches_by_kana(&self, pattern: &str) -> Result<Vec<&Branch>, ZenginError> {
+        let re = Regex::new(pattern)?;
+        Ok(self.find_branches_by(&re, |branch| &branch.kana))
     }
 
-    /// Finds banks by their hiragana name using a regular expression pattern.
+    /// Finds branches by their kana name using a precompiled regular expression.
     ///
-    /// This function takes a regular expression pattern as input and returns a vector
-    /// of references to the banks whose names match the pattern.
+    /// This works like [`Bank::find_branches_by_kana`], but takes a compiled
+    /// [`Regex`], so that searching with the same pattern many times does not
+    /// compile it again on every call.
     ///
     /// # Arguments
     ///
-    /// * `pattern` - A string slice that holds the regular expression pattern.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the regular expression pattern is invalid.
+    /// * `re` - The compiled regular expression.
     ///
     /// # Examples
     /// ```
-    /// use zengin::Zengin;
-    /// let zengin = Zengin::new().unwrap();
-    /// let banks = zengin.find_banks_by_hira(".*みずほ.*").unwrap();
-    /// for bank in banks {
-    ///     println!("Found bank: {}", bank.name);
+    /// let re = zengin::regex::Regex::new(".*トウキヨウ.*").unwrap();
+    /// for bank in zengin.all_banks().values() {
+    ///     for branch in bank.find_branches_by_kana_re(&re) {
+    ///         println!("Found branch: {} {}", bank.name, branch.name);
+    ///     }
     /// }
     /// ```
-    pub fn find_banks_by_hira(&self, pattern: &str) -> Result<Vec<&Bank>, regex::Error> {
-        self.find_banks_by(pattern, |bank| &bank.hira)
+    pub fn find_branches_by_kana_re(&self, re: &Regex) -> Vec<&Branch> {
+        self.find_branches_by(re, |branch| &branch.kana)
     }
 
-    /// Finds banks by their romanized name using a regular expression pattern.
+    /// Finds branches by their romanized name using a regular expression pattern.
     ///
     /// This function takes a regular expression pattern as input and returns a vector
-    /// of references to the banks whose names match the pattern.
+    /// of references to the branches whose names match the pattern, in code order.
+    /// The pattern is matched case-insensitively, so `Toukiyou` finds `toukiyou`.
     ///
     /// # Arguments
     ///
@@ -167,97 +1223,58 @@ impl Zengin {
     ///
     /// # Examples
     /// ```
-    /// use zengin::Zengin;
-    /// let zengin = Zengin::new().unwrap();
-    /// let banks = zengin.find_banks_by_roma(".*mizuho.*").unwrap();
-    /// for bank in banks {
-    ///     println!("Found bank: {}", bank.name);
+    /// let branches = bank.find_branches_by_roma(".*toukiyou.*").unwrap();
+    /// for branch in branches {
+    ///    println!("Found branch: {}", branch.name);
     /// }
     /// ```
-    pub fn find_banks_by_roma(&self, pattern: &str) -> Result<Vec<&Bank>, regex::Error> {
-        self.find_banks_by(pattern, |bank| &bank.roma)
+    pub fn find_branches_by_roma(&self, pattern: &str) -> Result<Vec<&Branch>, ZenginError> {
+        let re = roma_regex(pattern)?;
+        Ok(self.find_branches_by(&re, |branch| &branch.roma))
     }
 
-    /// Retrieves a reference to all banks.
+    /// Finds branches by their romanized name using a precompiled regular expression.
     ///
-    /// This function returns a reference to the internal `HashMap` containing all banks.
-    ///
-    /// # Examples
-    /// ```
-    /// use zengin::Zengin;
-    /// let zengin = Zengin::new().unwrap();
-    /// let banks = zengin.all_banks();
-    /// for (code, bank) in banks {
-    ///     println!("Bank code: {}, Bank name: {}", code, bank.name);
-    /// }
-    /// ```
-    pub fn all_banks(&self) -> &BankMap {
-        &self.banks
-    }
-}
-
-type BranchMap = HashMap<String, Branch>;
-type BankMap = HashMap<String, Bank>;
-
-/// The `Bank` struct represents a bank with its associated branches.
-///
-/// This struct contains information about the bank, including its code, name,
-/// kana, hiragana, and romanized name. It also holds a collection of branches
-/// associated with the bank.
-#[derive(Serialize, Deserialize, Debug)]
-pub struct Bank {
-    pub code: String,
-    pub name: String,
-    pub kana: String,
-    pub hira: String,
-    pub roma: String,
-
-    #[serde(skip_deserializing)]
-    branches: BranchMap,
-}
-
-impl Bank {
-    /// Retrieves a reference to a branch by its code.
+    /// This works like [`Bank::find_branches_by_roma`], but takes a compiled
+    /// [`Regex`], so that searching with the same pattern many times does not
+    /// compile it again on every call.
     ///
-    /// This function takes a branch code as input and returns an `Option` containing
-    /// a reference to the corresponding `Branch` if it exists.
+    /// The regular expression is used as compiled, so unlike
+    /// [`Bank::find_branches_by_roma`] it is case-sensitive unless it was
+    /// built with the `(?i)` flag or [`RegexBuilder::case_insensitive`].
     ///
     /// # Arguments
     ///
-    /// * `code` - A string slice that holds the branch code.
+    /// * `re` - The compiled regular expression.
     ///
     /// # Examples
     /// ```
-    /// if let Some(branch) = bank.get_branch("001") {
-    ///     println!("Found branch: {}", branch.name);
+    /// let re = zengin::regex::Regex::new("(?i)toukiyou").unwrap();
+    /// for bank in zengin.all_banks().values() {
+    ///     for branch in bank.find_branches_by_roma_re(&re) {
+    ///         println!("Found branch: {} {}", bank.name, branch.name);
+    ///     }
     /// }
     /// ```
-    pub fn get_branch(&self, code: &str) -> Option<&Branch> {
-        self.branches.get(code)
+    pub fn find_branches_by_roma_re(&self, re: &Regex) -> Vec<&Branch> {
+        self.find_branches_by(re, |branch| &branch.roma)
     }
 
-    fn find_branches_by<F>(
-        &self,
-        pattern: &str,
-        key_extractor: F,
-    ) -> Result<Vec<&Branch>, regex::Error>
+    fn find_branches_by_iter<F>(&self, re: Regex, key_extractor: F) -> impl Iterator<Item = &Branch>
     where
         F: Fn(&Branch) -> &str,
     {
-        let re = regex::Regex::new(pattern)?;
-        let mut matched = vec![];
-        for branch in self.branches.values() {
-            if re.is_match(key_extractor(branch)) {
-                matched.push(branch);
-            }
-        }
-        Ok(matched)
+        self.branches
+            .values()
+            .filter(move |branch| re.is_match(key_extractor(branch)))
     }
 
-    /// Finds branches by their name using a regular expression pattern.
+    /// Lazily finds branches by their name using a regular expression pattern.
     ///
-    /// This function takes a regular expression pattern as input and returns a vector
-    /// of references to the branches whose names match the pattern.
+    /// This works like [`Bank::find_branches_by_name`], but returns an iterator
+    /// that tests the branches as it is advanced, so taking the first few matches
+    /// does not scan every branch. Because nothing is collected, the branches are
+    /// yielded in no particular order.
     ///
     /// # Arguments
     ///
@@ -269,19 +1286,21 @@ impl Bank {
     ///
     /// # Examples
     /// ```
-    /// let branches = bank.find_branches_by_name(".*東京.*").unwrap();
-    /// for branch in branches {
-    ///     println!("Found branch: {}", branch.name);
-    /// }
+    /// let first: Vec<_> = bank.find_branches_by_name_iter("新宿").unwrap().take(3).collect();
     /// ```
-    pub fn find_branches_by_name(&self, pattern: &str) -> Result<Vec<&Branch>, regex::Error> {
-        self.find_branches_by(pattern, |branch| &branch.name)
+    pub fn find_branches_by_name_iter(
+        &self,
+        pattern: &str,
+    ) -> Result<impl Iterator<Item = &Branch>, ZenginError> {
+        Ok(self.find_branches_by_iter(Regex::new(pattern)?, |branch| &branch.name))
     }
 
-    /// Finds branches by their hiragana name using a regular expression pattern.
+    /// Lazily finds branches by their kana name using a regular expression pattern.
     ///
-    /// This function takes a regular expression pattern as input and returns a vector
-    /// of references to the branches whose names match the pattern.
+    /// This works like [`Bank::find_branches_by_kana`], but returns an iterator
+    /// that tests the branches as it is advanced, so taking the first few matches
+    /// does not scan every branch. Because nothing is collected, the branches are
+    /// yielded in no particular order.
     ///
     /// # Arguments
     ///
@@ -293,19 +1312,21 @@ impl Bank {
     ///
     /// # Examples
     /// ```
-    /// let branches = bank.find_branches_by_hira(".*とうきよう.*").unwrap();
-    /// for branch in branches {
-    ///    println!("Found branch: {}", branch.name);
-    /// }
+    /// let first: Vec<_> = bank.find_branches_by_kana_iter("シンジユク").unwrap().take(3).collect();
     /// ```
-    pub fn find_branches_by_hira(&self, pattern: &str) -> Result<Vec<&Branch>, regex::Error> {
-        self.find_branches_by(pattern, |branch| &branch.hira)
+    pub fn find_branches_by_kana_iter(
+        &self,
+        pattern: &str,
+    ) -> Result<impl Iterator<Item = &Branch>, ZenginError> {
+        Ok(self.find_branches_by_iter(Regex::new(pattern)?, |branch| &branch.kana))
     }
 
-    /// Finds branches by their kana name using a regular expression pattern.
+    /// Lazily finds branches by their hiragana name using a regular expression pattern.
     ///
-    /// This function takes a regular expression pattern as input and returns a vector
-    /// of references to the branches whose names match the pattern.
+    /// This works like [`Bank::find_branches_by_hira`], but returns an iterator
+    /// that tests the branches as it is advanced, so taking the first few matches
+    /// does not scan every branch. Because nothing is collected, the branches are
+    /// yielded in no particular order.
     ///
     /// # Arguments
     ///
@@ -317,19 +1338,21 @@ impl Bank {
     ///
     /// # Examples
     /// ```
-    /// let branches = bank.find_branches_by_kana(".*トウキヨウ.*").unwrap();
-    /// for branch in branches {
-    ///    println!("Found branch: {}", branch.name);
-    /// }
+    /// let first: Vec<_> = bank.find_branches_by_hira_iter("しんじゆく").unwrap().take(3).collect();
     /// ```
-    pub fn find_branches_by_kana(&self, pattern: &str) -> Result<Vec<&Branch>, regex::Error> {
-        self.find_branches_by(pattern, |branch| &branch.kana)
+    pub fn find_branches_by_hira_iter(
+        &self,
+        pattern: &str,
+    ) -> Result<impl Iterator<Item = &Branch>, ZenginError> {
+        Ok(self.find_branches_by_iter(Regex::new(pattern)?, |branch| &branch.hira))
     }
 
-    /// Finds branches by their romanized name using a regular expression pattern.
+    /// Lazily finds branches by their romanized name using a regular expression pattern.
     ///
-    /// This function takes a regular expression pattern as input and returns a vector
-    /// of references to the branches whose names match the pattern.
+    /// This works like [`Bank::find_branches_by_roma`], but returns an iterator
+    /// that tests the branches as it is advanced, so taking the first few matches
+    /// does not scan every branch. Because nothing is collected, the branches are
+    /// yielded in no particular order.
     ///
     /// # Arguments
     ///
@@ -341,13 +1364,13 @@ impl Bank {
     ///
     /// # Examples
     /// ```
-    /// let branches = bank.find_branches_by_roma(".*toukiyou.*").unwrap();
-    /// for branch in branches {
-    ///    println!("Found branch: {}", branch.name);
-    /// }
+    /// let first: Vec<_> = bank.find_branches_by_roma_iter("shinjiyuku").unwrap().take(3).collect();
     /// ```
-    pub fn find_branches_by_roma(&self, pattern: &str) -> Result<Vec<&Branch>, regex::Error> {
-        self.find_branches_by(pattern, |branch| &branch.roma)
+    pub fn find_branches_by_roma_iter(
+        &self,
+        pattern: &str,
+    ) -> Result<impl Iterator<Item = &Branch>, ZenginError> {
+        Ok(self.find_branches_by_iter(roma_regex(pattern)?, |branch| &branch.roma))
     }
 
     /// Retrieves a reference to all branches.
@@ -364,6 +1387,18 @@ impl Bank {
     pub fn all_branches(&self) -> &BranchMap {
         &self.branches
     }
+
+    /// Retrieves the codes of all branches of this bank in ascending order.
+    ///
+    /// # Examples
+    /// ```
+    /// for code in bank.branch_codes() {
+    ///     println!("Branch code: {}", code);
+    /// }
+    /// ```
+    pub fn branch_codes(&self) -> Vec<&str> {
+        sorted_codes(&self.branches)
+    }
 }
 
 /// The `Branch` struct represents a branch of a bank.
@@ -371,6 +1406,7 @@ impl Bank {
 /// This struct contains information about the branch, including its code, name,
 /// kana, hiragana, and romanized name.
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Branch {
     pub code: String,
     pub name: String,
@@ -379,31 +1415,131 @@ pub struct Branch {
     pub roma: String,
 }
 
-fn parse_banks(json_data: &str) -> std::result::Result<BankMap, Box<dyn Error>> {
-    let bank_map = serde_json::from_str(json_data)?;
-    Ok(bank_map)
+fn serialize_sorted<S, V>(map: &HashMap<String, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    V: Serialize,
+{
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
+/// Romanized names are matched case-insensitively, since the dataset stores
+/// them in lowercase while users commonly type capitalized names.
+fn roma_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern).case_insensitive(true).build()
+}
+
+/// Looks up a bank by its code, like [`Zengin::get_bank`].
+///
+/// # Panics
+///
+/// Panics if there is no bank with the code.
+///
+/// # Examples
+/// ```
+/// use zengin::Zengin;
+/// let zengin = Zengin::new().unwrap();
+/// assert_eq!(zengin["0001"]["001"].name, "東京営業部");
+/// ```
+impl Index<&str> for Zengin {
+    type Output = Bank;
+
+    fn index(&self, code: &str) -> &Bank {
+        self.get_bank(code)
+            .unwrap_or_else(|| panic!("no bank with code {code:?}"))
+    }
+}
+
+/// Looks up a branch of the bank by its code, like [`Bank::get_branch`].
+///
+/// # Panics
+///
+/// Panics if the bank has no branch with the code.
+impl Index<&str> for Bank {
+    type Output = Branch;
+
+    fn index(&self, code: &str) -> &Branch {
+        self.get_branch(code)
+            .unwrap_or_else(|| panic!("bank {} has no branch with code {code:?}", self.code))
+    }
+}
+
+fn sorted_codes<T>(records: &HashMap<String, T>) -> Vec<&str> {
+    let mut codes: Vec<_> = records.keys().map(String::as_str).collect();
+    codes.sort_unstable();
+    codes
+}
+
+/// City banks and net banks share the `00xx` block of bank codes.
+fn is_major_bank_code(code: &str) -> bool {
+    code.starts_with("00")
 }
 
-fn parse_branches(json_data: &str) -> std::result::Result<BranchMap, Box<dyn Error>> {
-    let branch_map = serde_json::from_str(json_data)?;
-    Ok(branch_map)
+// The parsers deserialize straight from the embedded bytes so that no
+// intermediate `String` copy of a data file is ever held in memory.
+fn parse_banks(json_data: &[u8]) -> Result<BankMap, serde_json::Error> {
+    serde_json::from_slice(json_data)
 }
 
-fn load_banks_from_file(file_path: &str) -> std::result::Result<BankMap, Box<dyn Error>> {
+fn parse_branches(json_data: &[u8]) -> Result<BranchMap, serde_json::Error> {
+    serde_json::from_slice(json_data)
+}
+
+fn load_banks_from_file(file_path: &str) -> Result<BankMap, ZenginError> {
     let json_data = read_data_file(file_path)?;
-    let banks = parse_banks(&json_data)?;
-    Ok(banks)
+    parse_banks(json_data).map_err(|e| ZenginError::parse(Some(file_path), e))
 }
 
-fn load_branches_from_file(file_path: &str) -> std::result::Result<BranchMap, Box<dyn Error>> {
+fn load_branches_from_file(file_path: &str) -> Result<BranchMap, ZenginError> {
     let json_data = read_data_file(file_path)?;
-    parse_branches(&json_data)
+    parse_branches(json_data).map_err(|e| ZenginError::parse(Some(file_path), e))
+}
+
+fn load_embedded_banks() -> Result<BankMap, ZenginError> {
+    let mut banks = load_banks_from_file("banks.json")?;
+
+    if cfg!(feature = "data-major-banks") {
+        banks.retain(|code, _| is_major_bank_code(code));
+    }
+
+    if !cfg!(feature = "data-banks-only") {
+        load_all_branches(&mut banks)?;
+    }
+
+    Ok(banks)
+}
+
+fn load_bank_branches(bank: &mut Bank) -> Result<(), ZenginError> {
+    bank.branches = load_branches_from_file(format!("branches/{}.json", bank.code).as_str())?;
+    Ok(())
+}
+
+#[cfg(not(feature = "rayon"))]
+fn load_all_branches(banks: &mut BankMap) -> Result<(), ZenginError> {
+    banks.values_mut().try_for_each(load_bank_branches)
+}
+
+#[cfg(feature = "rayon")]
+fn load_all_branches(banks: &mut BankMap) -> Result<(), ZenginError> {
+    use rayon::prelude::*;
+
+    banks
+        .par_iter_mut()
+        .try_for_each(|(_, bank)| load_bank_branches(bank))
+}
+
+fn read_data_file(file_path: &str) -> Result<&'static [u8], ZenginError> {
+    let data = DATA_DIR
+        .get_file(file_path)
+        .ok_or_else(|| ZenginError::MissingFile(file_path.to_string()))?;
+    Ok(data.contents())
 }
 
-fn read_data_file(file_path: &str) -> std::result::Result<String, Box<dyn Error>> {
-    let data = DATA_DIR.get_file(file_path).unwrap();
-    let data_str = std::str::from_utf8(data.contents())?;
-    Ok(data_str.to_string())
+/// A fixed dataset for the tests that check exact results, which the daily
+/// updates of the embedded data would otherwise break.
+#[cfg(test)]
+pub(crate) fn fixture() -> Zengin {
+    Zengin::from_combined_json(include_str!("fixture.json")).unwrap()
 }
 
 #[cfg(test)]
@@ -441,10 +1577,38 @@ mod tests {
         }"#
     }
 
+    fn sample_combined_data() -> &'static str {
+        r#"{
+            "0001":{
+                "code":"0001",
+                "name":"みずほ",
+                "kana":"ミズホ",
+                "hira":"みずほ",
+                "roma":"mizuho",
+                "branches":{
+                    "001":{
+                        "code":"001",
+                        "name":"東京営業部",
+                        "kana":"トウキヨウ",
+                        "hira":"とうきよう",
+                        "roma":"toukiyou"
+                    }
+                }
+            },
+            "0005":{
+                "code":"0005",
+                "name":"三菱ＵＦＪ",
+                "kana":"ミツビシユ－エフジエイ",
+                "hira":"みつびしゆ－えふじえい",
+                "roma":"mitsubishiyu-efujiei"
+            }
+        }"#
+    }
+
     #[test]
     fn test_parse_banks() {
         let json_data = sample_bank_data();
-        let banks = parse_banks(json_data).unwrap();
+        let banks = parse_banks(json_data.as_bytes()).unwrap();
         assert_eq!(banks["0001"].name, "みずほ");
         assert_eq!(banks["0005"].name, "三菱ＵＦＪ");
     }
@@ -452,7 +1616,7 @@ mod tests {
     #[test]
     fn test_parse_branches() {
         let json_data = sample_branch_data();
-        let branches = parse_branches(json_data).unwrap();
+        let branches = parse_branches(json_data.as_bytes()).unwrap();
         assert_eq!(branches["001"].name, "東京営業部");
     }
 
@@ -461,4 +1625,318 @@ mod tests {
         let zengin = Zengin::new().unwrap();
         assert_eq!(zengin.banks["0001"].name, "みずほ");
     }
+
+    #[test]
+    fn test_to_json() {
+        let mut banks = parse_banks(sample_bank_data().as_bytes()).unwrap();
+        banks.get_mut("0001").unwrap().branches =
+            parse_branches(sample_branch_data().as_bytes()).unwrap();
+        let zengin = Zengin::from_banks(banks);
+
+        let json: serde_json::Value = serde_json::from_str(&zengin.to_json().unwrap()).unwrap();
+        assert_eq!(json["0001"]["name"], "みずほ");
+        assert_eq!(json["0001"]["branches"]["001"]["name"], "東京営業部");
+        assert_eq!(json["0005"]["branches"], serde_json::json!({}));
+    }
+
+    #[test]
+    fn test_from_combined_json() {
+        let zengin = Zengin::from_combined_json(sample_combined_data()).unwrap();
+        let bank = zengin.get_bank("0001").unwrap();
+        assert_eq!(bank.get_branch("001").unwrap().name, "東京営業部");
+        assert!(zengin.get_bank("0005").unwrap().all_branches().is_empty());
+
+        let roundtrip = Zengin::from_combined_json(&zengin.to_json().unwrap()).unwrap();
+        assert_eq!(roundtrip.all_banks().len(), 2);
+
+        let json = zengin.to_json().unwrap();
+        let streamed = Zengin::from_combined_reader(json.as_bytes()).unwrap();
+        assert_eq!(streamed.get_bank("0001").unwrap().all_branches().len(), 1);
+    }
+
+    #[test]
+    fn test_get_bank_by_typed_code() {
+        let zengin = crate::fixture();
+        let code: BankCode = "0001".parse().unwrap();
+        assert_eq!(zengin.get_bank(code).unwrap().name, "みずほ");
+        assert_eq!(zengin.get_bank("1").unwrap().name, "みずほ");
+        assert_eq!(zengin.get_bank(1u16).unwrap().name, "みずほ");
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        assert!(matches!(
+            Zengin::from_combined_json("{"),
+            Err(ZenginError::Parse { file: None, .. })
+        ));
+        assert!(matches!(
+            read_data_file("branches/0000.json"),
+            Err(ZenginError::MissingFile(file)) if file == "branches/0000.json"
+        ));
+
+        let zengin = Zengin::from_combined_json("{}").unwrap();
+        assert!(matches!(
+            zengin.find_banks_by_name("("),
+            Err(ZenginError::InvalidPattern(_))
+        ));
+
+        let json = sample_combined_data().replacen(r#""code":"001""#, r#""code":"1""#, 1);
+        let e = Zengin::from_combined_json(&json).err().unwrap();
+        assert!(matches!(
+            &e,
+            ZenginError::InvalidCode { code, expected: 3 } if code == "1"
+        ));
+        assert_eq!(e.to_string(), "invalid code \"1\": expected 3 ASCII digits");
+    }
+
+    #[test]
+    fn test_get_bank_by_name() {
+        let zengin = Zengin::from_combined_json(sample_bank_data()).unwrap();
+        assert_eq!(zengin.get_bank_by_name("三菱ＵＦＪ").unwrap().code, "0005");
+        assert!(zengin.get_bank_by_name("みず").is_none());
+    }
+
+    #[test]
+    fn test_autocomplete() {
+        let zengin = Zengin::from_combined_json(sample_bank_data()).unwrap();
+        let codes =
+            |banks: Vec<&Bank>| banks.iter().map(|b| b.code.to_string()).collect::<Vec<_>>();
+        assert_eq!(codes(zengin.autocomplete_banks("みず", 10)), vec!["0001"]);
+        assert_eq!(
+            codes(zengin.autocomplete_banks("MI", 10)),
+            vec!["0005", "0001"]
+        );
+        assert_eq!(codes(zengin.autocomplete_banks("MI", 1)), vec!["0005"]);
+
+        let zengin = Zengin::from_combined_json(sample_combined_data()).unwrap();
+        let bank = zengin.get_bank("0001").unwrap();
+        assert_eq!(bank.autocomplete_branches("トウ", 10)[0].code, "001");
+        assert!(bank.autocomplete_branches("トウ", 0).is_empty());
+    }
+
+    #[test]
+    fn test_find_by_roma_ignores_case() {
+        let zengin = Zengin::from_combined_json(sample_combined_data()).unwrap();
+        let banks = zengin.find_banks_by_roma("^Mizuho$").unwrap();
+        assert_eq!(banks.len(), 1);
+        assert_eq!(banks[0].code, "0001");
+
+        let bank = zengin.get_bank("0001").unwrap();
+        assert_eq!(bank.find_branches_by_roma("TOUKIYOU").unwrap().len(), 1);
+        assert!(zengin.find_banks_by_name("^ミズホ$").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_find_by_precompiled_regex() {
+        let zengin = Zengin::from_combined_json(sample_combined_data()).unwrap();
+        let re = Regex::new("^みずほ$").unwrap();
+        assert_eq!(zengin.find_banks_by_name_re(&re).len(), 1);
+        assert_eq!(zengin.find_banks_by_hira_re(&re).len(), 1);
+        assert!(zengin.find_banks_by_kana_re(&re).is_empty());
+
+        let re = Regex::new("TOUKIYOU").unwrap();
+        let bank = zengin.get_bank("0001").unwrap();
+        assert!(bank.find_branches_by_roma_re(&re).is_empty());
+    }
+
+    #[test]
+    fn test_find_branches_across_banks() {
+        let zengin = fixture();
+        let pairs: Vec<_> = zengin
+            .find_branches_by_name("新宿")
+            .unwrap()
+            .into_iter()
+            .map(|(bank, branch)| (bank.code.to_string(), branch.code.to_string()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("0001".to_string(), "110".to_string()),
+                ("0005".to_string(), "330".to_string()),
+                ("0009".to_string(), "221".to_string())
+            ]
+        );
+        assert!(zengin.find_branches_by_name("(").is_err());
+    }
+
+    #[test]
+    fn test_get_branch_by_pair() {
+        let zengin = crate::fixture();
+        let (bank, branch) = zengin.get_branch("0001", 1).unwrap();
+        assert_eq!(bank.name, "みずほ");
+        assert_eq!(branch.name, "東京営業部");
+        assert!(zengin.get_branch("0001", "999").is_none());
+        assert!(zengin.get_branch("9999", "001").is_none());
+        assert!(zengin.get_branch("x", "001").is_none());
+    }
+
+    #[test]
+    fn test_try_get() {
+        let zengin = fixture();
+        assert_eq!(zengin.try_get_bank(1).unwrap().name, "みずほ");
+        let e = zengin.try_get_bank("0008").unwrap_err();
+        assert_eq!(
+            e,
+            NotFound::Bank {
+                code: "0008".parse().unwrap(),
+                retired_on: Some("2006-01-01"),
+                successor: Some("0005".parse().unwrap()),
+            }
+        );
+        assert_eq!(
+            e.to_string(),
+            "bank 0008 was retired on 2006-01-01, consider 0005"
+        );
+        assert_eq!(
+            zengin.try_get_bank("0999").unwrap_err().to_string(),
+            "bank 0999 not found"
+        );
+        assert_eq!(
+            zengin.try_get_bank("x").unwrap_err(),
+            NotFound::InvalidBankCode
+        );
+
+        let (bank, branch) = zengin.try_get_branch("0001", 1).unwrap();
+        assert_eq!(
+            (bank.name.as_str(), branch.name.as_str()),
+            ("みずほ", "東京営業部")
+        );
+        assert!(matches!(
+            zengin.try_get_branch("0008", "001"),
+            Err(NotFound::Bank { .. })
+        ));
+        assert_eq!(
+            zengin
+                .try_get_branch("0001", "999")
+                .unwrap_err()
+                .to_string(),
+            "branch 999 not found for bank 0001"
+        );
+        assert_eq!(
+            zengin.try_get_branch("0001", "x").unwrap_err(),
+            NotFound::InvalidBranchCode
+        );
+    }
+
+    #[test]
+    fn test_find_results_in_code_order() {
+        let zengin = crate::fixture();
+        let codes: Vec<_> = zengin
+            .find_banks_by_roma(".")
+            .unwrap()
+            .iter()
+            .map(|bank| bank.code.to_string())
+            .collect();
+        let mut sorted = codes.clone();
+        sorted.sort();
+        assert_eq!(codes, sorted);
+        assert_eq!(codes.len(), zengin.all_banks().len());
+
+        let bank = zengin.get_bank("0001").unwrap();
+        let branches = bank.find_branches_by_name(".").unwrap();
+        assert!(branches.windows(2).all(|w| w[0].code < w[1].code));
+    }
+
+    #[test]
+    fn test_find_iter() {
+        let zengin = crate::fixture();
+        let mut codes: Vec<_> = zengin
+            .find_banks_by_roma_iter("MIZUHO")
+            .unwrap()
+            .map(|bank| bank.code.to_string())
+            .collect();
+        codes.sort();
+        assert_eq!(codes, vec!["0001", "0289"]);
+        assert_eq!(
+            zengin.find_banks_by_kana_iter(".").unwrap().take(2).count(),
+            2
+        );
+        assert!(zengin.find_banks_by_name_iter("(").is_err());
+
+        let bank = zengin.get_bank("0001").unwrap();
+        assert_eq!(
+            bank.find_branches_by_name_iter("^新宿$").unwrap().count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_sorted_codes() {
+        let zengin = fixture();
+        let codes = zengin.bank_codes();
+        assert_eq!(codes.len(), zengin.all_banks().len());
+        assert_eq!(&codes[..3], ["0001", "0005", "0009"]);
+        assert!(codes.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(
+            zengin.get_bank("0001").unwrap().branch_codes(),
+            vec!["001", "004", "110", "251"]
+        );
+    }
+
+    #[test]
+    fn test_batch_lookup() {
+        let zengin = crate::fixture();
+        let banks = zengin.get_banks(&["0005", "9999", "1"]);
+        let found: Vec<_> = banks
+            .iter()
+            .map(|(code, bank)| (*code, bank.map(|bank| bank.name.as_str())))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("0005", Some("三菱ＵＦＪ")),
+                ("9999", None),
+                ("1", Some("みずほ"))
+            ]
+        );
+
+        let codes = vec!["999".to_string(), "001".to_string()];
+        let branches = zengin["0001"].get_branches(&codes);
+        assert!(branches[0].1.is_none());
+        assert_eq!(branches[1].1.map(|b| b.name.as_str()), Some("東京営業部"));
+    }
+
+    #[test]
+    fn test_validate() {
+        let zengin = crate::fixture();
+        assert!(zengin.validate("0001", "001").is_ok());
+        assert!(matches!(
+            zengin.validate("1", "001"),
+            Err(ValidationError::InvalidBankCode(_))
+        ));
+        assert!(matches!(
+            zengin.validate("0001", "1a"),
+            Err(ValidationError::InvalidBranchCode(_))
+        ));
+        assert_eq!(
+            zengin.validate("9999", "001").unwrap_err().to_string(),
+            "bank 9999 not found"
+        );
+        assert_eq!(
+            zengin.validate("0001", "999").unwrap_err().to_string(),
+            "branch 999 not found for bank 0001"
+        );
+    }
+
+    #[test]
+    fn test_index() {
+        let zengin = crate::fixture();
+        assert_eq!(zengin["0001"]["001"].name, "東京営業部");
+        assert_eq!(zengin["5"].name, "三菱ＵＦＪ");
+    }
+
+    #[test]
+    #[should_panic(expected = "bank 0001 has no branch with code \"999\"")]
+    fn test_index_missing_branch() {
+        let zengin = crate::fixture();
+        let _ = &zengin["0001"]["999"];
+    }
+
+    #[test]
+    fn test_is_major_bank_code() {
+        assert!(is_major_bank_code("0001"));
+        assert!(is_major_bank_code("0038"));
+        assert!(!is_major_bank_code("0117"));
+        assert!(!is_major_bank_code("9900"));
+    }
 }
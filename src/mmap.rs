@@ -0,0 +1,300 @@
+//! Memory-mapped access to a prebuilt dataset file.
+//!
+//! [`MappedZengin`] maps a combined JSON document (as written by
+//! [`Zengin::to_json`](crate::Zengin::to_json)) into memory and hands out
+//! string slices that point straight into the mapped pages. Worker processes
+//! that open the same file share those pages instead of each holding its own
+//! copy of every bank and branch string; only a small per-process table of
+//! offsets is allocated.
+
+use crate::ZenginError;
+use memmap2::Mmap;
+use serde::{de, Deserialize};
+use std::{collections::HashMap, fs::File, ops::Range, path::Path};
+
+/// A dataset backed by a memory-mapped combined JSON document.
+pub struct MappedZengin {
+    mmap: Mmap,
+    banks: Vec<BankEntry>,
+    branches: Vec<Entry>,
+}
+
+impl MappedZengin {
+    /// Memory-maps the combined JSON document at `path`.
+    ///
+    /// The document must not contain escape sequences in its strings, so that
+    /// every value can be borrowed from the file as is. Documents written by
+    /// `Zengin::to_json` satisfy this.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or mapped, if it is not
+    /// UTF-8, or if it is not a valid combined document.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while the returned
+    /// `MappedZengin` is alive, by this process or any other. The strings it
+    /// hands out point into the mapped pages, which are checked to be UTF-8
+    /// only once, here.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::mmap::MappedZengin;
+    /// // SAFETY: nothing writes to zengin.json while it is mapped.
+    /// let zengin = unsafe { MappedZengin::open("zengin.json") }.unwrap();
+    /// if let Some(bank) = zengin.get_bank("0001") {
+    ///     println!("Found bank: {}", bank.name());
+    /// }
+    /// ```
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> Result<MappedZengin, ZenginError> {
+        let path = path.as_ref();
+        let parse_error = |e| ZenginError::parse(Some(&path.to_string_lossy()), e);
+        let file = File::open(path)?;
+        // SAFETY: the mapping is read-only, and the caller guarantees that the
+        // file is not modified while it is mapped, so it stays the UTF-8 that
+        // is checked here.
+        let mmap = unsafe { Mmap::map(&file)? };
+        std::str::from_utf8(&mmap).map_err(|e| parse_error(de::Error::custom(e)))?;
+        let (banks, branches) = index(&mmap).map_err(parse_error)?;
+        Ok(MappedZengin {
+            mmap,
+            banks,
+            branches,
+        })
+    }
+
+    /// Retrieves a bank by its code.
+    pub fn get_bank(&self, code: &str) -> Option<MappedBank<'_>> {
+        self.banks
+            .binary_search_by(|bank| self.str(bank.entry.code()).cmp(code))
+            .ok()
+            .map(|i| MappedBank {
+                zengin: self,
+                bank: &self.banks[i],
+            })
+    }
+
+    /// Returns an iterator over all banks in code order.
+    pub fn banks(&self) -> impl Iterator<Item = MappedBank<'_>> {
+        self.banks
+            .iter()
+            .map(move |bank| MappedBank { zengin: self, bank })
+    }
+
+    fn str(&self, span: &Range<usize>) -> &str {
+        // SAFETY: `open` checked that the file is UTF-8, its caller guarantees
+        // that the file has not changed since, and the spans are those of JSON
+        // strings, which start and end on character boundaries.
+        unsafe { std::str::from_utf8_unchecked(&self.mmap[span.clone()]) }
+    }
+}
+
+/// A bank borrowed from a [`MappedZengin`].
+#[derive(Clone, Copy)]
+pub struct MappedBank<'a> {
+    zengin: &'a MappedZengin,
+    bank: &'a BankEntry,
+}
+
+impl<'a> MappedBank<'a> {
+    /// The code of the bank.
+    pub fn code(&self) -> &'a str {
+        self.zengin.str(self.bank.entry.code())
+    }
+
+    /// The name of the bank.
+    pub fn name(&self) -> &'a str {
+        self.zengin.str(self.bank.entry.name())
+    }
+
+    /// The name of the bank in katakana.
+    pub fn kana(&self) -> &'a str {
+        self.zengin.str(self.bank.entry.kana())
+    }
+
+    /// The name of the bank in hiragana.
+    pub fn hira(&self) -> &'a str {
+        self.zengin.str(self.bank.entry.hira())
+    }
+
+    /// The name of the bank in romaji.
+    pub fn roma(&self) -> &'a str {
+        self.zengin.str(self.bank.entry.roma())
+    }
+
+    /// Retrieves a branch of this bank by its code.
+    pub fn get_branch(&self, code: &str) -> Option<MappedBranch<'a>> {
+        let zengin = self.zengin;
+        let branches = &zengin.branches[self.bank.branches.clone()];
+        branches
+            .binary_search_by(|branch| zengin.str(branch.code()).cmp(code))
+            .ok()
+            .map(|i| MappedBranch {
+                zengin,
+                branch: &branches[i],
+            })
+    }
+
+    /// Returns an iterator over the branches of this bank in code order.
+    pub fn branches(&self) -> impl Iterator<Item = MappedBranch<'a>> {
+        let zengin = self.zengin;
+        zengin.branches[self.bank.branches.clone()]
+            .iter()
+            .map(move |branch| MappedBranch { zengin, branch })
+    }
+}
+
+/// A branch borrowed from a [`MappedZengin`].
+#[derive(Clone, Copy)]
+pub struct MappedBranch<'a> {
+    zengin: &'a MappedZengin,
+    branch: &'a Entry,
+}
+
+impl<'a> MappedBranch<'a> {
+    /// The code of the branch.
+    pub fn code(&self) -> &'a str {
+        self.zengin.str(self.branch.code())
+    }
+
+    /// The name of the branch.
+    pub fn name(&self) -> &'a str {
+        self.zengin.str(self.branch.name())
+    }
+
+    /// The name of the branch in katakana.
+    pub fn kana(&self) -> &'a str {
+        self.zengin.str(self.branch.kana())
+    }
+
+    /// The name of the branch in hiragana.
+    pub fn hira(&self) -> &'a str {
+        self.zengin.str(self.branch.hira())
+    }
+
+    /// The name of the branch in romaji.
+    pub fn roma(&self) -> &'a str {
+        self.zengin.str(self.branch.roma())
+    }
+}
+
+/// Byte ranges of the code, name, kana, hira, and roma fields.
+struct Entry([Range<usize>; 5]);
+
+impl Entry {
+    fn code(&self) -> &Range<usize> {
+        &self.0[0]
+    }
+
+    fn name(&self) -> &Range<usize> {
+        &self.0[1]
+    }
+
+    fn kana(&self) -> &Range<usize> {
+        &self.0[2]
+    }
+
+    fn hira(&self) -> &Range<usize> {
+        &self.0[3]
+    }
+
+    fn roma(&self) -> &Range<usize> {
+        &self.0[4]
+    }
+}
+
+struct BankEntry {
+    entry: Entry,
+    branches: Range<usize>,
+}
+
+#[derive(Deserialize)]
+struct RawBank<'a> {
+    code: &'a str,
+    name: &'a str,
+    kana: &'a str,
+    hira: &'a str,
+    roma: &'a str,
+    #[serde(borrow, default)]
+    branches: HashMap<&'a str, RawBranch<'a>>,
+}
+
+#[derive(Deserialize)]
+struct RawBranch<'a> {
+    code: &'a str,
+    name: &'a str,
+    kana: &'a str,
+    hira: &'a str,
+    roma: &'a str,
+}
+
+fn index(data: &[u8]) -> Result<(Vec<BankEntry>, Vec<Entry>), serde_json::Error> {
+    let raw: HashMap<&str, RawBank<'_>> = serde_json::from_slice(data)?;
+    let span = |s: &str| {
+        let start = s.as_ptr() as usize - data.as_ptr() as usize;
+        start..start + s.len()
+    };
+
+    let mut raw_banks: Vec<_> = raw.into_values().collect();
+    raw_banks.sort_unstable_by_key(|bank| bank.code);
+
+    let mut banks = Vec::with_capacity(raw_banks.len());
+    let mut branches = Vec::new();
+    for bank in raw_banks {
+        let mut raw_branches: Vec<_> = bank.branches.into_values().collect();
+        raw_branches.sort_unstable_by_key(|branch| branch.code);
+
+        let start = branches.len();
+        branches.extend(raw_branches.into_iter().map(|branch| {
+            Entry([
+                span(branch.code),
+                span(branch.name),
+                span(branch.kana),
+                span(branch.hira),
+                span(branch.roma),
+            ])
+        }));
+        banks.push(BankEntry {
+            entry: Entry([
+                span(bank.code),
+                span(bank.name),
+                span(bank.kana),
+                span(bank.hira),
+                span(bank.roma),
+            ]),
+            branches: start..branches.len(),
+        });
+    }
+
+    Ok((banks, branches))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open() {
+        let path = std::env::temp_dir().join(format!("zengin-mmap-{}.json", std::process::id()));
+        std::fs::write(&path, crate::fixture().to_json().unwrap()).unwrap();
+
+        let zengin = unsafe { MappedZengin::open(&path) }.unwrap();
+        let bank = zengin.get_bank("0001").unwrap();
+        assert_eq!(bank.name(), "みずほ");
+        assert_eq!(bank.roma(), "mizuho");
+        assert!(zengin.get_bank("0000").is_none());
+        assert!(zengin.banks().map(|bank| bank.code()).is_sorted());
+
+        let mut json = crate::fixture().to_json().unwrap().into_bytes();
+        let name = json.windows(3).position(|w| w == "ほ".as_bytes()).unwrap();
+        json[name + 1] = 0xff;
+        std::fs::write(&path, json).unwrap();
+        assert!(matches!(
+            unsafe { MappedZengin::open(&path) },
+            Err(ZenginError::Parse { file: Some(_), .. })
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,176 @@
+//! UniFFI bindings of the dataset, for Swift and Kotlin apps.
+//!
+//! [`ZenginClient`] loads the embedded dataset, and looks up and searches
+//! banks and branches, which it returns as [`BankRecord`] and
+//! [`BranchRecord`] values. UniFFI renames the methods in the style of each
+//! language, so `get_bank` is `getBank` in Swift and Kotlin.
+//!
+//! Build the library with
+//! `cargo rustc --release --lib --features uniffi --crate-type cdylib`, then
+//! generate the sources of a language from it with
+//! `cargo run --features uniffi-cli --bin uniffi-bindgen -- generate --library target/release/libzengin.so --language swift --out-dir out`.
+
+use crate::{Record, Zengin, ZenginError};
+use std::sync::Arc;
+
+/// A bank, without its branches.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct BankRecord {
+    pub code: String,
+    pub name: String,
+    pub kana: String,
+    pub hira: String,
+    pub roma: String,
+}
+
+/// A branch of a bank.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct BranchRecord {
+    pub bank_code: String,
+    pub code: String,
+    pub name: String,
+    pub kana: String,
+    pub hira: String,
+    pub roma: String,
+}
+
+impl From<&crate::Bank> for BankRecord {
+    fn from(bank: &crate::Bank) -> Self {
+        let [name, kana, hira, roma] = bank.names().map(str::to_string);
+        BankRecord {
+            code: bank.code().to_string(),
+            name,
+            kana,
+            hira,
+            roma,
+        }
+    }
+}
+
+impl BranchRecord {
+    fn new(bank: &crate::Bank, branch: &crate::Branch) -> Self {
+        let [name, kana, hira, roma] = branch.names().map(str::to_string);
+        BranchRecord {
+            bank_code: bank.code().to_string(),
+            code: branch.code().to_string(),
+            name,
+            kana,
+            hira,
+            roma,
+        }
+    }
+}
+
+/// The error of [`ZenginClient::new`], with the message of the
+/// [`ZenginError`] it stands for.
+#[derive(Debug, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum LoadError {
+    Failed(ZenginError),
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Failed(e) => e.fmt(f),
+        }
+    }
+}
+
+/// Lookups and searches over the embedded dataset.
+#[derive(uniffi::Object)]
+pub struct ZenginClient {
+    zengin: Zengin,
+}
+
+#[uniffi::export]
+impl ZenginClient {
+    /// Loads the embedded dataset.
+    #[uniffi::constructor]
+    pub fn new() -> Result<Arc<Self>, LoadError> {
+        let zengin = Zengin::new().map_err(LoadError::Failed)?;
+        Ok(Arc::new(ZenginClient { zengin }))
+    }
+
+    /// Returns a bank by its code. Stripped leading zeros are restored, so
+    /// `"1"` finds bank `0001`.
+    pub fn get_bank(&self, code: String) -> Option<BankRecord> {
+        self.zengin.get_bank(code.as_str()).map(BankRecord::from)
+    }
+
+    /// Returns a branch by the code of its bank and its own code.
+    pub fn get_branch(&self, bank_code: String, code: String) -> Option<BranchRecord> {
+        self.zengin
+            .get_branch(bank_code.as_str(), code.as_str())
+            .map(|(bank, branch)| BranchRecord::new(bank, branch))
+    }
+
+    /// Returns at most `limit` banks matching a query in any of their names,
+    /// most relevant first, or all of them if `limit` is 0.
+    pub fn search_banks(&self, query: String, limit: u32) -> Vec<BankRecord> {
+        let limit = if limit == 0 {
+            usize::MAX
+        } else {
+            limit as usize
+        };
+        self.zengin
+            .find_banks_ranked(&query)
+            .into_iter()
+            .take(limit)
+            .map(|(_, bank)| bank.into())
+            .collect()
+    }
+
+    /// Returns at most `limit` branches of a bank matching a query in any of
+    /// their names, most relevant first, or all of them if `limit` is 0.
+    /// Returns no branches if there is no bank with the code.
+    pub fn search_branches(
+        &self,
+        bank_code: String,
+        query: String,
+        limit: u32,
+    ) -> Vec<BranchRecord> {
+        let Some(bank) = self.zengin.get_bank(bank_code.as_str()) else {
+            return vec![];
+        };
+        let limit = if limit == 0 {
+            usize::MAX
+        } else {
+            limit as usize
+        };
+        bank.find_branches_ranked(&query)
+            .into_iter()
+            .take(limit)
+            .map(|(_, branch)| BranchRecord::new(bank, branch))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client() {
+        let client = ZenginClient {
+            zengin: crate::fixture(),
+        };
+        let bank = client.get_bank("1".to_string()).unwrap();
+        assert_eq!((bank.code.as_str(), bank.name.as_str()), ("0001", "みずほ"));
+        let branch = client
+            .get_branch("0001".to_string(), "110".to_string())
+            .unwrap();
+        assert_eq!(
+            (branch.bank_code.as_str(), branch.name.as_str()),
+            ("0001", "新宿")
+        );
+        assert_eq!(client.get_bank("9999".to_string()), None);
+
+        assert_eq!(client.search_banks("みずほ".to_string(), 1), vec![bank]);
+        let branches = client.search_branches("0001".to_string(), "新宿".to_string(), 0);
+        assert_eq!(branches[0], branch);
+        assert!(client
+            .search_branches("9999".to_string(), "新宿".to_string(), 0)
+            .is_empty());
+    }
+}
@@ -0,0 +1,70 @@
+//! MessagePack serialization of the dataset.
+
+use crate::{Zengin, ZenginError};
+
+impl Zengin {
+    /// Serializes the entire dataset into MessagePack, a compact binary
+    /// counterpart of [`Zengin::to_json`] for sending the dataset to clients.
+    ///
+    /// The structure is the same as that of the JSON document, with banks and
+    /// branches as maps keyed by field name, so that clients in other
+    /// languages can decode it without knowing the field order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// std::fs::write("zengin.msgpack", zengin.to_msgpack().unwrap()).unwrap();
+    /// ```
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec_named(self)
+    }
+
+    /// Creates a new instance of `Zengin` from a MessagePack dataset, as
+    /// written by [`Zengin::to_msgpack`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The MessagePack dataset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dataset cannot be decoded, or a bank or branch
+    /// code in it is malformed.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let bytes = std::fs::read("zengin.msgpack").unwrap();
+    /// let zengin = Zengin::from_msgpack(&bytes).unwrap();
+    /// ```
+    pub fn from_msgpack(bytes: &[u8]) -> Result<Zengin, ZenginError> {
+        let banks = rmp_serde::from_slice(bytes).map_err(ZenginError::Decode)?;
+        crate::check_codes(&banks)?;
+        Ok(Zengin::from_banks(banks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_msgpack_roundtrip() {
+        let zengin = crate::fixture();
+        let bytes = zengin.to_msgpack().unwrap();
+        assert!(bytes.len() < zengin.to_json().unwrap().len());
+        let decoded = Zengin::from_msgpack(&bytes).unwrap();
+        assert_eq!(decoded.to_json().unwrap(), zengin.to_json().unwrap());
+        assert_eq!(decoded["0001"]["110"].name, "新宿");
+
+        assert!(matches!(
+            Zengin::from_msgpack(&bytes[..bytes.len() / 2]),
+            Err(ZenginError::Decode(_))
+        ));
+    }
+}
@@ -0,0 +1,284 @@
+//! Normalization of names and queries for script- and width-insensitive search.
+//!
+//! The dataset spells names in a specific script and width: `name` uses
+//! full-width alphanumerics such as `三菱ＵＦＪ`, `kana` uses full-width
+//! katakana, and `hira` uses hiragana. [`normalize`] folds all of these
+//! variants into one form so that a query matches regardless of how it was
+//! typed.
+//!
+//! It also folds spelling variants of kana that people use interchangeably.
+//! The dataset spells `トウキヨウ` with a large `ヨ` and an explicit `ウ`, while
+//! most people type `トーキョー`, and both normalize to `とうきよう`.
+
+use unicode_normalization::UnicodeNormalization;
+
+/// Normalizes a string for comparison.
+///
+/// This applies NFKC (which turns full-width alphanumerics into ASCII and
+/// half-width katakana into full-width katakana), folds katakana into
+/// hiragana, and lowercases ASCII letters. It then folds kana variants:
+///
+/// * small kana become large, so `ッ` and `ツ` are equal,
+/// * `ヴ` becomes `ブ`,
+/// * the long vowel mark `ー` becomes the vowel it lengthens, with `e` and `o`
+///   lengthened to `い` and `う` as in `けい` and `とう`. The dashes that the
+///   dataset and users put in its place, `－`, `−`, and `-`, are folded the
+///   same way when they follow kana.
+///
+/// # Examples
+/// ```
+/// use zengin::normalize::normalize;
+/// assert_eq!(normalize("三菱ＵＦＪ"), normalize("三菱UFJ"));
+/// assert_eq!(normalize("ミズホ"), "みずほ");
+/// assert_eq!(normalize("ﾐｽﾞﾎ"), "みずほ");
+/// assert_eq!(normalize("トーキョー"), normalize("トウキヨウ"));
+/// assert_eq!(normalize("ユーエフジエイ"), normalize("ユ－エフジエイ"));
+/// ```
+pub fn normalize(s: &str) -> String {
+    let mut normalized = String::with_capacity(s.len());
+    for c in s.nfkc().map(katakana_to_hiragana) {
+        let c = match c {
+            // NFKC has already turned `－` into `-`.
+            'ー' | '−' | '-' => match normalized.chars().next_back() {
+                Some(prev @ 'ぁ'..='ゖ') => long_vowel(prev).unwrap_or('ー'),
+                _ => c,
+            },
+            'ゔ' => 'ぶ',
+            _ => small_to_large(c),
+        };
+        normalized.push(c.to_ascii_lowercase());
+    }
+    normalized
+}
+
+/// Normalizes the name of a financial institution typed by a user, for
+/// matching against the names of the dataset.
+///
+/// This applies [`normalize`], then strips corporate-form markers such as
+/// `株式会社` or `(株)` and institutional suffixes such as `銀行` or
+/// `信用金庫`, which users type but the dataset may leave out. Apply it to
+/// both sides of a comparison.
+///
+/// # Examples
+/// ```
+/// use zengin::normalize::normalized_name;
+/// assert_eq!(normalized_name("株式会社みずほ銀行"), normalized_name("みずほ"));
+/// assert_eq!(normalized_name("朝日信金"), normalized_name("朝日信用金庫"));
+/// ```
+pub fn normalized_name(name: &str) -> String {
+    // NFKC turns `（株）` and `㈱` into `(株)`.
+    const MARKERS: [&str; 2] = ["株式会社", "(株)"];
+    const SUFFIXES: [&str; 11] = [
+        "銀行",
+        "信用金庫",
+        "信用組合",
+        "労働金庫",
+        "農業協同組合",
+        "漁業協同組合",
+        "信金",
+        "信組",
+        "労金",
+        "農協",
+        "漁協",
+    ];
+    let mut name = normalize(name.trim());
+    for marker in MARKERS {
+        name = name.replace(marker, "");
+    }
+    let name = name.trim();
+    let stripped = SUFFIXES
+        .iter()
+        .find_map(|suffix| name.strip_suffix(suffix))
+        .filter(|stripped| !stripped.is_empty())
+        .unwrap_or(name);
+    stripped.to_string()
+}
+
+/// Converts kana into the half-width katakana of Zengin transfer data.
+///
+/// Hiragana and full-width katakana become half-width katakana, with voiced
+/// kana split into a base and a voicing mark, so `ガ` becomes `ｶﾞ` and `ぱ`
+/// becomes `ﾊﾟ`. Kana without a half-width form are replaced by their closest
+/// one, such as `ヰ` by `ｲ` and `ヶ` by `ｹ`. Full-width ASCII characters and
+/// the ideographic space become their ASCII counterparts, and anything else
+/// is kept as is.
+///
+/// # Examples
+/// ```
+/// use zengin::normalize::to_halfwidth_kana;
+/// assert_eq!(to_halfwidth_kana("ミツビシユーエフジエイ"), "ﾐﾂﾋﾞｼﾕｰｴﾌｼﾞｴｲ");
+/// assert_eq!(to_halfwidth_kana("やまだ　たろう"), "ﾔﾏﾀﾞ ﾀﾛｳ");
+/// assert_eq!(to_halfwidth_kana("（カ）ＡＢＣ"), "(ｶ)ABC");
+/// ```
+pub fn to_halfwidth_kana(s: &str) -> String {
+    let mut converted = String::with_capacity(s.len());
+    for c in s.chars() {
+        let c = match c {
+            'ぁ'..='ゖ' | 'ゝ' | 'ゞ' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+            '！'..='～' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            '\u{3000}' => ' ',
+            '゛' => '\u{3099}',
+            '゜' => '\u{309A}',
+            _ => c,
+        };
+        for c in std::iter::once(c).nfd() {
+            let c = match c {
+                'ヰ' => 'イ',
+                'ヱ' => 'エ',
+                'ヮ' => 'ワ',
+                'ヵ' => 'カ',
+                'ヶ' => 'ケ',
+                _ => c,
+            };
+            let halfwidth = ('\u{FF61}'..='\u{FF9F}').find(|&h| h.nfkc().eq([c]));
+            converted.push(halfwidth.unwrap_or(c));
+        }
+    }
+    converted
+}
+
+/// Converts an account holder name into the characters that Zengin transfer
+/// data carries, as listed by [`is_holder_char`](crate::account::is_holder_char).
+///
+/// The name is converted by [`to_halfwidth_kana`] first. Then, as banks
+/// expect, small kana become large ones, so `ｬ` becomes `ﾔ`, the long vowel
+/// mark and hyphen-like dashes become `-`, and lower-case letters become
+/// upper-case ones. Characters the format has no counterpart for, such as
+/// kanji, are kept, so that validation still reports them.
+///
+/// # Examples
+/// ```
+/// use zengin::normalize::to_holder_kana;
+/// assert_eq!(to_holder_kana("キャロル"), "ｷﾔﾛﾙ");
+/// assert_eq!(to_holder_kana("ジョー　ｓｍｉｔｈ"), "ｼﾞﾖ- SMITH");
+/// ```
+pub fn to_holder_kana(s: &str) -> String {
+    to_halfwidth_kana(s)
+        .chars()
+        .map(|c| match c {
+            'ｧ' => 'ｱ',
+            'ｨ' => 'ｲ',
+            'ｩ' => 'ｳ',
+            'ｪ' => 'ｴ',
+            'ｫ' => 'ｵ',
+            'ｬ' => 'ﾔ',
+            'ｭ' => 'ﾕ',
+            'ｮ' => 'ﾖ',
+            'ｯ' => 'ﾂ',
+            'ｰ' | '‐' | '−' => '-',
+            _ => c.to_ascii_uppercase(),
+        })
+        .collect()
+}
+
+/// Converts a katakana character into the corresponding hiragana, leaving
+/// other characters (including katakana without a hiragana form, such as
+/// `ヷ`) untouched.
+pub fn katakana_to_hiragana(c: char) -> char {
+    match c {
+        'ァ'..='ヶ' | 'ヽ' | 'ヾ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// Converts a small hiragana character into the corresponding large one,
+/// leaving other characters untouched.
+pub(crate) fn small_to_large(c: char) -> char {
+    match c {
+        'ぁ' | 'ぃ' | 'ぅ' | 'ぇ' | 'ぉ' | 'っ' | 'ゃ' | 'ゅ' | 'ょ' | 'ゎ' => {
+            char::from_u32(c as u32 + 1).unwrap_or(c)
+        }
+        'ゕ' => 'か',
+        'ゖ' => 'け',
+        _ => c,
+    }
+}
+
+/// Returns the hiragana that a long vowel mark after `c` stands for.
+fn long_vowel(c: char) -> Option<char> {
+    const ROWS: [(&str, char); 5] = [
+        ("あかさたなはまやらわがざだばぱ", 'あ'),
+        ("いきしちにひみりぎじぢびぴ", 'い'),
+        ("うくすつぬふむゆるぐずづぶぷ", 'う'),
+        ("えけせてねへめれげぜでべぺ", 'い'),
+        ("おこそとのほもよろをごぞどぼぽ", 'う'),
+    ];
+    ROWS.iter()
+        .find(|(row, _)| row.contains(c))
+        .map(|&(_, vowel)| vowel)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize("ミツビシユ－エフジエイ"),
+            "みつびしゆうえふじえい"
+        );
+        assert_eq!(normalize("ミツビシユ−エフジエイ"), "みつびしゆうえふじえい");
+        assert_eq!(normalize("ミツビシユ-エフジエイ"), "みつびしゆうえふじえい");
+        assert_eq!(normalize("ン-"), "んー");
+        assert_eq!(normalize("ＡＢＣ－1"), "abc-1");
+        assert_eq!(normalize("三菱ＵＦＪ"), "三菱ufj");
+        assert_eq!(normalize("ﾄｳｷﾖｳ"), "とうきよう");
+        assert_eq!(normalize("Mizuho"), "mizuho");
+        assert_eq!(normalize("ヴ"), "ぶ");
+    }
+
+    #[test]
+    fn test_normalize_long_vowel_dashes() {
+        let zengin = crate::fixture();
+        for query in [
+            "ミツビシユーエフジエイ",
+            "ミツビシユ－エフジエイ",
+            "みつびしゆ-えふじえい",
+        ] {
+            let codes: Vec<_> = zengin
+                .find_banks_normalized(query)
+                .iter()
+                .map(|bank| bank.code.to_string())
+                .collect();
+            assert_eq!(codes, vec!["0005"], "{query}");
+        }
+    }
+
+    #[test]
+    fn test_normalized_name() {
+        assert_eq!(normalized_name("株式会社みずほ銀行"), "みずほ");
+        assert_eq!(normalized_name("（株）三菱ＵＦＪ銀行"), "三菱ufj");
+        assert_eq!(normalized_name(" 朝日信用金庫 "), "朝日");
+        assert_eq!(normalized_name("銀行"), "銀行");
+    }
+
+    #[test]
+    fn test_normalize_kana_variants() {
+        assert_eq!(normalize("トーキョー"), "とうきよう");
+        assert_eq!(normalize("ｼﾝｼﾞｭｸ"), "しんじゆく");
+        assert_eq!(normalize("ケーヨー"), "けいよう");
+        assert_eq!(normalize("ヴィ"), "ぶい");
+        assert_eq!(normalize("ー"), "ー");
+    }
+
+    #[test]
+    fn test_to_halfwidth_kana() {
+        assert_eq!(to_halfwidth_kana("トウキヨウ"), "ﾄｳｷﾖｳ");
+        assert_eq!(to_halfwidth_kana("ガギグゲゴ"), "ｶﾞｷﾞｸﾞｹﾞｺﾞ");
+        assert_eq!(to_halfwidth_kana("パピプペポヴ"), "ﾊﾟﾋﾟﾌﾟﾍﾟﾎﾟｳﾞ");
+        assert_eq!(to_halfwidth_kana("ぁっゃヲヰヶ"), "ｧｯｬｦｲｹ");
+        assert_eq!(to_halfwidth_kana("「ｶ」。、・"), "｢ｶ｣｡､･");
+        assert_eq!(to_halfwidth_kana("ﾐｽﾞﾎ 銀行"), "ﾐｽﾞﾎ 銀行");
+    }
+
+    #[test]
+    fn test_to_holder_kana() {
+        assert_eq!(to_holder_kana("キャロル"), "ｷﾔﾛﾙ");
+        assert_eq!(to_holder_kana("ジョー"), "ｼﾞﾖ-");
+        assert_eq!(to_holder_kana("ｷｯﾁｮｳ ｼｮｳﾃﾝ"), "ｷﾂﾁﾖｳ ｼﾖｳﾃﾝ");
+        assert_eq!(to_holder_kana("ぁぃぅぇぉ"), "ｱｲｳｴｵ");
+        assert_eq!(to_holder_kana("(ｶ)abc‐1"), "(ｶ)ABC-1");
+        assert_eq!(to_holder_kana("ﾔﾏﾀﾞ 太郎"), "ﾔﾏﾀﾞ 太郎");
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{Bank, Branch, Zengin, ZenginError};
+use rayon::prelude::*;
+use regex::Regex;
+
+impl Zengin {
+    /// Returns a parallel iterator over all banks.
+    ///
+    /// This function is available with the `rayon` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use rayon::prelude::*;
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let shinkin = zengin
+    ///     .par_banks()
+    ///     .filter(|bank| bank.name.ends_with("信用金庫"))
+    ///     .count();
+    /// ```
+    pub fn par_banks(&self) -> impl ParallelIterator<Item = &Bank> {
+        self.banks.par_iter().map(|(_, bank)| bank)
+    }
+
+    /// Returns a parallel iterator over the branches of every bank, each
+    /// paired with its bank.
+    ///
+    /// This function is available with the `rayon` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use rayon::prelude::*;
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let branches = zengin.par_branches().count();
+    /// ```
+    pub fn par_branches(&self) -> impl ParallelIterator<Item = (&Bank, &Branch)> {
+        self.par_banks()
+            .flat_map_iter(|bank| bank.branches.values().map(move |branch| (bank, branch)))
+    }
+
+    /// Finds branches of every bank by their name using a regular expression
+    /// pattern, searching the banks in parallel.
+    ///
+    /// This works like [`Zengin::find_branches_by_name`], and is available
+    /// with the `rayon` feature. The pairs are ordered by bank code, then
+    /// branch code.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - A string slice that holds the regular expression pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regular expression pattern is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// for (bank, branch) in zengin.par_find_branches_by_name("駅前").unwrap() {
+    ///     println!("Found branch: {} {}", bank.name, branch.name);
+    /// }
+    /// ```
+    pub fn par_find_branches_by_name(
+        &self,
+        pattern: &str,
+    ) -> Result<Vec<(&Bank, &Branch)>, ZenginError> {
+        let re = Regex::new(pattern)?;
+        let mut matched: Vec<_> = self
+            .par_branches()
+            .filter(|(_, branch)| re.is_match(&branch.name))
+            .collect();
+        matched.par_sort_unstable_by(|(a, x), (b, y)| (&a.code, &x.code).cmp(&(&b.code, &y.code)));
+        Ok(matched)
+    }
+
+    /// Finds banks by their name using a regular expression pattern,
+    /// searching the banks in parallel.
+    ///
+    /// This works like [`Zengin::find_banks_by_name`], and is available with
+    /// the `rayon` feature. Matching banks are returned in code order.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - A string slice that holds the regular expression pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regular expression pattern is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let banks = zengin.par_find_banks_by_name("信用金庫$").unwrap();
+    /// ```
+    pub fn par_find_banks_by_name(&self, pattern: &str) -> Result<Vec<&Bank>, ZenginError> {
+        let re = Regex::new(pattern)?;
+        let mut matched: Vec<_> = self
+            .par_banks()
+            .filter(|bank| re.is_match(&bank.name))
+            .collect();
+        matched.par_sort_unstable_by(|a, b| a.code.cmp(&b.code));
+        Ok(matched)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_par_find() {
+        let zengin = crate::fixture();
+        let pairs = |pairs: Vec<(&Bank, &Branch)>| {
+            pairs
+                .iter()
+                .map(|(bank, branch)| format!("{}-{}", bank.code, branch.code))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            pairs(zengin.par_find_branches_by_name("新宿").unwrap()),
+            pairs(zengin.find_branches_by_name("新宿").unwrap())
+        );
+        let codes: Vec<_> = zengin
+            .par_find_banks_by_name("みずほ")
+            .unwrap()
+            .iter()
+            .map(|bank| bank.code.to_string())
+            .collect();
+        assert_eq!(codes, vec!["0001", "0289"]);
+        assert!(zengin.par_find_banks_by_name("(").is_err());
+        assert_eq!(zengin.par_banks().count(), zengin.all_banks().len());
+    }
+}
@@ -0,0 +1,538 @@
+//! Protocol Buffers messages of the dataset, for gRPC services that serve it.
+//!
+//! The messages are defined in `proto/zengin.proto`, which ships with the
+//! crate for clients to generate their own types from. The types here are
+//! those that prost-build generates from it, kept in this file so that
+//! building the crate needs no `protoc`; update both together. The tests
+//! encode every message and check its fields against the tags and types of
+//! the `.proto` file.
+//!
+//! [`Zengin`], [`Bank`](crate::Bank) and [`Branch`](crate::Branch) convert
+//! into the messages with `From`, and a [`Dataset`] converts back into a
+//! [`Zengin`].
+//!
+//! # Examples
+//! ```
+//! use prost::Message;
+//! use zengin::{proto, Zengin};
+//! let zengin = Zengin::new().unwrap();
+//! let bytes = proto::Dataset::from(&zengin).encode_to_vec();
+//! let zengin = Zengin::from(proto::Dataset::decode(&*bytes).unwrap());
+//! ```
+
+use crate::{Record, Zengin};
+use std::sync::OnceLock;
+
+/// A branch of a bank.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Branch {
+    /// The three-digit branch code, such as "110".
+    #[prost(string, tag = "1")]
+    pub code: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    /// The name in full-width katakana.
+    #[prost(string, tag = "3")]
+    pub kana: ::prost::alloc::string::String,
+    /// The name in hiragana.
+    #[prost(string, tag = "4")]
+    pub hira: ::prost::alloc::string::String,
+    /// The romanized name.
+    #[prost(string, tag = "5")]
+    pub roma: ::prost::alloc::string::String,
+}
+
+/// A bank with its branches.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Bank {
+    /// The four-digit bank code, such as "0001".
+    #[prost(string, tag = "1")]
+    pub code: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    /// The name in full-width katakana.
+    #[prost(string, tag = "3")]
+    pub kana: ::prost::alloc::string::String,
+    /// The name in hiragana.
+    #[prost(string, tag = "4")]
+    pub hira: ::prost::alloc::string::String,
+    /// The romanized name.
+    #[prost(string, tag = "5")]
+    pub roma: ::prost::alloc::string::String,
+    /// The branches of the bank, in code order.
+    #[prost(message, repeated, tag = "6")]
+    pub branches: ::prost::alloc::vec::Vec<Branch>,
+}
+
+/// Every bank of the dataset, in code order.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Dataset {
+    #[prost(message, repeated, tag = "1")]
+    pub banks: ::prost::alloc::vec::Vec<Bank>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LookupBankRequest {
+    /// The bank code. Stripped leading zeros are restored, so "1" finds "0001".
+    #[prost(string, tag = "1")]
+    pub code: ::prost::alloc::string::String,
+    /// Whether to fill in the branches of the bank.
+    #[prost(bool, tag = "2")]
+    pub include_branches: bool,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LookupBranchRequest {
+    #[prost(string, tag = "1")]
+    pub bank_code: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub code: ::prost::alloc::string::String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchBanksRequest {
+    /// The query, matched against every name of the banks.
+    #[prost(string, tag = "1")]
+    pub query: ::prost::alloc::string::String,
+    /// The maximum number of banks to return, or 0 for all of them.
+    #[prost(uint32, tag = "2")]
+    pub limit: u32,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchBanksResponse {
+    /// The matching banks, without their branches.
+    #[prost(message, repeated, tag = "1")]
+    pub banks: ::prost::alloc::vec::Vec<Bank>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamAllBanksRequest {
+    /// Whether to fill in the branches of each bank.
+    #[prost(bool, tag = "1")]
+    pub include_branches: bool,
+}
+
+/// The server side of the `ZenginService` service, as tonic-build generates
+/// it without the compression and message size options.
+#[cfg(feature = "grpc")]
+pub mod zengin_service_server {
+    use tonic::{codec::ProstCodec, codegen::*, server::Grpc};
+
+    /// The methods of the `ZenginService` service.
+    #[async_trait]
+    pub trait ZenginService: Send + Sync + 'static {
+        /// Returns a bank by its code, or NOT_FOUND.
+        async fn lookup_bank(
+            &self,
+            request: tonic::Request<super::LookupBankRequest>,
+        ) -> Result<tonic::Response<super::Bank>, tonic::Status>;
+        /// Returns a branch by the code of its bank and its own code, or
+        /// NOT_FOUND.
+        async fn lookup_branch(
+            &self,
+            request: tonic::Request<super::LookupBranchRequest>,
+        ) -> Result<tonic::Response<super::Branch>, tonic::Status>;
+        /// Returns the banks matching a query, most relevant first.
+        async fn search_banks(
+            &self,
+            request: tonic::Request<super::SearchBanksRequest>,
+        ) -> Result<tonic::Response<super::SearchBanksResponse>, tonic::Status>;
+        /// The stream of the responses of `StreamAllBanks`.
+        type StreamAllBanksStream: tokio_stream::Stream<Item = Result<super::Bank, tonic::Status>>
+            + Send
+            + 'static;
+        /// Streams every bank in code order.
+        async fn stream_all_banks(
+            &self,
+            request: tonic::Request<super::StreamAllBanksRequest>,
+        ) -> Result<tonic::Response<Self::StreamAllBanksStream>, tonic::Status>;
+    }
+
+    /// A tower service that routes the requests of the `ZenginService`
+    /// service to an implementation of it.
+    #[derive(Debug)]
+    pub struct ZenginServiceServer<T> {
+        inner: Arc<T>,
+    }
+
+    impl<T> ZenginServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self { inner }
+        }
+    }
+
+    impl<T> Clone for ZenginServiceServer<T> {
+        fn clone(&self) -> Self {
+            Self {
+                inner: self.inner.clone(),
+            }
+        }
+    }
+
+    /// Declares a unary method of the service.
+    macro_rules! unary {
+        ($svc:ident, $method:ident, $request:ty, $response:ty) => {
+            struct $svc<T: ZenginService>(Arc<T>);
+
+            impl<T: ZenginService> tonic::server::UnaryService<$request> for $svc<T> {
+                type Response = $response;
+                type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+
+                fn call(&mut self, request: tonic::Request<$request>) -> Self::Future {
+                    let inner = Arc::clone(&self.0);
+                    Box::pin(async move { <T as ZenginService>::$method(&inner, request).await })
+                }
+            }
+        };
+    }
+
+    unary!(
+        LookupBankSvc,
+        lookup_bank,
+        super::LookupBankRequest,
+        super::Bank
+    );
+    unary!(
+        LookupBranchSvc,
+        lookup_branch,
+        super::LookupBranchRequest,
+        super::Branch
+    );
+    unary!(
+        SearchBanksSvc,
+        search_banks,
+        super::SearchBanksRequest,
+        super::SearchBanksResponse
+    );
+
+    struct StreamAllBanksSvc<T: ZenginService>(Arc<T>);
+
+    impl<T: ZenginService> tonic::server::ServerStreamingService<super::StreamAllBanksRequest>
+        for StreamAllBanksSvc<T>
+    {
+        type Response = super::Bank;
+        type ResponseStream = T::StreamAllBanksStream;
+        type Future = BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+
+        fn call(&mut self, request: tonic::Request<super::StreamAllBanksRequest>) -> Self::Future {
+            let inner = Arc::clone(&self.0);
+            Box::pin(async move { <T as ZenginService>::stream_all_banks(&inner, request).await })
+        }
+    }
+
+    impl<T, B> Service<http::Request<B>> for ZenginServiceServer<T>
+    where
+        T: ZenginService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/zengin.v1.ZenginService/LookupBank" => Box::pin(async move {
+                    Ok(Grpc::new(ProstCodec::default())
+                        .unary(LookupBankSvc(inner), req)
+                        .await)
+                }),
+                "/zengin.v1.ZenginService/LookupBranch" => Box::pin(async move {
+                    Ok(Grpc::new(ProstCodec::default())
+                        .unary(LookupBranchSvc(inner), req)
+                        .await)
+                }),
+                "/zengin.v1.ZenginService/SearchBanks" => Box::pin(async move {
+                    Ok(Grpc::new(ProstCodec::default())
+                        .unary(SearchBanksSvc(inner), req)
+                        .await)
+                }),
+                "/zengin.v1.ZenginService/StreamAllBanks" => Box::pin(async move {
+                    Ok(Grpc::new(ProstCodec::default())
+                        .server_streaming(StreamAllBanksSvc(inner), req)
+                        .await)
+                }),
+                _ => Box::pin(async move {
+                    let mut response = http::Response::new(empty_body());
+                    let headers = response.headers_mut();
+                    headers.insert(
+                        tonic::Status::GRPC_STATUS,
+                        (tonic::Code::Unimplemented as i32).into(),
+                    );
+                    headers.insert(
+                        http::header::CONTENT_TYPE,
+                        tonic::metadata::GRPC_CONTENT_TYPE,
+                    );
+                    Ok(response)
+                }),
+            }
+        }
+    }
+
+    /// The full name of the service.
+    pub const SERVICE_NAME: &str = "zengin.v1.ZenginService";
+
+    impl<T> tonic::server::NamedService for ZenginServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+
+impl From<&crate::Branch> for Branch {
+    fn from(branch: &crate::Branch) -> Self {
+        let [name, kana, hira, roma] = branch.names().map(str::to_string);
+        Branch {
+            code: branch.code().to_string(),
+            name,
+            kana,
+            hira,
+            roma,
+        }
+    }
+}
+
+impl From<&crate::Bank> for Bank {
+    fn from(bank: &crate::Bank) -> Self {
+        let [name, kana, hira, roma] = bank.names().map(str::to_string);
+        Bank {
+            code: bank.code().to_string(),
+            name,
+            kana,
+            hira,
+            roma,
+            branches: bank.iter().map(|(_, branch)| branch.into()).collect(),
+        }
+    }
+}
+
+impl From<&Zengin> for Dataset {
+    fn from(zengin: &Zengin) -> Self {
+        Dataset {
+            banks: zengin.iter().map(|(_, bank)| bank.into()).collect(),
+        }
+    }
+}
+
+impl From<Branch> for crate::Branch {
+    fn from(branch: Branch) -> Self {
+        crate::Branch {
+            code: branch.code,
+            name: branch.name,
+            kana: branch.kana,
+            hira: branch.hira,
+            roma: branch.roma,
+        }
+    }
+}
+
+impl From<Dataset> for Zengin {
+    fn from(dataset: Dataset) -> Self {
+        let banks = dataset
+            .banks
+            .into_iter()
+            .map(|bank| {
+                let branches = bank
+                    .branches
+                    .into_iter()
+                    .map(|branch| (branch.code.clone(), branch.into()))
+                    .collect();
+                let bank = crate::Bank {
+                    code: bank.code,
+                    name: bank.name,
+                    kana: bank.kana,
+                    hira: bank.hira,
+                    roma: bank.roma,
+                    branches,
+                    prefix_index: OnceLock::new(),
+                    ngram_index: OnceLock::new(),
+                };
+                (bank.code.clone(), bank)
+            })
+            .collect();
+        Zengin::from_banks(banks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+
+    #[test]
+    fn test_dataset_roundtrip() {
+        let zengin = crate::fixture();
+        let dataset = Dataset::from(&zengin);
+        assert_eq!(dataset.banks.len(), zengin.all_banks().len());
+        assert_eq!(dataset.banks[0].code, "0001");
+        let branches = &dataset.banks[0].branches;
+        assert!(branches.windows(2).all(|pair| pair[0].code < pair[1].code));
+
+        let decoded = Dataset::decode(&*dataset.encode_to_vec()).unwrap();
+        assert_eq!(decoded, dataset);
+        let decoded = Zengin::from(decoded);
+        assert_eq!(decoded.to_json().unwrap(), zengin.to_json().unwrap());
+    }
+
+    /// The type, name and tag of a field of `proto/zengin.proto`.
+    type ProtoField = (String, String, u32);
+
+    /// Returns the fields of the messages of `proto/zengin.proto`, with the
+    /// name of each message.
+    fn proto_fields() -> Vec<(String, Vec<ProtoField>)> {
+        let mut messages = Vec::new();
+        let mut current: Option<(String, Vec<_>)> = None;
+        for line in include_str!("../proto/zengin.proto").lines() {
+            let line = line.split("//").next().unwrap().trim();
+            if let Some(name) = line.strip_prefix("message ") {
+                let name = name.trim_end_matches('{').trim().to_string();
+                current = Some((name, Vec::new()));
+            } else if line == "}" {
+                messages.extend(current.take());
+            } else if let (Some((_, fields)), Some((field, tag))) =
+                (&mut current, line.split_once('='))
+            {
+                let field: Vec<_> = field.split_whitespace().collect();
+                let [.., ty, name] = field[..] else {
+                    panic!("malformed field {line:?}");
+                };
+                let tag = tag.trim().trim_end_matches(';').parse().unwrap();
+                fields.push((ty.to_string(), name.to_string(), tag));
+            }
+        }
+        messages
+    }
+
+    fn read_varint(bytes: &mut &[u8]) -> u64 {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let (&byte, rest) = bytes.split_first().unwrap();
+            *bytes = rest;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        value
+    }
+
+    /// Splits an encoded message into the tag, wire type and payload of each
+    /// field.
+    fn wire_fields(mut bytes: &[u8]) -> Vec<(u32, u8, Vec<u8>)> {
+        let mut fields = Vec::new();
+        while !bytes.is_empty() {
+            let key = read_varint(&mut bytes);
+            let (tag, wire_type) = ((key >> 3) as u32, (key & 7) as u8);
+            let payload = match wire_type {
+                0 => read_varint(&mut bytes).to_le_bytes().to_vec(),
+                2 => {
+                    let len = read_varint(&mut bytes) as usize;
+                    let (payload, rest) = bytes.split_at(len);
+                    bytes = rest;
+                    payload.to_vec()
+                }
+                _ => panic!("unexpected wire type {wire_type}"),
+            };
+            fields.push((tag, wire_type, payload));
+        }
+        fields
+    }
+
+    #[test]
+    fn test_messages_match_proto() {
+        // Every field is set once, and string fields hold their own name, so
+        // a field written under the tag of another one is caught.
+        let branch = Branch {
+            code: "code".into(),
+            name: "name".into(),
+            kana: "kana".into(),
+            hira: "hira".into(),
+            roma: "roma".into(),
+        };
+        let bank = Bank {
+            code: "code".into(),
+            name: "name".into(),
+            kana: "kana".into(),
+            hira: "hira".into(),
+            roma: "roma".into(),
+            branches: vec![Branch::default()],
+        };
+        let samples = [
+            ("Branch", branch.encode_to_vec()),
+            ("Bank", bank.encode_to_vec()),
+            (
+                "Dataset",
+                Dataset {
+                    banks: vec![Bank::default()],
+                }
+                .encode_to_vec(),
+            ),
+            (
+                "LookupBankRequest",
+                LookupBankRequest {
+                    code: "code".into(),
+                    include_branches: true,
+                }
+                .encode_to_vec(),
+            ),
+            (
+                "LookupBranchRequest",
+                LookupBranchRequest {
+                    bank_code: "bank_code".into(),
+                    code: "code".into(),
+                }
+                .encode_to_vec(),
+            ),
+            (
+                "SearchBanksRequest",
+                SearchBanksRequest {
+                    query: "query".into(),
+                    limit: 1,
+                }
+                .encode_to_vec(),
+            ),
+            (
+                "SearchBanksResponse",
+                SearchBanksResponse {
+                    banks: vec![Bank::default()],
+                }
+                .encode_to_vec(),
+            ),
+            (
+                "StreamAllBanksRequest",
+                StreamAllBanksRequest {
+                    include_branches: true,
+                }
+                .encode_to_vec(),
+            ),
+        ];
+
+        let messages = proto_fields();
+        let names: Vec<_> = messages.iter().map(|(name, _)| name.as_str()).collect();
+        let sampled: Vec<_> = samples.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, sampled);
+        for ((message, fields), (_, bytes)) in messages.iter().zip(&samples) {
+            let wire = wire_fields(bytes);
+            assert_eq!(wire.len(), fields.len(), "fields of {message}");
+            for (ty, name, tag) in fields {
+                let (_, wire_type, payload) = wire
+                    .iter()
+                    .find(|(wire_tag, ..)| wire_tag == tag)
+                    .unwrap_or_else(|| panic!("{message}.{name} is not tag {tag}"));
+                match ty.as_str() {
+                    "string" => assert_eq!(payload, name.as_bytes(), "{message}.{name}"),
+                    "bool" | "uint32" => assert_eq!(*wire_type, 0, "{message}.{name}"),
+                    _ => assert_eq!(*wire_type, 2, "{message}.{name}"),
+                }
+            }
+        }
+    }
+}
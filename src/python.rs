@@ -0,0 +1,296 @@
+//! PyO3 bindings of the dataset, built as the `zengin` Python package.
+//!
+//! The package has [`PyZengin`], [`PyBank`], and [`PyBranch`] classes, named
+//! `Zengin`, `Bank`, and `Branch` in Python, and raises `zengin.ZenginError`
+//! for errors of this crate:
+//!
+//! ```python
+//! import zengin
+//! z = zengin.Zengin()
+//! bank = z.get_bank("0001")
+//! print(bank.name, bank.get_branch("110").name)
+//! for bank in z.search_banks("みずほ", limit=5):
+//!     print(bank.code, bank.name)
+//! ```
+//!
+//! Build and install the package with `maturin develop --release`, which
+//! reads the features to enable from `pyproject.toml`.
+
+use crate::{Bank, Branch, Record, Zengin};
+use pyo3::{create_exception, exceptions::PyException, prelude::*};
+use std::sync::Arc;
+
+create_exception!(zengin, ZenginError, PyException, "An error of the dataset.");
+
+impl From<crate::ZenginError> for PyErr {
+    fn from(e: crate::ZenginError) -> Self {
+        ZenginError::new_err(e.to_string())
+    }
+}
+
+/// Returns at most `limit` ranked results, or all of them if `limit` is
+/// `None`.
+fn take_ranked<T>(ranked: Vec<(f64, T)>, limit: Option<usize>) -> impl Iterator<Item = T> {
+    ranked
+        .into_iter()
+        .take(limit.unwrap_or(usize::MAX))
+        .map(|(_, record)| record)
+}
+
+/// The dataset, as the `zengin.Zengin` class.
+#[pyclass(name = "Zengin", module = "zengin", frozen)]
+pub struct PyZengin {
+    zengin: Arc<Zengin>,
+}
+
+impl PyZengin {
+    fn bank(&self, bank: &Bank) -> PyBank {
+        PyBank {
+            zengin: Arc::clone(&self.zengin),
+            code: bank.code().to_string(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyZengin {
+    /// Loads the embedded dataset.
+    #[new]
+    fn new() -> PyResult<Self> {
+        Ok(PyZengin {
+            zengin: Arc::new(Zengin::new()?),
+        })
+    }
+
+    /// Returns a bank by its code, or `None`.
+    fn get_bank(&self, code: &str) -> Option<PyBank> {
+        self.zengin.get_bank(code).map(|bank| self.bank(bank))
+    }
+
+    /// Returns a branch by the code of its bank and its own code, or `None`.
+    fn get_branch(&self, bank_code: &str, code: &str) -> Option<PyBranch> {
+        self.get_bank(bank_code)?.get_branch(code)
+    }
+
+    /// Returns every bank in code order.
+    fn banks(&self) -> Vec<PyBank> {
+        self.zengin
+            .iter()
+            .map(|(_, bank)| self.bank(bank))
+            .collect()
+    }
+
+    /// Returns the banks whose names match a regular expression.
+    fn find_banks_by_name(&self, pattern: &str) -> PyResult<Vec<PyBank>> {
+        let banks = self.zengin.find_banks_by_name(pattern)?;
+        Ok(banks.into_iter().map(|bank| self.bank(bank)).collect())
+    }
+
+    /// Returns the banks matching a query in any of their names, most
+    /// relevant first.
+    #[pyo3(signature = (query, limit = None))]
+    fn search_banks(&self, query: &str, limit: Option<usize>) -> Vec<PyBank> {
+        take_ranked(self.zengin.find_banks_ranked(query), limit)
+            .map(|bank| self.bank(bank))
+            .collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.zengin.all_banks().len()
+    }
+}
+
+/// A bank, as the `zengin.Bank` class.
+#[pyclass(name = "Bank", module = "zengin", frozen)]
+pub struct PyBank {
+    zengin: Arc<Zengin>,
+    code: String,
+}
+
+impl PyBank {
+    fn inner(&self) -> &Bank {
+        // A `PyBank` is only created for a bank of its dataset.
+        &self.zengin[self.code.as_str()]
+    }
+
+    fn branch(&self, branch: &Branch) -> PyBranch {
+        PyBranch {
+            zengin: Arc::clone(&self.zengin),
+            bank_code: self.code.clone(),
+            code: branch.code().to_string(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyBank {
+    #[getter]
+    fn code(&self) -> &str {
+        &self.code
+    }
+
+    #[getter]
+    fn name(&self) -> &str {
+        &self.inner().name
+    }
+
+    #[getter]
+    fn kana(&self) -> &str {
+        &self.inner().kana
+    }
+
+    #[getter]
+    fn hira(&self) -> &str {
+        &self.inner().hira
+    }
+
+    #[getter]
+    fn roma(&self) -> &str {
+        &self.inner().roma
+    }
+
+    /// Returns a branch by its code, or `None`.
+    fn get_branch(&self, code: &str) -> Option<PyBranch> {
+        self.inner()
+            .get_branch(code)
+            .map(|branch| self.branch(branch))
+    }
+
+    /// Returns every branch in code order.
+    fn branches(&self) -> Vec<PyBranch> {
+        self.inner()
+            .iter()
+            .map(|(_, branch)| self.branch(branch))
+            .collect()
+    }
+
+    /// Returns the branches whose names match a regular expression.
+    fn find_branches_by_name(&self, pattern: &str) -> PyResult<Vec<PyBranch>> {
+        let branches = self.inner().find_branches_by_name(pattern)?;
+        Ok(branches
+            .into_iter()
+            .map(|branch| self.branch(branch))
+            .collect())
+    }
+
+    /// Returns the branches matching a query in any of their names, most
+    /// relevant first.
+    #[pyo3(signature = (query, limit = None))]
+    fn search_branches(&self, query: &str, limit: Option<usize>) -> Vec<PyBranch> {
+        take_ranked(self.inner().find_branches_ranked(query), limit)
+            .map(|branch| self.branch(branch))
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Bank(code={:?}, name={:?})", self.code, self.name())
+    }
+}
+
+/// A branch, as the `zengin.Branch` class.
+#[pyclass(name = "Branch", module = "zengin", frozen)]
+pub struct PyBranch {
+    zengin: Arc<Zengin>,
+    bank_code: String,
+    code: String,
+}
+
+impl PyBranch {
+    fn inner(&self) -> &Branch {
+        // A `PyBranch` is only created for a branch of its dataset.
+        &self.zengin[self.bank_code.as_str()][self.code.as_str()]
+    }
+}
+
+#[pymethods]
+impl PyBranch {
+    #[getter]
+    fn bank_code(&self) -> &str {
+        &self.bank_code
+    }
+
+    #[getter]
+    fn code(&self) -> &str {
+        &self.code
+    }
+
+    #[getter]
+    fn name(&self) -> &str {
+        &self.inner().name
+    }
+
+    #[getter]
+    fn kana(&self) -> &str {
+        &self.inner().kana
+    }
+
+    #[getter]
+    fn hira(&self) -> &str {
+        &self.inner().hira
+    }
+
+    #[getter]
+    fn roma(&self) -> &str {
+        &self.inner().roma
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Branch(bank_code={:?}, code={:?}, name={:?})",
+            self.bank_code,
+            self.code,
+            self.name()
+        )
+    }
+}
+
+/// The `zengin` Python module.
+#[pymodule]
+fn zengin(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyZengin>()?;
+    m.add_class::<PyBank>()?;
+    m.add_class::<PyBranch>()?;
+    m.add("ZenginError", m.py().get_type::<ZenginError>())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_python() {
+        pyo3::append_to_inittab!(zengin);
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let locals = pyo3::types::PyDict::new(py);
+            let z = PyZengin {
+                zengin: Arc::new(crate::fixture()),
+            };
+            locals.set_item("z", Py::new(py, z).unwrap()).unwrap();
+            py.run(
+                cr#"
+import zengin
+assert isinstance(z, zengin.Zengin)
+bank = z.get_bank("1")
+assert (bank.code, bank.name, bank.roma) == ("0001", "みずほ", "mizuho")
+branch = z.get_branch("0001", "110")
+assert (branch.bank_code, branch.name) == ("0001", "新宿")
+assert bank.get_branch("110").kana == "シンジユク"
+assert z.get_bank("9999") is None
+assert [b.code for b in z.search_banks("みずほ", limit=1)] == ["0001"]
+assert bank.search_branches("新宿")[0].code == "110"
+assert repr(bank) == 'Bank(code="0001", name="みずほ")'
+try:
+    z.find_banks_by_name("(")
+    raise AssertionError
+except zengin.ZenginError:
+    pass
+"#,
+                None,
+                Some(&locals),
+            )
+            .unwrap();
+        });
+    }
+}
@@ -0,0 +1,260 @@
+use crate::{Bank, Branch, Field, Record, Zengin, ZenginError};
+use regex::{Regex, RegexBuilder};
+use std::collections::HashMap;
+
+/// A composable query over banks or branches.
+///
+/// Conditions added one after another must all hold. Calling [`Query::or`]
+/// starts a new group of conditions, and a record matches the query if all
+/// conditions of any group hold, so
+/// `a.and().b().or().c()` reads as `(a AND b) OR c`.
+///
+/// Romanized names are compared regardless of case, like the other searches
+/// of this crate.
+///
+/// # Examples
+/// ```
+/// use zengin::Zengin;
+/// let zengin = Zengin::new().unwrap();
+/// let banks = zengin
+///     .query()
+///     .name_contains("みずほ")
+///     .or()
+///     .roma_matches("^mizuho")
+///     .execute()
+///     .unwrap();
+/// ```
+#[must_use]
+pub struct Query<'a, T> {
+    records: &'a HashMap<String, T>,
+    groups: Vec<Vec<Condition>>,
+    error: Option<ZenginError>,
+}
+
+enum Condition {
+    Equals(Field, String),
+    Contains(Field, String),
+    Matches(Field, Regex),
+}
+
+impl Condition {
+    fn holds(&self, record: &impl Record) -> bool {
+        match self {
+            Condition::Equals(Field::Roma, value) => {
+                record.field(Field::Roma).eq_ignore_ascii_case(value)
+            }
+            Condition::Equals(field, value) => record.field(*field) == value,
+            Condition::Contains(Field::Roma, value) => record
+                .field(Field::Roma)
+                .to_ascii_lowercase()
+                .contains(&value.to_ascii_lowercase()),
+            Condition::Contains(field, value) => record.field(*field).contains(value.as_str()),
+            Condition::Matches(field, re) => re.is_match(record.field(*field)),
+        }
+    }
+}
+
+impl<'a, T: Record> Query<'a, T> {
+    fn new(records: &'a HashMap<String, T>) -> Self {
+        Query {
+            records,
+            groups: vec![vec![]],
+            error: None,
+        }
+    }
+
+    fn push(mut self, condition: Condition) -> Self {
+        self.groups
+            .last_mut()
+            .expect("a query always has a group")
+            .push(condition);
+        self
+    }
+
+    /// Requires the following conditions to hold as well. This is the default
+    /// between conditions, and only serves readability.
+    pub fn and(self) -> Self {
+        self
+    }
+
+    /// Starts a new group of conditions, which matches independently of the
+    /// previous ones.
+    pub fn or(mut self) -> Self {
+        self.groups.push(vec![]);
+        self
+    }
+
+    /// Requires `field` to be equal to `value`.
+    pub fn equals(self, field: Field, value: &str) -> Self {
+        self.push(Condition::Equals(field, value.to_string()))
+    }
+
+    /// Requires `field` to contain `value`.
+    pub fn contains(self, field: Field, value: &str) -> Self {
+        self.push(Condition::Contains(field, value.to_string()))
+    }
+
+    /// Requires `field` to match the regular expression `pattern`.
+    ///
+    /// An invalid pattern is reported by [`Query::execute`].
+    pub fn matches(mut self, field: Field, pattern: &str) -> Self {
+        let re = RegexBuilder::new(pattern)
+            .case_insensitive(field == Field::Roma)
+            .build();
+        match re {
+            Ok(re) => self.push(Condition::Matches(field, re)),
+            Err(e) => {
+                self.error.get_or_insert(e.into());
+                self
+            }
+        }
+    }
+
+    /// Requires the code to be equal to `code`.
+    pub fn code_equals(self, code: &str) -> Self {
+        self.equals(Field::Code, code)
+    }
+
+    /// Requires the name to contain `value`.
+    pub fn name_contains(self, value: &str) -> Self {
+        self.contains(Field::Name, value)
+    }
+
+    /// Requires the kana to contain `value`.
+    pub fn kana_contains(self, value: &str) -> Self {
+        self.contains(Field::Kana, value)
+    }
+
+    /// Requires the hiragana name to contain `value`.
+    pub fn hira_contains(self, value: &str) -> Self {
+        self.contains(Field::Hira, value)
+    }
+
+    /// Requires the romanized name to contain `value`.
+    pub fn roma_contains(self, value: &str) -> Self {
+        self.contains(Field::Roma, value)
+    }
+
+    /// Requires the name to match the regular expression `pattern`.
+    pub fn name_matches(self, pattern: &str) -> Self {
+        self.matches(Field::Name, pattern)
+    }
+
+    /// Requires the kana to match the regular expression `pattern`.
+    pub fn kana_matches(self, pattern: &str) -> Self {
+        self.matches(Field::Kana, pattern)
+    }
+
+    /// Requires the hiragana name to match the regular expression `pattern`.
+    pub fn hira_matches(self, pattern: &str) -> Self {
+        self.matches(Field::Hira, pattern)
+    }
+
+    /// Requires the romanized name to match the regular expression `pattern`.
+    pub fn roma_matches(self, pattern: &str) -> Self {
+        self.matches(Field::Roma, pattern)
+    }
+
+    /// Runs the query, returning the matching records in code order.
+    ///
+    /// A query without any conditions matches every record. Groups without
+    /// conditions, such as one left by a trailing [`Query::or`], are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the regular expression patterns is invalid.
+    pub fn execute(self) -> Result<Vec<&'a T>, ZenginError> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        // An empty group left by a stray `or()` would otherwise match everything.
+        let groups: Vec<_> = self.groups.iter().filter(|g| !g.is_empty()).collect();
+        let mut matched: Vec<_> = self
+            .records
+            .values()
+            .filter(|record| {
+                groups.is_empty()
+                    || groups
+                        .iter()
+                        .any(|group| group.iter().all(|condition| condition.holds(*record)))
+            })
+            .collect();
+        matched.sort_by(|a, b| a.code().cmp(b.code()));
+        Ok(matched)
+    }
+}
+
+impl Zengin {
+    /// Starts a composable query over all banks.
+    ///
+    /// See [`Query`] for how conditions combine.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let banks = zengin
+    ///     .query()
+    ///     .kana_contains("シンキン")
+    ///     .and()
+    ///     .roma_matches("^a")
+    ///     .execute()
+    ///     .unwrap();
+    /// ```
+    pub fn query(&self) -> Query<'_, Bank> {
+        Query::new(&self.banks)
+    }
+}
+
+impl Bank {
+    /// Starts a composable query over the branches of this bank.
+    ///
+    /// See [`Query`] for how conditions combine.
+    ///
+    /// # Examples
+    /// ```
+    /// let branches = bank
+    ///     .query()
+    ///     .name_contains("新宿")
+    ///     .or()
+    ///     .name_contains("渋谷")
+    ///     .execute()
+    ///     .unwrap();
+    /// ```
+    pub fn query(&self) -> Query<'_, Branch> {
+        Query::new(&self.branches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() {
+        let zengin = crate::fixture();
+        let codes =
+            |banks: Vec<&Bank>| banks.iter().map(|b| b.code.to_string()).collect::<Vec<_>>();
+
+        let banks = zengin
+            .query()
+            .name_contains("みずほ")
+            .and()
+            .roma_matches("SHINTAKU$")
+            .or()
+            .code_equals("0005")
+            .execute()
+            .unwrap();
+        assert_eq!(codes(banks), vec!["0005", "0289"]);
+
+        assert!(zengin.query().name_matches("(").execute().is_err());
+        assert_eq!(
+            codes(zengin.query().code_equals("0001").or().execute().unwrap()),
+            vec!["0001"]
+        );
+        assert_eq!(
+            zengin.query().execute().unwrap().len(),
+            zengin.all_banks().len()
+        );
+    }
+}
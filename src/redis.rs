@@ -0,0 +1,149 @@
+//! Seeding of Redis with a hash per bank and per branch of the dataset.
+
+use crate::{Record, Zengin};
+use redis::{ConnectionLike, Pipeline, RedisResult};
+
+/// The number of hashes written per pipeline.
+const HASHES_PER_PIPELINE: usize = 1000;
+
+/// Returns the fields of a bank or branch hash.
+fn fields(record: &impl Record) -> [(&'static str, &str); 5] {
+    let [name, kana, hira, roma] = record.names();
+    [
+        ("code", record.code()),
+        ("name", name),
+        ("kana", kana),
+        ("hira", hira),
+        ("roma", roma),
+    ]
+}
+
+impl Zengin {
+    /// Returns the pipelines that [`seed_redis`](Zengin::seed_redis) runs,
+    /// for running them over an async connection with `exec_async`, which
+    /// needs an async feature of redis such as `tokio-comp` enabled by the
+    /// application.
+    ///
+    /// Each pipeline is atomic and writes up to a thousand hashes.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+    /// let mut con = client.get_multiplexed_async_connection().await.unwrap();
+    /// for pipeline in Zengin::new().unwrap().redis_pipelines() {
+    ///     pipeline.exec_async(&mut con).await.unwrap();
+    /// }
+    /// ```
+    pub fn redis_pipelines(&self) -> Vec<Pipeline> {
+        let mut hashes: Vec<(String, Vec<(&str, &str)>)> = vec![];
+        for (code, bank) in self {
+            hashes.push((format!("zengin:bank:{code}"), fields(bank).to_vec()));
+            for (branch_code, branch) in bank {
+                let mut hash = vec![("bank_code", code)];
+                hash.extend(fields(branch));
+                hashes.push((format!("zengin:branch:{code}:{branch_code}"), hash));
+            }
+        }
+        hashes
+            .chunks(HASHES_PER_PIPELINE)
+            .map(|chunk| {
+                let mut pipeline = redis::pipe();
+                pipeline.atomic();
+                for (key, fields) in chunk {
+                    pipeline.cmd("HSET").arg(key).arg(fields).ignore();
+                }
+                pipeline
+            })
+            .collect()
+    }
+
+    /// Writes the dataset into Redis hashes, for lookups from a cache shared
+    /// by services that do not embed this crate.
+    ///
+    /// Each bank is written to `zengin:bank:<code>`, such as
+    /// `zengin:bank:0001`, with `code`, `name`, `kana`, `hira`, and `roma`
+    /// fields. Each branch is written to `zengin:branch:<bank>:<code>`, such
+    /// as `zengin:branch:0001:001`, with a `bank_code` field and the same
+    /// fields. The fields of existing hashes are overwritten, and the hashes
+    /// of banks and branches that are no longer in the dataset are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `con` - The connection to the Redis server.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a command fails. The hashes are written in
+    /// batches of a thousand, each in a transaction, so the batches before
+    /// the failed one are kept.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+    /// let mut con = client.get_connection().unwrap();
+    /// Zengin::new().unwrap().seed_redis(&mut con).unwrap();
+    /// ```
+    pub fn seed_redis<C: ConnectionLike>(&self, con: &mut C) -> RedisResult<()> {
+        for pipeline in self.redis_pipelines() {
+            pipeline.exec(con)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use redis::Arg;
+
+    #[test]
+    fn test_redis_pipelines() {
+        let zengin = crate::fixture();
+        let pipelines = zengin.redis_pipelines();
+        let commands: Vec<Vec<String>> = pipelines[0]
+            .cmd_iter()
+            .map(|cmd| {
+                cmd.args_iter()
+                    .map(|arg| match arg {
+                        Arg::Simple(arg) => String::from_utf8(arg.to_vec()).unwrap(),
+                        Arg::Cursor => unreachable!(),
+                    })
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            commands[0],
+            [
+                "HSET",
+                "zengin:bank:0001",
+                "code",
+                "0001",
+                "name",
+                "みずほ",
+                "kana",
+                "ミズホ",
+                "hira",
+                "みずほ",
+                "roma",
+                "mizuho"
+            ]
+        );
+        assert_eq!(
+            commands[1][..6],
+            [
+                "HSET",
+                "zengin:branch:0001:001",
+                "bank_code",
+                "0001",
+                "code",
+                "001"
+            ]
+        );
+        let hashes: usize = pipelines.iter().map(|p| p.cmd_iter().count()).sum();
+        let branches: usize = zengin.iter().map(|(_, bank)| bank.iter().count()).sum();
+        assert_eq!(hashes, zengin.all_banks().len() + branches);
+        assert_eq!(pipelines.len(), hashes.div_ceil(HASHES_PER_PIPELINE));
+    }
+}
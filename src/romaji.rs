@@ -0,0 +1,159 @@
+//! Transliteration of romaji into hiragana.
+//!
+//! Users who cannot type kana can still search for Japanese institutions by
+//! typing their names in Latin letters. [`to_hiragana`] accepts Hepburn
+//! (`shinjuku`, `chiba`), Kunrei-shiki (`sinzyuku`, `tiba`), and the usual
+//! IME spellings, so the query can be matched against the kana fields.
+
+/// Syllables of up to three letters, tried longest first.
+#[rustfmt::skip]
+const SYLLABLES: &[(&str, &str)] = &[
+    ("kya", "きゃ"), ("kyu", "きゅ"), ("kyo", "きょ"),
+    ("gya", "ぎゃ"), ("gyu", "ぎゅ"), ("gyo", "ぎょ"),
+    ("sha", "しゃ"), ("shi", "し"), ("shu", "しゅ"), ("she", "しぇ"), ("sho", "しょ"),
+    ("sya", "しゃ"), ("syu", "しゅ"), ("syo", "しょ"),
+    ("zya", "じゃ"), ("zyu", "じゅ"), ("zyo", "じょ"),
+    ("jya", "じゃ"), ("jyu", "じゅ"), ("jyo", "じょ"),
+    ("cha", "ちゃ"), ("chi", "ち"), ("chu", "ちゅ"), ("che", "ちぇ"), ("cho", "ちょ"),
+    ("tya", "ちゃ"), ("tyu", "ちゅ"), ("tyo", "ちょ"),
+    ("dya", "ぢゃ"), ("dyu", "ぢゅ"), ("dyo", "ぢょ"),
+    ("tsu", "つ"),
+    ("nya", "にゃ"), ("nyu", "にゅ"), ("nyo", "にょ"),
+    ("hya", "ひゃ"), ("hyu", "ひゅ"), ("hyo", "ひょ"),
+    ("bya", "びゃ"), ("byu", "びゅ"), ("byo", "びょ"),
+    ("pya", "ぴゃ"), ("pyu", "ぴゅ"), ("pyo", "ぴょ"),
+    ("mya", "みゃ"), ("myu", "みゅ"), ("myo", "みょ"),
+    ("rya", "りゃ"), ("ryu", "りゅ"), ("ryo", "りょ"),
+    ("ka", "か"), ("ki", "き"), ("ku", "く"), ("ke", "け"), ("ko", "こ"),
+    ("ga", "が"), ("gi", "ぎ"), ("gu", "ぐ"), ("ge", "げ"), ("go", "ご"),
+    ("sa", "さ"), ("si", "し"), ("su", "す"), ("se", "せ"), ("so", "そ"),
+    ("za", "ざ"), ("zi", "じ"), ("zu", "ず"), ("ze", "ぜ"), ("zo", "ぞ"),
+    ("ja", "じゃ"), ("ji", "じ"), ("ju", "じゅ"), ("je", "じぇ"), ("jo", "じょ"),
+    ("ta", "た"), ("ti", "ち"), ("tu", "つ"), ("te", "て"), ("to", "と"),
+    ("da", "だ"), ("di", "ぢ"), ("du", "づ"), ("de", "で"), ("do", "ど"),
+    ("na", "な"), ("ni", "に"), ("nu", "ぬ"), ("ne", "ね"), ("no", "の"),
+    ("ha", "は"), ("hi", "ひ"), ("hu", "ふ"), ("fu", "ふ"), ("he", "へ"), ("ho", "ほ"),
+    ("ba", "ば"), ("bi", "び"), ("bu", "ぶ"), ("be", "べ"), ("bo", "ぼ"),
+    ("pa", "ぱ"), ("pi", "ぴ"), ("pu", "ぷ"), ("pe", "ぺ"), ("po", "ぽ"),
+    ("ma", "ま"), ("mi", "み"), ("mu", "む"), ("me", "め"), ("mo", "も"),
+    ("ya", "や"), ("yu", "ゆ"), ("yo", "よ"),
+    ("ra", "ら"), ("ri", "り"), ("ru", "る"), ("re", "れ"), ("ro", "ろ"),
+    ("wa", "わ"), ("wo", "を"),
+    ("va", "ゔぁ"), ("vi", "ゔぃ"), ("vu", "ゔ"), ("ve", "ゔぇ"), ("vo", "ゔぉ"),
+    ("a", "あ"), ("i", "い"), ("u", "う"), ("e", "え"), ("o", "お"),
+    ("-", "ー"),
+];
+
+/// Transliterates romaji into hiragana.
+///
+/// Letters are matched case-insensitively. A doubled consonant such as the
+/// `kk` of `hokkaido` becomes `っ`, and `n` becomes `ん` unless a vowel or
+/// `y` follows; write `n'` to force `ん` before them. Vowels with a
+/// macron are spelled out, so `ō` becomes `おう`. Anything that is not romaji
+/// is kept as is.
+///
+/// # Examples
+/// ```
+/// use zengin::romaji::to_hiragana;
+/// assert_eq!(to_hiragana("mizuho"), "みずほ");
+/// assert_eq!(to_hiragana("Hokkaidō"), "ほっかいどう");
+/// assert_eq!(to_hiragana("shin'yō"), "しんよう");
+/// ```
+pub fn to_hiragana(romaji: &str) -> String {
+    let input: String = romaji
+        .chars()
+        .flat_map(|c| {
+            let spelled = match c.to_lowercase().next().unwrap_or(c) {
+                'ā' | 'â' => "aa",
+                'ī' | 'î' => "ii",
+                'ū' | 'û' => "uu",
+                'ē' | 'ê' => "ee",
+                'ō' | 'ô' => "ou",
+                _ => "",
+            };
+            if spelled.is_empty() {
+                c.to_lowercase().collect::<Vec<_>>()
+            } else {
+                spelled.chars().collect()
+            }
+        })
+        .collect();
+
+    let mut kana = String::with_capacity(input.len() * 2);
+    let mut rest = input.as_str();
+    while let Some(c) = rest.chars().next() {
+        let next = rest[c.len_utf8()..].chars().next();
+        if c == 'n' {
+            let after = rest[1..].chars().nth(1);
+            let len = match next {
+                Some('\'') => Some(2),
+                // `nn` before a vowel is `ん` followed by a syllable of the `n` row.
+                Some('n') if after.is_some_and(|a| "aiueoy".contains(a)) => Some(1),
+                Some('n') => Some(2),
+                Some(n) if "aiueoy".contains(n) => None,
+                _ => Some(1),
+            };
+            if let Some(len) = len {
+                kana.push('ん');
+                rest = &rest[len..];
+                continue;
+            }
+        }
+        if c.is_ascii_alphabetic() && !"aiueon".contains(c) && next == Some(c)
+            || c == 't' && next == Some('c')
+        {
+            kana.push('っ');
+            rest = &rest[1..];
+            continue;
+        }
+        match SYLLABLES
+            .iter()
+            .find(|(romaji, _)| rest.starts_with(romaji))
+        {
+            Some((romaji, syllable)) => {
+                kana.push_str(syllable);
+                rest = &rest[romaji.len()..];
+            }
+            None => {
+                kana.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    kana
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_hiragana() {
+        assert_eq!(to_hiragana("shinjuku"), "しんじゅく");
+        assert_eq!(to_hiragana("sinzyuku"), "しんじゅく");
+        assert_eq!(to_hiragana("MITSUI SUMITOMO"), "みつい すみとも");
+        assert_eq!(to_hiragana("matcha"), "まっちゃ");
+        assert_eq!(to_hiragana("kin'yuu"), "きんゆう");
+        assert_eq!(to_hiragana("konnichiha"), "こんにちは");
+        assert_eq!(to_hiragana("shinkinn"), "しんきん");
+        assert_eq!(to_hiragana("ginkou"), "ぎんこう");
+        assert_eq!(to_hiragana("tōkyō"), "とうきょう");
+        assert_eq!(to_hiragana("ufj"), "うfj");
+    }
+
+    #[test]
+    fn test_find_by_romaji() {
+        let zengin = crate::fixture();
+        let bank = zengin.get_bank("0001").unwrap();
+        let codes: Vec<_> = bank
+            .find_branches_normalized("shinjuku")
+            .iter()
+            .map(|branch| branch.code.to_string())
+            .collect();
+        assert_eq!(codes, vec!["110"]);
+        assert_eq!(
+            zengin.find_banks_ranked("Yuucho")[0].1.code.to_string(),
+            "9900"
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! diesel tables and models of the dataset, for applications that mirror it
+//! into their own database.
+//!
+//! The tables have the schema that [`Zengin::to_sql`] creates, so a migration
+//! can take its `CREATE TABLE` statements as they are.
+
+use crate::{Bank, Branch, Record, Zengin};
+use diesel::{
+    connection::Connection, insertable::Insertable, prelude::*, query_builder::InsertStatement,
+    query_dsl::methods::ExecuteDsl,
+};
+
+/// The number of rows per `INSERT` statement, which keeps the number of bind
+/// parameters within the limits of every backend.
+const ROWS_PER_INSERT: usize = 1000;
+
+diesel::table! {
+    /// The banks of the dataset.
+    banks (code) {
+        code -> Text,
+        name -> Text,
+        kana -> Text,
+        hira -> Text,
+        roma -> Text,
+    }
+}
+
+diesel::table! {
+    /// The branches of the dataset.
+    branches (bank_code, code) {
+        bank_code -> Text,
+        code -> Text,
+        name -> Text,
+        kana -> Text,
+        hira -> Text,
+        roma -> Text,
+    }
+}
+
+diesel::joinable!(branches -> banks (bank_code));
+diesel::allow_tables_to_appear_in_same_query!(banks, branches);
+
+/// A row of the `banks` table.
+#[derive(Debug, Clone, PartialEq, Eq, Queryable, Selectable, Insertable)]
+#[diesel(table_name = banks)]
+pub struct BankRow {
+    pub code: String,
+    pub name: String,
+    pub kana: String,
+    pub hira: String,
+    pub roma: String,
+}
+
+/// A row of the `branches` table.
+#[derive(Debug, Clone, PartialEq, Eq, Queryable, Selectable, Insertable)]
+#[diesel(table_name = branches)]
+pub struct BranchRow {
+    pub bank_code: String,
+    pub code: String,
+    pub name: String,
+    pub kana: String,
+    pub hira: String,
+    pub roma: String,
+}
+
+impl From<&Bank> for BankRow {
+    fn from(bank: &Bank) -> Self {
+        let [name, kana, hira, roma] = bank.names().map(str::to_string);
+        BankRow {
+            code: bank.code().to_string(),
+            name,
+            kana,
+            hira,
+            roma,
+        }
+    }
+}
+
+impl BranchRow {
+    /// Returns the row of a branch of a bank.
+    pub fn new(bank: &Bank, branch: &Branch) -> Self {
+        let [name, kana, hira, roma] = branch.names().map(str::to_string);
+        BranchRow {
+            bank_code: bank.code().to_string(),
+            code: branch.code().to_string(),
+            name,
+            kana,
+            hira,
+            roma,
+        }
+    }
+}
+
+/// Inserts every bank and branch of a dataset into the `banks` and
+/// `branches` tables, in a single transaction.
+///
+/// The tables must exist and must not hold any of the rows yet; empty them
+/// first to refresh a mirror of an older dataset.
+///
+/// # Arguments
+///
+/// * `conn` - A connection of any backend with the tables.
+/// * `zengin` - The dataset to insert.
+///
+/// # Errors
+///
+/// Returns an error if an insert fails, in which case nothing is inserted.
+///
+/// # Examples
+/// ```
+/// use diesel::{pg::PgConnection, Connection};
+/// use zengin::{schema, Zengin};
+/// let mut conn = PgConnection::establish("postgres://localhost/app").unwrap();
+/// let zengin = Zengin::new().unwrap();
+/// let rows = schema::seed(&mut conn, &zengin).unwrap();
+/// println!("{rows} rows inserted");
+/// ```
+pub fn seed<C>(conn: &mut C, zengin: &Zengin) -> QueryResult<usize>
+where
+    C: Connection,
+    InsertStatement<banks::table, <Vec<BankRow> as Insertable<banks::table>>::Values>:
+        ExecuteDsl<C>,
+    InsertStatement<branches::table, <Vec<BranchRow> as Insertable<branches::table>>::Values>:
+        ExecuteDsl<C>,
+{
+    let banks: Vec<_> = zengin.iter().map(|(_, bank)| BankRow::from(bank)).collect();
+    let branches: Vec<_> = zengin
+        .iter()
+        .flat_map(|(_, bank)| {
+            bank.iter()
+                .map(move |(_, branch)| BranchRow::new(bank, branch))
+        })
+        .collect();
+    conn.transaction(|conn| {
+        let mut inserted = 0;
+        for chunk in banks.chunks(ROWS_PER_INSERT) {
+            inserted += diesel::insert_into(banks::table)
+                .values(chunk.to_vec())
+                .execute(conn)?;
+        }
+        for chunk in branches.chunks(ROWS_PER_INSERT) {
+            inserted += diesel::insert_into(branches::table)
+                .values(chunk.to_vec())
+                .execute(conn)?;
+        }
+        Ok(inserted)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{create_tables, SqlDialect};
+    use diesel::{connection::SimpleConnection, sqlite::SqliteConnection};
+
+    #[test]
+    fn test_seed() {
+        let zengin = crate::fixture();
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.batch_execute(&create_tables(SqlDialect::Sqlite))
+            .unwrap();
+        let inserted = seed(&mut conn, &zengin).unwrap();
+        let branch_count: usize = zengin.iter().map(|(_, bank)| bank.iter().len()).sum();
+        assert_eq!(inserted, zengin.all_banks().len() + branch_count);
+
+        let (bank, branch): (BankRow, BranchRow) = branches::table
+            .inner_join(banks::table)
+            .filter(branches::bank_code.eq("0001"))
+            .filter(branches::code.eq("110"))
+            .select((BankRow::as_select(), BranchRow::as_select()))
+            .first(&mut conn)
+            .unwrap();
+        assert_eq!(bank, BankRow::from(&zengin["0001"]));
+        assert_eq!(branch.name, "新宿");
+        assert!(seed(&mut conn, &zengin).is_err());
+    }
+}
@@ -0,0 +1,1292 @@
+use crate::{
+    collation::gojuon_cmp, index::NgramIndex, normalize::normalize, Bank, Branch, Zengin,
+    ZenginError,
+};
+use aho_corasick::AhoCorasick;
+use regex::{Regex, RegexBuilder};
+use regex_syntax::ast::{self, Ast, ClassSetItem};
+use std::{cmp::Ordering, collections::HashMap, convert::Infallible, ops::Range, sync::OnceLock};
+
+/// The minimum similarity for a record to be returned by the fuzzy searches.
+const FUZZY_THRESHOLD: f64 = 0.75;
+
+/// A field of a bank or branch that can be searched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Field {
+    Code,
+    Name,
+    Kana,
+    Hira,
+    Roma,
+}
+
+/// The order of the results of [`Zengin::find_banks`] and [`Bank::find_branches`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SortOrder {
+    /// Sort by code.
+    #[default]
+    Code,
+    /// Sort by name.
+    Name,
+    /// Sort by kana, in gojūon order.
+    Kana,
+}
+
+/// Options for [`Zengin::find_banks`] and [`Bank::find_branches`].
+///
+/// Construct it with struct update syntax, so that options added in the
+/// future take their default values:
+///
+/// ```
+/// use zengin::{Field, SearchOptions};
+/// let options = SearchOptions {
+///     limit: Some(10),
+///     fields: vec![Field::Name, Field::Kana],
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// The maximum number of results to return. `None` returns every match.
+    pub limit: Option<usize>,
+    /// The number of sorted results to skip before returning any, for paging
+    /// through the results together with `limit`.
+    pub offset: usize,
+    /// Whether the pattern matches letters regardless of case.
+    pub case_insensitive: bool,
+    /// Whether the literals of the pattern and the fields are passed through
+    /// [`normalize`](crate::normalize::normalize) before matching, so that
+    /// differences of script and character width are ignored. Escapes and
+    /// classes such as `\D` keep their meaning.
+    pub normalize_kana: bool,
+    /// The fields to match the pattern against. A record matches if any of
+    /// them does. Defaults to the name, kana, hira, and roma fields.
+    pub fields: Vec<Field>,
+    /// The order of the results.
+    pub sort: SortOrder,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            limit: None,
+            offset: 0,
+            case_insensitive: false,
+            normalize_kana: false,
+            fields: vec![Field::Name, Field::Kana, Field::Hira, Field::Roma],
+            sort: SortOrder::default(),
+        }
+    }
+}
+
+/// The fields shared by banks and branches, for code that works with both.
+///
+/// # Examples
+/// ```
+/// use zengin::{Field, Record};
+/// fn describe(record: &impl Record) -> String {
+///     format!("{} {}", record.code(), record.field(Field::Name))
+/// }
+/// ```
+pub trait Record {
+    /// Returns the code.
+    fn code(&self) -> &str;
+
+    /// Returns the name, kana, hira, and roma fields.
+    fn names(&self) -> [&str; 4];
+
+    /// Returns the given field.
+    fn field(&self, field: Field) -> &str {
+        let [name, kana, hira, roma] = self.names();
+        match field {
+            Field::Code => self.code(),
+            Field::Name => name,
+            Field::Kana => kana,
+            Field::Hira => hira,
+            Field::Roma => roma,
+        }
+    }
+}
+
+impl Record for Bank {
+    fn code(&self) -> &str {
+        &self.code
+    }
+
+    fn names(&self) -> [&str; 4] {
+        [&self.name, &self.kana, &self.hira, &self.roma]
+    }
+}
+
+impl Record for Branch {
+    fn code(&self) -> &str {
+        &self.code
+    }
+
+    fn names(&self) -> [&str; 4] {
+        [&self.name, &self.kana, &self.hira, &self.roma]
+    }
+}
+
+/// Returns the best Jaro-Winkler similarity between `query` and any of the
+/// name fields of a record. Romanized names are compared case-insensitively.
+fn fuzzy_score(query: &str, record: &impl Record) -> f64 {
+    let [name, kana, hira, roma] = record.names();
+    let roma_score = strsim::jaro_winkler(&query.to_ascii_lowercase(), roma);
+    [name, kana, hira]
+        .into_iter()
+        .map(|field| strsim::jaro_winkler(query, field))
+        .fold(roma_score, f64::max)
+}
+
+/// Orders scored records by descending score and then by code.
+fn sort_by_score<T: Record>(scored: &mut [(f64, &T)]) {
+    scored.sort_by(|(a, x), (b, y)| b.total_cmp(a).then_with(|| x.code().cmp(y.code())));
+}
+
+/// Scores `records` against `query`, keeping the ones above the threshold
+/// ordered by descending score and then by code.
+fn rank_fuzzy<'a, T: Record>(
+    query: &str,
+    records: impl Iterator<Item = &'a T>,
+) -> Vec<(f64, &'a T)> {
+    let mut ranked: Vec<_> = records
+        .map(|record| (fuzzy_score(query, record), record))
+        .filter(|(score, _)| *score >= FUZZY_THRESHOLD)
+        .collect();
+    sort_by_score(&mut ranked);
+    ranked
+}
+
+/// Scores how well a record matches `query`, which must already be normalized.
+///
+/// Every name field is normalized and scored by the best kind of match: 3 for
+/// an exact match, 2 plus the share of the field covered by the query for a
+/// prefix, 1 plus that share for a substring, and the Jaro-Winkler similarity
+/// for a fuzzy match. Returns `None` if no field matches at all.
+fn relevance_score(query: &str, record: &impl Record) -> Option<f64> {
+    let query_len = query.chars().count() as f64;
+    record
+        .names()
+        .into_iter()
+        .filter_map(|field| {
+            let field = normalize(field);
+            let coverage = query_len / field.chars().count().max(1) as f64;
+            if field == query {
+                Some(3.0)
+            } else if field.starts_with(query) {
+                Some(2.0 + coverage)
+            } else if field.contains(query) {
+                Some(1.0 + coverage)
+            } else {
+                let similarity = strsim::jaro_winkler(query, &field);
+                (similarity >= FUZZY_THRESHOLD).then_some(similarity)
+            }
+        })
+        .reduce(f64::max)
+}
+
+/// Returns the normalized forms of `query` to match against normalized
+/// fields. With the `romaji` feature, a query with Latin letters is also
+/// transliterated into kana.
+fn query_forms(query: &str) -> Vec<String> {
+    let normalized = normalize(query);
+    #[cfg(feature = "romaji")]
+    if query.bytes().any(|b| b.is_ascii_alphabetic()) {
+        let kana = normalize(&crate::romaji::to_hiragana(query));
+        if kana != normalized {
+            return vec![normalized, kana];
+        }
+    }
+    vec![normalized]
+}
+
+/// Scores `records` by relevance to `query`, keeping the matching ones ordered
+/// by descending score and then by code.
+fn rank_relevance<'a, T: Record>(
+    query: &str,
+    records: impl Iterator<Item = &'a T>,
+) -> Vec<(f64, &'a T)> {
+    let queries = query_forms(query);
+    if queries.iter().any(String::is_empty) {
+        return Vec::new();
+    }
+    let mut ranked: Vec<_> = records
+        .filter_map(|record| {
+            let score = queries
+                .iter()
+                .filter_map(|query| relevance_score(query, record))
+                .reduce(f64::max)?;
+            Some((score, record))
+        })
+        .collect();
+    sort_by_score(&mut ranked);
+    ranked
+}
+
+/// Returns the `n` records closest to `query`, which is either a code or a name.
+///
+/// Codes are compared digit by digit after restoring stripped leading zeros,
+/// with ties broken by numeric distance. Names are compared by fuzzy score.
+fn suggest<'a, T: Record>(
+    query: &str,
+    n: usize,
+    width: usize,
+    records: impl Iterator<Item = &'a T>,
+) -> Vec<&'a T> {
+    let query = query.trim();
+    if !query.is_empty() && query.len() <= width && query.bytes().all(|b| b.is_ascii_digit()) {
+        let query = format!("{query:0>width$}");
+        let target: u32 = query.parse().unwrap_or_default();
+        let mut candidates: Vec<_> = records
+            .map(|record| {
+                let code = record.code();
+                let mismatches = code
+                    .bytes()
+                    .zip(query.bytes())
+                    .filter(|(a, b)| a != b)
+                    .count();
+                let distance = code.parse::<u32>().map_or(u32::MAX, |c| c.abs_diff(target));
+                ((mismatches, distance, code.to_string()), record)
+            })
+            .collect();
+        candidates.sort_by(|(a, _), (b, _)| a.cmp(b));
+        return candidates
+            .into_iter()
+            .take(n)
+            .map(|(_, record)| record)
+            .collect();
+    }
+
+    let mut candidates: Vec<_> = records
+        .map(|record| (fuzzy_score(query, record), record))
+        .collect();
+    sort_by_score(&mut candidates);
+    candidates
+        .into_iter()
+        .take(n)
+        .map(|(_, record)| record)
+        .collect()
+}
+
+/// Returns the records where the normalized `query` is a substring of any of
+/// the normalized name fields, in code order.
+fn find_normalized<'a, T: Record>(query: &str, records: impl Iterator<Item = &'a T>) -> Vec<&'a T> {
+    let queries = query_forms(query);
+    let mut matched: Vec<_> = records
+        .filter(|record| {
+            record.names().into_iter().any(|field| {
+                let field = normalize(field);
+                queries.iter().any(|query| field.contains(query.as_str()))
+            })
+        })
+        .collect();
+    matched.sort_by(|a, b| a.code().cmp(b.code()));
+    matched
+}
+
+/// Returns the records where `needle` is a substring of any of the name
+/// fields, in code order. Romanized names are compared case-insensitively.
+fn find_containing<'a, T: Record>(
+    needle: &str,
+    records: impl Iterator<Item = &'a T>,
+) -> Vec<&'a T> {
+    let roma_needle = needle.to_ascii_lowercase();
+    let mut matched: Vec<_> = records
+        .filter(|record| {
+            let [name, kana, hira, roma] = record.names();
+            name.contains(needle)
+                || kana.contains(needle)
+                || hira.contains(needle)
+                || roma.to_ascii_lowercase().contains(&roma_needle)
+        })
+        .collect();
+    matched.sort_by(|a, b| a.code().cmp(b.code()));
+    matched
+}
+
+/// Like [`find_containing`], but narrows `records` down with the bigram index
+/// first, building the index on the first call.
+fn find_containing_indexed<'a, T: Record>(
+    needle: &str,
+    records: &'a HashMap<String, T>,
+    index: &OnceLock<NgramIndex>,
+) -> Vec<&'a T> {
+    let index =
+        index.get_or_init(|| NgramIndex::new(records.values().map(|r| (r.code(), r.names()))));
+    match index.candidates(needle) {
+        Some(codes) => find_containing(needle, codes.into_iter().map(|code| &records[code])),
+        None => find_containing(needle, records.values()),
+    }
+}
+
+/// Returns the records where any of `needles` is a substring of any of the
+/// name fields, in code order. ASCII letters are compared case-insensitively,
+/// so that romanized names match regardless of case.
+///
+/// All needles are compiled into a single Aho-Corasick automaton, so each
+/// field is scanned once no matter how many needles there are.
+fn find_matching_any<'a, T: Record>(
+    needles: &[&str],
+    records: impl Iterator<Item = &'a T>,
+) -> Result<Vec<&'a T>, ZenginError> {
+    if needles.is_empty() {
+        return Ok(Vec::new());
+    }
+    let automaton = AhoCorasick::builder()
+        .ascii_case_insensitive(true)
+        .build(needles)?;
+    let mut matched: Vec<_> = records
+        .filter(|record| {
+            record
+                .names()
+                .into_iter()
+                .any(|field| automaton.is_match(field))
+        })
+        .collect();
+    matched.sort_by(|a, b| a.code().cmp(b.code()));
+    Ok(matched)
+}
+
+/// Translates a shell-style pattern into an anchored regular expression, in
+/// which `*` matches any run of characters, `?` matches a single character,
+/// and every other character matches itself.
+fn glob_to_regex(glob: &str) -> String {
+    let mut pattern = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            _ => pattern.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    pattern.push('$');
+    pattern
+}
+
+/// Returns the records where any of the name fields matches `glob`, in code
+/// order. Romanized names are compared case-insensitively.
+fn find_glob<'a, T: Record>(
+    glob: &str,
+    records: impl Iterator<Item = &'a T>,
+) -> Result<Vec<&'a T>, ZenginError> {
+    let pattern = glob_to_regex(glob);
+    let re = Regex::new(&pattern)?;
+    let roma_re = RegexBuilder::new(&pattern).case_insensitive(true).build()?;
+    let mut matched: Vec<_> = records
+        .filter(|record| {
+            let [name, kana, hira, roma] = record.names();
+            re.is_match(name) || re.is_match(kana) || re.is_match(hira) || roma_re.is_match(roma)
+        })
+        .collect();
+    matched.sort_by(|a, b| a.code().cmp(b.code()));
+    Ok(matched)
+}
+
+/// Returns a normalized character of a pattern in regular expression syntax,
+/// as a hexadecimal escape unless it is alphanumeric, so that it keeps its
+/// meaning inside classes and in verbose mode.
+fn escape_normalized(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_string()
+            } else {
+                format!("\\x{{{:X}}}", c as u32)
+            }
+        })
+        .collect()
+}
+
+/// Collects the replacements of the literals of a pattern by their
+/// [`normalize`]d forms.
+#[derive(Default)]
+struct LiteralNormalizer {
+    replacements: Vec<(Range<usize>, String)>,
+    /// The end of the last literal outside classes and its character, which
+    /// a long vowel mark right after it is normalized by.
+    previous: Option<(usize, char)>,
+}
+
+impl LiteralNormalizer {
+    /// Replaces a literal inside a class, if it normalizes to a single
+    /// other character.
+    fn replace_in_class(&mut self, literal: &ast::Literal) -> Option<char> {
+        let mut chars = normalize(&literal.c.to_string())
+            .chars()
+            .collect::<Vec<_>>();
+        let c = chars.pop().filter(|_| chars.is_empty())?;
+        if c != literal.c {
+            let span = literal.span.start.offset..literal.span.end.offset;
+            self.replacements
+                .push((span, escape_normalized(&c.to_string())));
+        }
+        Some(c)
+    }
+}
+
+impl ast::Visitor for LiteralNormalizer {
+    type Output = Vec<(Range<usize>, String)>;
+    type Err = Infallible;
+
+    fn finish(self) -> Result<Self::Output, Infallible> {
+        Ok(self.replacements)
+    }
+
+    fn visit_pre(&mut self, ast: &Ast) -> Result<(), Infallible> {
+        let Ast::Literal(literal) = ast else {
+            return Ok(());
+        };
+        let span = literal.span.start.offset..literal.span.end.offset;
+        let text = match self.previous {
+            Some((end, previous)) if end == span.start => {
+                let prefix = normalize(&previous.to_string());
+                let both = normalize(&format!("{previous}{}", literal.c));
+                both[prefix.len()..].to_string()
+            }
+            _ => normalize(&literal.c.to_string()),
+        };
+        self.previous = Some((span.end, literal.c));
+        if text.chars().eq([literal.c]) {
+            return Ok(());
+        }
+        let escaped = escape_normalized(&text);
+        let replacement = match text.chars().count() {
+            1 => escaped,
+            // A repetition after the literal applies to all of it.
+            _ => format!("(?:{escaped})"),
+        };
+        self.replacements.push((span, replacement));
+        Ok(())
+    }
+
+    fn visit_class_set_item_pre(&mut self, item: &ClassSetItem) -> Result<(), Infallible> {
+        match item {
+            ClassSetItem::Literal(literal) => {
+                self.replace_in_class(literal);
+            }
+            ClassSetItem::Range(range) => {
+                let replacements = self.replacements.len();
+                match (
+                    self.replace_in_class(&range.start),
+                    self.replace_in_class(&range.end),
+                ) {
+                    (Some(start), Some(end)) if start <= end => {}
+                    _ => self.replacements.truncate(replacements),
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Passes the literals of a regular expression through [`normalize`], so
+/// that the pattern matches normalized text as it matches the original text.
+///
+/// Only literals change: escapes such as `\D` and `\W`, classes, anchors,
+/// and repetitions keep their meaning. A pattern that does not parse is
+/// returned as is, for [`RegexBuilder::build`] to report the error.
+fn normalize_pattern(pattern: &str) -> String {
+    let Ok(ast) = ast::parse::Parser::new().parse(pattern) else {
+        return pattern.to_string();
+    };
+    let Ok(mut replacements) = ast::visit(&ast, LiteralNormalizer::default());
+    replacements.sort_by_key(|(span, _)| span.start);
+    let mut normalized = String::with_capacity(pattern.len());
+    let mut end = 0;
+    for (span, replacement) in replacements {
+        normalized.push_str(&pattern[end..span.start]);
+        normalized.push_str(&replacement);
+        end = span.end;
+    }
+    normalized.push_str(&pattern[end..]);
+    normalized
+}
+
+/// Compiles the pattern of a search into a predicate on records.
+fn matcher<'a, T: Record>(
+    pattern: &str,
+    options: &'a SearchOptions,
+) -> Result<impl Fn(&T) -> bool + 'a, ZenginError> {
+    let pattern = if options.normalize_kana {
+        normalize_pattern(pattern)
+    } else {
+        pattern.to_string()
+    };
+    let re = RegexBuilder::new(&pattern)
+        .case_insensitive(options.case_insensitive)
+        .build()?;
+
+    Ok(move |record: &T| {
+        options.fields.iter().any(|&field| {
+            let value = record.field(field);
+            if options.normalize_kana {
+                re.is_match(&normalize(value))
+            } else {
+                re.is_match(value)
+            }
+        })
+    })
+}
+
+/// Orders two records by the sort order of a search, leaving ties to the
+/// caller.
+fn sort_cmp<T: Record>(sort: SortOrder, a: &T, b: &T) -> Ordering {
+    match sort {
+        SortOrder::Code => Ordering::Equal,
+        SortOrder::Name => a.field(Field::Name).cmp(b.field(Field::Name)),
+        SortOrder::Kana => gojuon_cmp(a.field(Field::Kana), b.field(Field::Kana)),
+    }
+}
+
+/// Sorts the matches of a search, and keeps the requested page of them.
+fn sort_page<T>(
+    matched: &mut Vec<T>,
+    options: &SearchOptions,
+    compare: impl Fn(&T, &T) -> Ordering,
+) {
+    let end = options
+        .limit
+        .map_or(matched.len(), |limit| options.offset.saturating_add(limit));
+    if end < matched.len() {
+        // Only the requested page has to be sorted, not every match.
+        matched.select_nth_unstable_by(end, &compare);
+        matched.truncate(end);
+    }
+    matched.sort_by(compare);
+    matched.drain(..options.offset.min(matched.len()));
+}
+
+fn find_with_options<'a, T: Record>(
+    pattern: &str,
+    options: &SearchOptions,
+    records: impl Iterator<Item = &'a T>,
+) -> Result<Vec<&'a T>, ZenginError> {
+    let is_match = matcher(pattern, options)?;
+    let mut matched: Vec<_> = records.filter(|&record| is_match(record)).collect();
+    sort_page(&mut matched, options, |a, b| {
+        sort_cmp(options.sort, *a, *b).then_with(|| a.code().cmp(b.code()))
+    });
+    Ok(matched)
+}
+
+impl Zengin {
+    /// Finds banks matching a query, ranked by relevance.
+    ///
+    /// This function compares the query with the name, kana, hiragana, and
+    /// romanized name of every bank, after passing all of them through
+    /// [`normalize`](crate::normalize::normalize). Each bank is scored by its
+    /// best kind of match:
+    ///
+    /// * an exact match scores 3,
+    /// * a prefix match scores between 2 and 3,
+    /// * a substring match scores between 1 and 2,
+    /// * a fuzzy match scores between 0.75 and 1.
+    ///
+    /// Within prefix and substring matches, shorter names score higher, so
+    /// `みずほ` ranks above `みずほ信託` for the query `みずほ`. Banks that do
+    /// not match at all are dropped, and the rest are returned with their
+    /// scores, most relevant first, with ties in code order.
+    ///
+    /// With the `romaji` feature, a query in Latin letters is also
+    /// transliterated into kana, so `yuucho` finds `ゆうちょ` although its
+    /// romanized name is spelled `yuuchiyo`.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A string slice that holds the query.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// for (score, bank) in zengin.find_banks_ranked("みずほ") {
+    ///     println!("Found bank: {} ({:.2})", bank.name, score);
+    /// }
+    /// ```
+    pub fn find_banks_ranked(&self, query: &str) -> Vec<(f64, &Bank)> {
+        rank_relevance(query, self.banks.values())
+    }
+
+    /// Finds banks whose names are similar to a query, tolerating typos.
+    ///
+    /// This function scores every bank by the Jaro-Winkler similarity between
+    /// the query and its name, kana, hiragana, and romanized name, keeping the
+    /// best of the four. Banks scoring below 0.75 are dropped, and the rest
+    /// are returned with their scores, most similar first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A string slice that holds the query.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// for (score, bank) in zengin.find_banks_fuzzy("みづほ") {
+    ///     println!("Found bank: {} ({:.2})", bank.name, score);
+    /// }
+    /// ```
+    pub fn find_banks_fuzzy(&self, query: &str) -> Vec<(f64, &Bank)> {
+        rank_fuzzy(query, self.banks.values())
+    }
+
+    /// Suggests the banks closest to a code or name that could not be found.
+    ///
+    /// If `code_or_name` consists of digits, banks are ranked by how many
+    /// digits of their code differ from it (after restoring stripped leading
+    /// zeros), then by numeric distance. Otherwise banks are ranked by the
+    /// similarity of their names, as in [`Zengin::find_banks_fuzzy`]. The `n`
+    /// closest banks are returned, closest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `code_or_name` - A string slice that holds the bank code or name.
+    /// * `n` - The maximum number of suggestions to return.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// if zengin.get_bank("0009").is_none() {
+    ///     if let Some(bank) = zengin.suggest_banks("0009", 1).first() {
+    ///         println!("0009 not found, did you mean {} {}?", bank.code, bank.name);
+    ///     }
+    /// }
+    /// ```
+    pub fn suggest_banks(&self, code_or_name: &str, n: usize) -> Vec<&Bank> {
+        suggest(code_or_name, n, 4, self.banks.values())
+    }
+
+    /// Finds banks containing a query, regardless of script and character width.
+    ///
+    /// Both the query and the name, kana, hiragana, and romanized name of each
+    /// bank are passed through [`normalize`], so `ミズホ` matches the hiragana
+    /// name `みずほ` and `三菱UFJ` matches `三菱ＵＦＪ`. Matching banks are
+    /// returned in code order.
+    ///
+    /// With the `romaji` feature, a query in Latin letters is also
+    /// transliterated into kana, so `toukyou` finds `トウキヨウ`.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A string slice that holds the text to search for.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// for bank in zengin.find_banks_normalized("三菱UFJ") {
+    ///     println!("Found bank: {}", bank.name);
+    /// }
+    /// ```
+    pub fn find_banks_normalized(&self, query: &str) -> Vec<&Bank> {
+        find_normalized(query, self.banks.values())
+    }
+
+    /// Finds banks whose name, kana, hiragana, or romanized name contains a string.
+    ///
+    /// Unlike the `find_banks_by_*` functions, the argument is matched as
+    /// plain text, so user input can be passed as is without escaping regular
+    /// expression metacharacters. Romanized names match regardless of case.
+    /// Matching banks are returned in code order.
+    ///
+    /// Needles of two or more characters are looked up in a bigram index,
+    /// which is built on the first call, so only banks containing every pair
+    /// of adjacent characters of the needle are checked.
+    ///
+    /// # Arguments
+    ///
+    /// * `needle` - A string slice that holds the text to search for.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// for bank in zengin.find_banks_containing("みずほ") {
+    ///     println!("Found bank: {}", bank.name);
+    /// }
+    /// ```
+    pub fn find_banks_containing(&self, needle: &str) -> Vec<&Bank> {
+        find_containing_indexed(needle, &self.banks, &self.ngram_index)
+    }
+
+    /// Finds banks whose name, kana, hiragana, or romanized name contains any of several strings.
+    ///
+    /// This works like [`Zengin::find_banks_containing`] with many needles at
+    /// once. The needles are searched for in a single pass over the banks, so
+    /// looking for dozens of institution names costs about as much as looking
+    /// for one. Matching banks are returned in code order.
+    ///
+    /// # Arguments
+    ///
+    /// * `needles` - The strings to search for. An empty slice matches no bank.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the needles are too large to compile.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// for bank in zengin.find_banks_matching_any(&["みずほ", "りそな"]).unwrap() {
+    ///     println!("Found bank: {}", bank.name);
+    /// }
+    /// ```
+    pub fn find_banks_matching_any(&self, needles: &[&str]) -> Result<Vec<&Bank>, ZenginError> {
+        find_matching_any(needles, self.banks.values())
+    }
+
+    /// Finds banks whose name, kana, hiragana, or romanized name matches a shell-style pattern.
+    ///
+    /// In the pattern, `*` matches any run of characters and `?` matches a
+    /// single character. Every other character matches itself, and the pattern
+    /// has to match the whole field. Romanized names match regardless of case.
+    /// Matching banks are returned in code order.
+    ///
+    /// # Arguments
+    ///
+    /// * `glob` - A string slice that holds the pattern, such as `みずほ*`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern is too large to compile.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// for bank in zengin.find_banks_glob("*信用金庫").unwrap() {
+    ///     println!("Found bank: {}", bank.name);
+    /// }
+    /// ```
+    pub fn find_banks_glob(&self, glob: &str) -> Result<Vec<&Bank>, ZenginError> {
+        find_glob(glob, self.banks.values())
+    }
+
+    /// Finds banks matching a regular expression pattern, as configured by `options`.
+    ///
+    /// This function matches the pattern against the fields selected in
+    /// `options`, and returns the matching banks sorted and paged as
+    /// requested. See [`SearchOptions`] for the available options.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - A string slice that holds the regular expression pattern.
+    /// * `options` - The options of the search.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regular expression pattern is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{SearchOptions, Zengin};
+    /// let zengin = Zengin::new().unwrap();
+    /// let options = SearchOptions {
+    ///     limit: Some(5),
+    ///     case_insensitive: true,
+    ///     normalize_kana: true,
+    ///     ..Default::default()
+    /// };
+    /// for bank in zengin.find_banks("^みずほ", &options).unwrap() {
+    ///     println!("Found bank: {}", bank.name);
+    /// }
+    /// ```
+    pub fn find_banks(
+        &self,
+        pattern: &str,
+        options: &SearchOptions,
+    ) -> Result<Vec<&Bank>, ZenginError> {
+        find_with_options(pattern, options, self.banks.values())
+    }
+
+    /// Finds the branches of every bank matching a regular expression
+    /// pattern, as configured by `options`.
+    ///
+    /// This works like [`Bank::find_branches`], compiling the pattern once
+    /// for all banks. Branches with equal sort keys are ordered by the code
+    /// of their bank, then by their own code.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - A string slice that holds the regular expression pattern.
+    /// * `options` - The options of the search.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regular expression pattern is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{SearchOptions, Zengin};
+    /// let zengin = Zengin::new().unwrap();
+    /// let options = SearchOptions {
+    ///     limit: Some(10),
+    ///     ..Default::default()
+    /// };
+    /// for (bank, branch) in zengin.find_branches("^新宿", &options).unwrap() {
+    ///     println!("Found branch: {} {}", bank.name, branch.name);
+    /// }
+    /// ```
+    pub fn find_branches(
+        &self,
+        pattern: &str,
+        options: &SearchOptions,
+    ) -> Result<Vec<(&Bank, &Branch)>, ZenginError> {
+        let is_match = matcher(pattern, options)?;
+        let mut matched: Vec<_> = self
+            .banks
+            .values()
+            .flat_map(|bank| {
+                bank.branches
+                    .values()
+                    .filter(|&branch| is_match(branch))
+                    .map(move |branch| (bank, branch))
+            })
+            .collect();
+        sort_page(&mut matched, options, |(a, x), (b, y)| {
+            sort_cmp(options.sort, *x, *y)
+                .then_with(|| (a.code(), x.code()).cmp(&(b.code(), y.code())))
+        });
+        Ok(matched)
+    }
+}
+
+impl Bank {
+    /// Finds branches matching a query, ranked by relevance.
+    ///
+    /// This works like [`Zengin::find_banks_ranked`], searching the branches
+    /// of this bank.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A string slice that holds the query.
+    ///
+    /// # Examples
+    /// ```
+    /// for (score, branch) in bank.find_branches_ranked("しんじゅく") {
+    ///     println!("Found branch: {} ({:.2})", branch.name, score);
+    /// }
+    /// ```
+    pub fn find_branches_ranked(&self, query: &str) -> Vec<(f64, &Branch)> {
+        rank_relevance(query, self.branches.values())
+    }
+
+    /// Finds branches whose names are similar to a query, tolerating typos.
+    ///
+    /// This function scores every branch by the Jaro-Winkler similarity
+    /// between the query and its name, kana, hiragana, and romanized name,
+    /// keeping the best of the four. Branches scoring below 0.75 are dropped,
+    /// and the rest are returned with their scores, most similar first.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A string slice that holds the query.
+    ///
+    /// # Examples
+    /// ```
+    /// for (score, branch) in bank.find_branches_fuzzy("しんじゅく") {
+    ///     println!("Found branch: {} ({:.2})", branch.name, score);
+    /// }
+    /// ```
+    pub fn find_branches_fuzzy(&self, query: &str) -> Vec<(f64, &Branch)> {
+        rank_fuzzy(query, self.branches.values())
+    }
+
+    /// Suggests the branches closest to a code or name that could not be found.
+    ///
+    /// This works like [`Zengin::suggest_banks`], comparing against the
+    /// three-digit branch codes or the branch names.
+    ///
+    /// # Arguments
+    ///
+    /// * `code_or_name` - A string slice that holds the branch code or name.
+    /// * `n` - The maximum number of suggestions to return.
+    ///
+    /// # Examples
+    /// ```
+    /// for branch in bank.suggest_branches("002", 3) {
+    ///     println!("Did you mean {} {}?", branch.code, branch.name);
+    /// }
+    /// ```
+    pub fn suggest_branches(&self, code_or_name: &str, n: usize) -> Vec<&Branch> {
+        suggest(code_or_name, n, 3, self.branches.values())
+    }
+
+    /// Finds branches containing a query, regardless of script and character width.
+    ///
+    /// This works like [`Zengin::find_banks_normalized`], matching against
+    /// the name fields of the branches of this bank.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A string slice that holds the text to search for.
+    ///
+    /// # Examples
+    /// ```
+    /// for branch in bank.find_branches_normalized("ﾄｳｷﾖｳ") {
+    ///     println!("Found branch: {}", branch.name);
+    /// }
+    /// ```
+    pub fn find_branches_normalized(&self, query: &str) -> Vec<&Branch> {
+        find_normalized(query, self.branches.values())
+    }
+
+    /// Finds branches whose name, kana, hiragana, or romanized name contains a string.
+    ///
+    /// This works like [`Zengin::find_banks_containing`], searching the
+    /// branches of this bank.
+    ///
+    /// # Arguments
+    ///
+    /// * `needle` - A string slice that holds the text to search for.
+    ///
+    /// # Examples
+    /// ```
+    /// for branch in bank.find_branches_containing("新宿") {
+    ///     println!("Found branch: {}", branch.name);
+    /// }
+    /// ```
+    pub fn find_branches_containing(&self, needle: &str) -> Vec<&Branch> {
+        find_containing_indexed(needle, &self.branches, &self.ngram_index)
+    }
+
+    /// Finds branches whose name, kana, hiragana, or romanized name contains any of several strings.
+    ///
+    /// This works like [`Zengin::find_banks_matching_any`], searching the
+    /// branches of this bank.
+    ///
+    /// # Arguments
+    ///
+    /// * `needles` - The strings to search for. An empty slice matches no branch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the needles are too large to compile.
+    ///
+    /// # Examples
+    /// ```
+    /// for branch in bank.find_branches_matching_any(&["新宿", "渋谷"]).unwrap() {
+    ///     println!("Found branch: {}", branch.name);
+    /// }
+    /// ```
+    pub fn find_branches_matching_any(
+        &self,
+        needles: &[&str],
+    ) -> Result<Vec<&Branch>, ZenginError> {
+        find_matching_any(needles, self.branches.values())
+    }
+
+    /// Finds branches whose name, kana, hiragana, or romanized name matches a shell-style pattern.
+    ///
+    /// This works like [`Zengin::find_banks_glob`], searching the branches of
+    /// this bank.
+    ///
+    /// # Arguments
+    ///
+    /// * `glob` - A string slice that holds the pattern, such as `*出張所`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern is too large to compile.
+    ///
+    /// # Examples
+    /// ```
+    /// for branch in bank.find_branches_glob("*出張所").unwrap() {
+    ///     println!("Found branch: {}", branch.name);
+    /// }
+    /// ```
+    pub fn find_branches_glob(&self, glob: &str) -> Result<Vec<&Branch>, ZenginError> {
+        find_glob(glob, self.branches.values())
+    }
+
+    /// Finds branches matching a regular expression pattern, as configured by `options`.
+    ///
+    /// This works like [`Zengin::find_banks`], searching the branches of this
+    /// bank.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - A string slice that holds the regular expression pattern.
+    /// * `options` - The options of the search.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regular expression pattern is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::{Field, SearchOptions};
+    /// let options = SearchOptions {
+    ///     fields: vec![Field::Code],
+    ///     ..Default::default()
+    /// };
+    /// let branches = bank.find_branches("^1", &options).unwrap();
+    /// ```
+    pub fn find_branches(
+        &self,
+        pattern: &str,
+        options: &SearchOptions,
+    ) -> Result<Vec<&Branch>, ZenginError> {
+        find_with_options(pattern, options, self.branches.values())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_banks_fuzzy() {
+        let zengin = crate::fixture();
+
+        let results = zengin.find_banks_fuzzy("みづほ");
+        assert_eq!(results[0].1.code, "0001");
+        assert!(results.windows(2).all(|w| w[0].0 >= w[1].0));
+
+        let results = zengin.find_banks_fuzzy("りそな銀行");
+        assert_eq!(results[0].1.code, "0010");
+
+        let results = zengin.find_banks_fuzzy("Mizuho");
+        assert_eq!(results[0].0, 1.0);
+        assert!(zengin.find_banks_fuzzy("xyz").is_empty());
+    }
+
+    #[test]
+    fn test_find_normalized() {
+        let zengin = crate::fixture();
+        let codes =
+            |banks: Vec<&Bank>| banks.iter().map(|b| b.code.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(codes(zengin.find_banks_normalized("三菱UFJ")), vec!["0005"]);
+        assert_eq!(codes(zengin.find_banks_normalized("ﾐｽﾞﾎｼﾝﾀｸ")), vec!["0289"]);
+        assert_eq!(
+            codes(zengin.find_banks_normalized("ミズホ")),
+            vec!["0001", "0289"]
+        );
+    }
+
+    #[test]
+    fn test_find_containing() {
+        let zengin = crate::fixture();
+        let codes =
+            |banks: Vec<&Bank>| banks.iter().map(|b| b.code.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            codes(zengin.find_banks_containing("みずほ")),
+            vec!["0001", "0289"]
+        );
+        assert_eq!(codes(zengin.find_banks_containing("ＵＦＪ")), vec!["0005"]);
+        assert_eq!(
+            codes(zengin.find_banks_containing("MIZUHO")),
+            vec!["0001", "0289"]
+        );
+        assert!(zengin.find_banks_containing("(").is_empty());
+    }
+
+    #[test]
+    fn test_find_matching_any() {
+        let zengin = crate::fixture();
+        let codes =
+            |banks: Vec<&Bank>| banks.iter().map(|b| b.code.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            codes(
+                zengin
+                    .find_banks_matching_any(&["りそな", "YUUCHIYO"])
+                    .unwrap()
+            ),
+            vec!["0010", "9900"]
+        );
+        assert_eq!(
+            codes(zengin.find_banks_matching_any(&["みずほ", "信託"]).unwrap()),
+            vec!["0001", "0289"]
+        );
+        assert!(zengin.find_banks_matching_any(&[]).unwrap().is_empty());
+
+        let bank = zengin.get_bank("0001").unwrap();
+        assert_eq!(
+            bank.find_branches_matching_any(&["新宿", "渋谷"])
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_find_glob() {
+        let zengin = crate::fixture();
+        let codes =
+            |banks: Vec<&Bank>| banks.iter().map(|b| b.code.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            codes(zengin.find_banks_glob("みずほ*").unwrap()),
+            vec!["0001", "0289"]
+        );
+        assert_eq!(
+            codes(zengin.find_banks_glob("みずほ").unwrap()),
+            vec!["0001"]
+        );
+        assert_eq!(
+            codes(zengin.find_banks_glob("*信託").unwrap()),
+            vec!["0289"]
+        );
+        assert_eq!(
+            codes(zengin.find_banks_glob("Mizuh?").unwrap()),
+            vec!["0001"]
+        );
+        assert!(zengin.find_banks_glob("みずほ.*").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_find_with_options() {
+        let zengin = crate::fixture();
+        let codes =
+            |banks: Vec<&Bank>| banks.iter().map(|b| b.code.to_string()).collect::<Vec<_>>();
+
+        let options = SearchOptions {
+            normalize_kana: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            codes(zengin.find_banks("^ミズホ", &options).unwrap()),
+            vec!["0001", "0289"]
+        );
+
+        let options = SearchOptions {
+            limit: Some(1),
+            case_insensitive: true,
+            fields: vec![Field::Roma],
+            sort: SortOrder::Name,
+            ..Default::default()
+        };
+        assert_eq!(
+            codes(zengin.find_banks("^MIZUHO", &options).unwrap()),
+            vec!["0001"]
+        );
+
+        let options = SearchOptions {
+            fields: vec![Field::Code],
+            ..Default::default()
+        };
+        assert!(zengin.find_banks("^00", &options).unwrap().len() >= 4);
+        assert!(zengin.find_banks("(", &options).is_err());
+
+        let normalized = SearchOptions {
+            normalize_kana: true,
+            ..options.clone()
+        };
+        for pattern in [r"\D", r"\W", r"^\d{4}$", r"\B\d"] {
+            assert_eq!(
+                codes(zengin.find_banks(pattern, &normalized).unwrap()),
+                codes(zengin.find_banks(pattern, &options).unwrap()),
+                "{pattern}"
+            );
+        }
+        assert!(zengin.find_banks(r"\D", &normalized).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_normalize_pattern() {
+        assert_eq!(normalize_pattern(r"^ミズホ\D\W\S"), r"^みずほ\D\W\S");
+        assert_eq!(normalize_pattern("[ア-ンＡ]+カー"), "[あ-んa]+かあ");
+        assert_eq!(normalize_pattern("㈱*"), r"(?:\x{28}株\x{29})*");
+        assert_eq!(normalize_pattern("(?x) ｱ"), "(?x) あ");
+        assert_eq!(normalize_pattern("("), "(");
+    }
+
+    #[test]
+    fn test_find_with_pagination() {
+        let zengin = crate::fixture();
+        let codes =
+            |banks: Vec<&Bank>| banks.iter().map(|b| b.code.to_string()).collect::<Vec<_>>();
+        let page = |offset, limit| SearchOptions {
+            limit,
+            offset,
+            fields: vec![Field::Code],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            codes(zengin.find_banks("^00", &page(1, Some(2))).unwrap()),
+            vec!["0005", "0009"]
+        );
+        assert_eq!(
+            codes(zengin.find_banks("^00", &page(4, None)).unwrap()),
+            vec!["0033"]
+        );
+        assert!(zengin
+            .find_banks("^00", &page(10, Some(2)))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_find_branches_across_banks() {
+        let zengin = crate::fixture();
+        let codes = |branches: Vec<(&Bank, &Branch)>| {
+            branches
+                .iter()
+                .map(|(bank, branch)| format!("{} {}", bank.code, branch.code))
+                .collect::<Vec<_>>()
+        };
+
+        let options = SearchOptions::default();
+        assert_eq!(
+            codes(zengin.find_branches("新宿", &options).unwrap()),
+            vec!["0001 110", "0005 330", "0009 221"]
+        );
+        let page = SearchOptions {
+            limit: Some(1),
+            offset: 1,
+            sort: SortOrder::Name,
+            ..Default::default()
+        };
+        assert_eq!(
+            codes(zengin.find_branches("新宿", &page).unwrap()),
+            vec!["0005 330"]
+        );
+        assert!(zengin.find_branches("(", &options).is_err());
+    }
+
+    #[test]
+    fn test_find_banks_ranked() {
+        let zengin = crate::fixture();
+        let ranked = zengin.find_banks_ranked("みずほ");
+        let codes: Vec<_> = ranked.iter().map(|(_, b)| b.code.to_string()).collect();
+        assert_eq!(codes, vec!["0001", "0289"]);
+        assert_eq!(ranked[0].0, 3.0);
+        assert!(ranked[1].0 > 2.0 && ranked[1].0 < 3.0);
+
+        let ranked = zengin.find_banks_ranked("シンタク");
+        assert_eq!(ranked[0].1.code.to_string(), "0289");
+        assert!(ranked[0].0 > 1.0 && ranked[0].0 < 2.0);
+
+        assert!(zengin.find_banks_ranked("").is_empty());
+    }
+
+    #[test]
+    fn test_suggest_banks() {
+        let zengin = Zengin::from_combined_json(
+            r#"{
+                "0001":{"code":"0001","name":"みずほ","kana":"ミズホ","hira":"みずほ","roma":"mizuho"},
+                "0005":{"code":"0005","name":"三菱ＵＦＪ","kana":"ミツビシユ－エフジエイ","hira":"みつびしゆ－えふじえい","roma":"mitsubishiyu-efujiei"},
+                "0010":{"code":"0010","name":"りそな","kana":"リソナ","hira":"りそな","roma":"risona"}
+            }"#,
+        )
+        .unwrap();
+        let codes =
+            |banks: Vec<&Bank>| banks.iter().map(|b| b.code.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(codes(zengin.suggest_banks("0009", 2)), vec!["0005", "0001"]);
+        assert_eq!(codes(zengin.suggest_banks("11", 1)), vec!["0010"]);
+        assert_eq!(
+            codes(zengin.suggest_banks("りそなぎんこう", 1)),
+            vec!["0010"]
+        );
+        assert!(zengin.suggest_banks("0009", 0).is_empty());
+    }
+}
@@ -0,0 +1,191 @@
+//! A REST API over the dataset, for serving it as a microservice.
+//!
+//! [`router`] returns an axum router with these routes, which respond with
+//! JSON:
+//!
+//! * `GET /banks` - every bank in code order, without their branches.
+//! * `GET /banks/{code}` - a bank, or 404 if there is none with the code.
+//! * `GET /banks/{code}/branches` - the branches of a bank in code order.
+//! * `GET /search?q=` - the banks matching a query, most relevant first. With
+//!   `bank=`, the branches of that bank are searched instead.
+//!
+//! Banks and branches are objects with `code`, `name`, `kana`, `hira`, and
+//! `roma` fields, and errors are objects with an `error` field. The
+//! `zengin-server` binary serves the router over the embedded dataset.
+//!
+//! # Examples
+//! ```
+//! use std::sync::Arc;
+//! use zengin::{server, Zengin};
+//! let app = server::router(Arc::new(Zengin::new().unwrap()));
+//! let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
+//! axum::serve(listener, app).await.unwrap();
+//! ```
+
+use crate::{Record, Zengin};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A bank or branch in a response, without the branches of a bank.
+#[derive(Serialize)]
+struct Entry<'a> {
+    code: &'a str,
+    name: &'a str,
+    kana: &'a str,
+    hira: &'a str,
+    roma: &'a str,
+}
+
+impl<'a> Entry<'a> {
+    fn new(record: &'a impl Record) -> Self {
+        let [name, kana, hira, roma] = record.names();
+        Entry {
+            code: record.code(),
+            name,
+            kana,
+            hira,
+            roma,
+        }
+    }
+}
+
+/// The parameters of `GET /search`.
+#[derive(Deserialize)]
+struct SearchParams {
+    q: String,
+    bank: Option<String>,
+}
+
+/// Returns a 404 response with an error message.
+fn not_found(message: String) -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(serde_json::json!({ "error": message })),
+    )
+        .into_response()
+}
+
+/// Returns a router with the routes of the REST API over a dataset.
+///
+/// # Arguments
+///
+/// * `zengin` - The dataset, which can be shared with the rest of the
+///   application.
+///
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use zengin::{server, Zengin};
+/// let app = axum::Router::new().nest("/zengin", server::router(Arc::new(Zengin::new().unwrap())));
+/// ```
+pub fn router(zengin: Arc<Zengin>) -> Router {
+    Router::new()
+        .route("/banks", get(banks))
+        .route("/banks/{code}", get(bank))
+        .route("/banks/{code}/branches", get(branches))
+        .route("/search", get(search))
+        .with_state(zengin)
+}
+
+async fn banks(State(zengin): State<Arc<Zengin>>) -> Response {
+    let banks: Vec<_> = zengin.iter().map(|(_, bank)| Entry::new(bank)).collect();
+    Json(banks).into_response()
+}
+
+async fn bank(State(zengin): State<Arc<Zengin>>, Path(code): Path<String>) -> Response {
+    match zengin.get_bank(code.as_str()) {
+        Some(bank) => Json(Entry::new(bank)).into_response(),
+        None => not_found(format!("no bank with code {code:?}")),
+    }
+}
+
+async fn branches(State(zengin): State<Arc<Zengin>>, Path(code): Path<String>) -> Response {
+    match zengin.get_bank(code.as_str()) {
+        Some(bank) => {
+            let branches: Vec<_> = bank.iter().map(|(_, branch)| Entry::new(branch)).collect();
+            Json(branches).into_response()
+        }
+        None => not_found(format!("no bank with code {code:?}")),
+    }
+}
+
+async fn search(
+    State(zengin): State<Arc<Zengin>>,
+    Query(SearchParams { q, bank }): Query<SearchParams>,
+) -> Response {
+    let Some(code) = bank else {
+        let banks: Vec<_> = zengin
+            .find_banks_ranked(&q)
+            .into_iter()
+            .map(|(_, bank)| Entry::new(bank))
+            .collect();
+        return Json(banks).into_response();
+    };
+    match zengin.get_bank(code.as_str()) {
+        Some(bank) => {
+            let branches: Vec<_> = bank
+                .find_branches_ranked(&q)
+                .into_iter()
+                .map(|(_, branch)| Entry::new(branch))
+                .collect();
+            Json(branches).into_response()
+        }
+        None => not_found(format!("no bank with code {code:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use tower::ServiceExt;
+
+    /// Sends a GET request to the router, and returns the status and the
+    /// JSON body of the response.
+    fn get(app: &Router, uri: &str) -> (StatusCode, serde_json::Value) {
+        futures_executor::block_on(async {
+            let request = Request::get(uri).body(Body::empty()).unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            let status = response.status();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (status, serde_json::from_slice(&body).unwrap())
+        })
+    }
+
+    #[test]
+    fn test_router() {
+        let zengin = Arc::new(crate::fixture());
+        let app = router(zengin.clone());
+
+        let (status, banks) = get(&app, "/banks");
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(banks.as_array().unwrap().len(), zengin.all_banks().len());
+        assert_eq!(banks[0]["code"], "0001");
+        assert!(banks[0].get("branches").is_none());
+
+        let (status, bank) = get(&app, "/banks/0005");
+        assert_eq!(
+            (status, &bank["kana"]),
+            (StatusCode::OK, &"ミツビシユ－エフジエイ".into())
+        );
+        let (_, branches) = get(&app, "/banks/0001/branches");
+        assert_eq!(branches[2]["name"], "新宿");
+        let (status, error) = get(&app, "/banks/9999");
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(error["error"].is_string());
+
+        let (_, found) = get(&app, "/search?q=%E3%81%BF%E3%81%9A%E3%81%BB");
+        assert_eq!(found[0]["code"], "0001");
+        let (_, found) = get(&app, "/search?q=shinjuku&bank=0001");
+        assert_eq!(found[0]["code"], "110");
+    }
+}
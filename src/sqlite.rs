@@ -0,0 +1,91 @@
+//! Loading of the dataset into a SQLite database with rusqlite.
+
+use crate::{
+    export::{create_tables, SqlDialect},
+    Record, Zengin,
+};
+use rusqlite::{params, Connection};
+
+impl Zengin {
+    /// Loads the dataset into a SQLite database, so that it can be joined
+    /// with application data in SQL.
+    ///
+    /// This creates the `banks` and `branches` tables and their indexes if
+    /// they do not exist yet, with the schema of [`Zengin::to_sql`], and
+    /// inserts every bank and branch in a single transaction. Rows that are
+    /// already there are replaced, so the function can refresh a database
+    /// loaded from an older dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - The connection to the database, such as one from
+    ///   `Connection::open_in_memory`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a statement fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use rusqlite::Connection;
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// let mut conn = Connection::open_in_memory().unwrap();
+    /// zengin.to_sqlite(&mut conn).unwrap();
+    /// let name: String = conn
+    ///     .query_row("SELECT name FROM banks WHERE code = '0001'", [], |row| row.get(0))
+    ///     .unwrap();
+    /// ```
+    pub fn to_sqlite(&self, conn: &mut Connection) -> rusqlite::Result<()> {
+        let tx = conn.transaction()?;
+        tx.execute_batch(&create_tables(SqlDialect::Sqlite))?;
+        {
+            let mut insert_bank = tx.prepare(
+                "INSERT OR REPLACE INTO banks (code, name, kana, hira, roma)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            let mut insert_branch = tx.prepare(
+                "INSERT OR REPLACE INTO branches (bank_code, code, name, kana, hira, roma)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            for (code, bank) in self {
+                let [name, kana, hira, roma] = bank.names();
+                insert_bank.execute(params![code, name, kana, hira, roma])?;
+                for (branch_code, branch) in bank {
+                    let [name, kana, hira, roma] = branch.names();
+                    insert_branch.execute(params![code, branch_code, name, kana, hira, roma])?;
+                }
+            }
+        }
+        tx.commit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_sqlite() {
+        let zengin = crate::fixture();
+        let mut conn = Connection::open_in_memory().unwrap();
+        zengin.to_sqlite(&mut conn).unwrap();
+        // Loading twice replaces the rows instead of failing.
+        zengin.to_sqlite(&mut conn).unwrap();
+
+        let count: usize = conn
+            .query_row("SELECT COUNT(*) FROM banks", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, zengin.all_banks().len());
+        let (bank, branch): (String, String) = conn
+            .query_row(
+                "SELECT banks.name, branches.name FROM branches
+                 JOIN banks ON banks.code = branches.bank_code
+                 WHERE branches.bank_code = '0001' AND branches.code = '110'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((bank.as_str(), branch.as_str()), ("みずほ", "新宿"));
+    }
+}
@@ -0,0 +1,197 @@
+//! Seeding of PostgreSQL and MySQL databases with the dataset through sqlx.
+
+use crate::{Record, Zengin};
+use sqlx::{mysql::MySql, postgres::Postgres, Database, MySqlPool, PgPool, QueryBuilder};
+
+/// The number of rows per `INSERT` statement, which keeps the number of bind
+/// parameters well within the limits of both databases.
+const ROWS_PER_INSERT: usize = 1000;
+
+/// The columns of a table that an upsert overwrites.
+const NAME_COLUMNS: [&str; 4] = ["name", "kana", "hira", "roma"];
+
+/// A row of the `banks` or `branches` table: the bank code, the branch code
+/// for branches, and the names.
+type Row<'a> = (&'a str, Option<&'a str>, [&'a str; 4]);
+
+impl Zengin {
+    /// Returns the rows of the `banks` table, then those of the `branches`
+    /// table, in code order.
+    fn rows(&self) -> (Vec<Row<'_>>, Vec<Row<'_>>) {
+        let banks = self
+            .iter()
+            .map(|(code, bank)| (code, None, bank.names()))
+            .collect();
+        let branches = self
+            .iter()
+            .flat_map(|(code, bank)| {
+                bank.iter()
+                    .map(move |(branch_code, branch)| (code, Some(branch_code), branch.names()))
+            })
+            .collect();
+        (banks, branches)
+    }
+
+    /// Upserts the dataset into the `banks` and `branches` tables of a
+    /// PostgreSQL database, so that a mirror of the dataset follows the
+    /// version of this crate.
+    ///
+    /// The tables must exist with the schema of [`Zengin::to_sql`]. Every
+    /// bank and branch is inserted in a single transaction, and the names of
+    /// rows that are already there are updated. Rows of banks and branches
+    /// that are no longer in the dataset are kept.
+    ///
+    /// sqlx needs a runtime feature such as `runtime-tokio` enabled by the
+    /// application.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The pool of connections to the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a statement fails, in which case nothing is
+    /// changed.
+    ///
+    /// # Examples
+    /// ```
+    /// use sqlx::PgPool;
+    /// use zengin::Zengin;
+    /// let pool = PgPool::connect("postgres://localhost/app").await.unwrap();
+    /// Zengin::new().unwrap().seed_postgres(&pool).await.unwrap();
+    /// ```
+    pub async fn seed_postgres(&self, pool: &PgPool) -> sqlx::Result<()> {
+        let (banks, branches) = self.rows();
+        let mut tx = pool.begin().await?;
+        for chunk in banks.chunks(ROWS_PER_INSERT) {
+            upsert::<Postgres>(chunk, postgres_conflict("code"))
+                .build()
+                .execute(&mut *tx)
+                .await?;
+        }
+        for chunk in branches.chunks(ROWS_PER_INSERT) {
+            upsert::<Postgres>(chunk, postgres_conflict("bank_code, code"))
+                .build()
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Upserts the dataset into the `banks` and `branches` tables of a MySQL
+    /// database, so that a mirror of the dataset follows the version of this
+    /// crate.
+    ///
+    /// This behaves as [`Zengin::seed_postgres`] does.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The pool of connections to the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a statement fails, in which case nothing is
+    /// changed.
+    ///
+    /// # Examples
+    /// ```
+    /// use sqlx::MySqlPool;
+    /// use zengin::Zengin;
+    /// let pool = MySqlPool::connect("mysql://localhost/app").await.unwrap();
+    /// Zengin::new().unwrap().seed_mysql(&pool).await.unwrap();
+    /// ```
+    pub async fn seed_mysql(&self, pool: &MySqlPool) -> sqlx::Result<()> {
+        let (banks, branches) = self.rows();
+        let mut tx = pool.begin().await?;
+        for chunk in banks
+            .chunks(ROWS_PER_INSERT)
+            .chain(branches.chunks(ROWS_PER_INSERT))
+        {
+            upsert::<MySql>(chunk, mysql_conflict())
+                .build()
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await
+    }
+}
+
+/// Returns the `ON CONFLICT` clause of PostgreSQL that updates the names of
+/// a row with the given primary key.
+fn postgres_conflict(key: &str) -> String {
+    let updates: Vec<_> = NAME_COLUMNS
+        .iter()
+        .map(|column| format!("{column} = EXCLUDED.{column}"))
+        .collect();
+    format!(" ON CONFLICT ({key}) DO UPDATE SET {}", updates.join(", "))
+}
+
+/// Returns the `ON DUPLICATE KEY` clause of MySQL that updates the names of
+/// a row.
+fn mysql_conflict() -> String {
+    let updates: Vec<_> = NAME_COLUMNS
+        .iter()
+        .map(|column| format!("{column} = VALUES({column})"))
+        .collect();
+    format!(" ON DUPLICATE KEY UPDATE {}", updates.join(", "))
+}
+
+/// Returns an `INSERT` statement of rows of a single table, which ends with
+/// `conflict`.
+fn upsert<'a, DB>(rows: &[Row<'a>], conflict: String) -> QueryBuilder<'a, DB>
+where
+    DB: Database,
+    &'a str: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+{
+    let mut builder = match rows.first() {
+        Some((_, Some(_), _)) => {
+            QueryBuilder::new("INSERT INTO branches (bank_code, code, name, kana, hira, roma) ")
+        }
+        _ => QueryBuilder::new("INSERT INTO banks (code, name, kana, hira, roma) "),
+    };
+    builder.push_values(rows, |mut values, &(code, branch_code, names)| {
+        values.push_bind(code);
+        if let Some(branch_code) = branch_code {
+            values.push_bind(branch_code);
+        }
+        for name in names {
+            values.push_bind(name);
+        }
+    });
+    builder.push(conflict);
+    builder
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upsert() {
+        let zengin = crate::fixture();
+        let (banks, branches) = zengin.rows();
+        assert_eq!(banks.len(), zengin.all_banks().len());
+        assert!(branches.contains(&(
+            "0001",
+            Some("110"),
+            ["新宿", "シンジユク", "しんじゆく", "shinjiyuku"]
+        )));
+
+        let sql = upsert::<Postgres>(&banks[..2], postgres_conflict("code")).into_sql();
+        assert_eq!(
+            sql,
+            "INSERT INTO banks (code, name, kana, hira, roma) \
+             VALUES ($1, $2, $3, $4, $5), ($6, $7, $8, $9, $10) \
+             ON CONFLICT (code) DO UPDATE SET name = EXCLUDED.name, kana = EXCLUDED.kana, \
+             hira = EXCLUDED.hira, roma = EXCLUDED.roma"
+        );
+        let sql = upsert::<MySql>(&branches[..1], mysql_conflict()).into_sql();
+        assert_eq!(
+            sql,
+            "INSERT INTO branches (bank_code, code, name, kana, hira, roma) \
+             VALUES (?, ?, ?, ?, ?, ?) \
+             ON DUPLICATE KEY UPDATE name = VALUES(name), kana = VALUES(kana), \
+             hira = VALUES(hira), roma = VALUES(roma)"
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! SWIFT/BIC codes of Japanese banks.
+//!
+//! The Zengin dataset does not carry SWIFT/BIC codes, so this module embeds a
+//! table of the codes of major banks, as published by the banks themselves.
+//! Banks missing from the table have no code here, which does not mean they
+//! have none at all.
+
+use crate::{Bank, Zengin};
+
+/// Pairs of bank codes and the 8-character BICs of their head offices, in
+/// code order.
+#[rustfmt::skip]
+const SWIFT_CODES: &[(&str, &str)] = &[
+    ("0001", "MHCBJPJT"), // みずほ
+    ("0005", "BOTKJPJT"), // 三菱ＵＦＪ
+    ("0009", "SMBCJPJT"), // 三井住友
+    ("0010", "DIWAJPJT"), // りそな
+    ("0017", "SAIBJPJT"), // 埼玉りそな
+    ("0033", "JNBKJPJT"), // ＰａｙＰａｙ
+    ("0036", "RAKTJPJT"), // 楽天
+    ("0038", "NTSSJPJT"), // 住信ＳＢＩネット
+    ("0117", "HKDBJPJT"), // 北海道
+    ("0130", "JOYOJPJT"), // 常陽
+    ("0134", "CHBAJPJT"), // 千葉
+    ("0138", "HAMAJPJT"), // 横浜
+    ("0149", "SHIZJPJT"), // 静岡
+    ("0177", "FKBKJPJT"), // 福岡
+    ("0288", "MTBCJPJT"), // 三菱ＵＦＪ信託
+    ("0294", "STBCJPJT"), // 三井住友信託
+    ("1000", "ZENBJPJT"), // 信金中央金庫
+    ("3008", "NOCUJPJT"), // 農林中金
+    ("9900", "JPPSJPJ1"), // ゆうちょ
+];
+
+impl Bank {
+    /// Returns the 8-character SWIFT/BIC code of the bank, if it is known.
+    ///
+    /// Only major banks are covered; see the [`swift`](crate::swift) module.
+    ///
+    /// # Examples
+    /// ```
+    /// if let Some(bic) = bank.swift_code() {
+    ///     println!("{} {}", bank.name, bic); // みずほ MHCBJPJT
+    /// }
+    /// ```
+    pub fn swift_code(&self) -> Option<&'static str> {
+        SWIFT_CODES
+            .binary_search_by_key(&self.code.as_str(), |&(code, _)| code)
+            .ok()
+            .map(|i| SWIFT_CODES[i].1)
+    }
+}
+
+impl Zengin {
+    /// Retrieves a bank by its SWIFT/BIC code.
+    ///
+    /// Both 8-character codes and 11-character codes with a branch suffix are
+    /// accepted, and letters are matched case-insensitively. Only the banks
+    /// covered by [`Bank::swift_code`] can be found.
+    ///
+    /// # Arguments
+    ///
+    /// * `bic` - A string slice that holds the SWIFT/BIC code.
+    ///
+    /// # Examples
+    /// ```
+    /// use zengin::Zengin;
+    /// let zengin = Zengin::new().unwrap();
+    /// if let Some(bank) = zengin.get_bank_by_swift_code("BOTKJPJTXXX") {
+    ///     println!("{} {}", bank.code, bank.name); // 0005 三菱ＵＦＪ
+    /// }
+    /// ```
+    pub fn get_bank_by_swift_code(&self, bic: &str) -> Option<&Bank> {
+        let bic = bic.trim();
+        if bic.len() != 8 && bic.len() != 11 {
+            return None;
+        }
+        let (code, _) = SWIFT_CODES
+            .iter()
+            .find(|(_, swift)| bic.get(..8).is_some_and(|b| b.eq_ignore_ascii_case(swift)))?;
+        self.get_bank(*code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swift_codes_are_sorted() {
+        assert!(SWIFT_CODES.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(SWIFT_CODES.iter().all(|(_, bic)| bic.len() == 8));
+    }
+
+    #[test]
+    fn test_swift_code() {
+        let zengin = crate::fixture();
+        assert_eq!(zengin["0005"].swift_code(), Some("BOTKJPJT"));
+        assert_eq!(zengin["1310"].swift_code(), None);
+        let code = |bic| {
+            zengin
+                .get_bank_by_swift_code(bic)
+                .map(|bank| bank.code.as_str())
+        };
+        assert_eq!(code("jppsjpj1"), Some("9900"));
+        assert_eq!(code("MHCBJPJTXXX"), Some("0001"));
+        assert_eq!(code("MHCBJPJTX"), None);
+        assert_eq!(code("RAKTJPJT"), None);
+    }
+}
@@ -0,0 +1,230 @@
+//! Conversion between the account numbers of ゆうちょ銀行 and Zengin codes.
+//!
+//! ゆうちょ銀行 (Japan Post Bank, bank code `9900`) identifies accounts by a
+//! five-digit 記号 and an up to eight-digit 番号, such as `10140-12345671`,
+//! while transfers from other banks need a branch code, an account type, and
+//! a seven-digit account number. The official rules map one onto the other:
+//!
+//! * 通常貯金 accounts have a 記号 of the form `1XXY0` and an eight-digit 番号
+//!   ending in `1`. The branch code is `XX8`, the account is an ordinary one,
+//!   and the account number is the 番号 without its last digit.
+//! * 振替口座 accounts have a 記号 of the form `0XXYY` and an up to six-digit
+//!   番号. The branch code is `XX9`, the account is a checking one, and the
+//!   account number is the 番号 zero-padded to seven digits.
+//!
+//! The `Y` digits of the 記号 do not carry over into the Zengin codes, so
+//! [`from_zengin`] can only restore the first three digits of the 記号.
+
+use crate::{account::AccountType, BranchCode};
+use std::{error::Error, fmt};
+
+/// An error returned when a ゆうちょ銀行 account cannot be converted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum YuchoError {
+    /// The 記号 is not five digits starting with `0` or `1`.
+    InvalidSymbol,
+    /// The 番号 does not fit the 記号, such as a 通常貯金 番号 not ending in `1`.
+    InvalidNumber,
+    /// The branch code does not end in `8` or `9`, so it is not one that
+    /// ゆうちょ銀行 accounts convert into.
+    InvalidBranchCode,
+    /// The account number is not seven digits, or too long or all zeros for
+    /// a 振替口座.
+    InvalidAccountNumber,
+}
+
+impl fmt::Display for YuchoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            YuchoError::InvalidSymbol => "invalid symbol: expected 5 digits starting with 0 or 1",
+            YuchoError::InvalidNumber => "invalid number for the symbol",
+            YuchoError::InvalidBranchCode => "invalid branch code: expected one ending in 8 or 9",
+            YuchoError::InvalidAccountNumber => "invalid account number for the branch code",
+        })
+    }
+}
+
+impl Error for YuchoError {}
+
+/// A ゆうちょ銀行 account as identified in transfers from other banks.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ZenginAccount {
+    /// The branch code, such as `018`.
+    pub branch_code: BranchCode,
+    /// The account type, [`AccountType::Ordinary`] for 通常貯金 and
+    /// [`AccountType::Checking`] for 振替口座.
+    pub account_type: AccountType,
+    /// The seven-digit account number.
+    pub number: String,
+}
+
+/// A ゆうちょ銀行 account as identified by its 記号 and 番号, as far as the
+/// Zengin codes determine them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SymbolNumber {
+    /// The first three digits of the 記号, such as `101`.
+    pub symbol_prefix: String,
+    /// The 番号, eight digits for 通常貯金 and up to six digits without
+    /// leading zeros for 振替口座.
+    pub number: String,
+}
+
+fn is_digits(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Converts a 記号 and 番号 into the branch code, account type, and account
+/// number used by transfers from other banks.
+///
+/// # Arguments
+///
+/// * `symbol` - A string slice that holds the five-digit 記号.
+/// * `number` - A string slice that holds the 番号. Leading zeros may be left
+///   out.
+///
+/// # Errors
+///
+/// Returns an error if the 記号 is malformed, or the 番号 does not fit it. A
+/// 番号 of 振替口座 that is all zeros is rejected, as [`from_zengin`] does.
+///
+/// # Examples
+/// ```
+/// use zengin::yucho::to_zengin;
+/// let account = to_zengin("10140", "12345671").unwrap();
+/// assert_eq!(account.branch_code.as_str(), "018");
+/// assert_eq!(account.number, "1234567");
+/// ```
+pub fn to_zengin(symbol: &str, number: &str) -> Result<ZenginAccount, YuchoError> {
+    let (symbol, number) = (symbol.trim(), number.trim());
+    if symbol.len() != 5 || !is_digits(symbol) {
+        return Err(YuchoError::InvalidSymbol);
+    }
+    if !is_digits(number) {
+        return Err(YuchoError::InvalidNumber);
+    }
+    let (suffix, account_type, number) = match &symbol[..1] {
+        "1" if number.len() <= 8 => {
+            let number = format!("{number:0>8}");
+            match number.strip_suffix('1') {
+                Some(number) => ('8', AccountType::Ordinary, number.to_string()),
+                None => return Err(YuchoError::InvalidNumber),
+            }
+        }
+        "0" if number.len() <= 6 && number.bytes().any(|b| b != b'0') => {
+            ('9', AccountType::Checking, format!("{number:0>7}"))
+        }
+        "0" | "1" => return Err(YuchoError::InvalidNumber),
+        _ => return Err(YuchoError::InvalidSymbol),
+    };
+    let branch_code = format!("{}{suffix}", &symbol[1..3])
+        .parse()
+        .expect("two digits and a suffix form a branch code");
+    Ok(ZenginAccount {
+        branch_code,
+        account_type,
+        number,
+    })
+}
+
+/// Converts a branch code and account number of ゆうちょ銀行 back into the
+/// 記号 and 番号.
+///
+/// Only the first three digits of the 記号 can be restored, since the Zengin
+/// codes do not carry the others; compare them with the 記号 a user gives, or
+/// convert the user's 記号 and 番号 with [`to_zengin`] instead.
+///
+/// # Arguments
+///
+/// * `branch_code` - The branch code, ending in `8` for 通常貯金 and `9` for
+///   振替口座.
+/// * `number` - A string slice that holds the seven-digit account number.
+///
+/// # Errors
+///
+/// Returns an error if the branch code is not one of ゆうちょ銀行, or the
+/// account number does not fit it.
+///
+/// # Examples
+/// ```
+/// use zengin::yucho::from_zengin;
+/// let symbol = from_zengin("018".parse().unwrap(), "1234567").unwrap();
+/// assert_eq!(symbol.symbol_prefix, "101");
+/// assert_eq!(symbol.number, "12345671");
+/// ```
+pub fn from_zengin(branch_code: BranchCode, number: &str) -> Result<SymbolNumber, YuchoError> {
+    let number = number.trim();
+    if number.len() != 7 || !is_digits(number) {
+        return Err(YuchoError::InvalidAccountNumber);
+    }
+    let code = branch_code.as_str();
+    let (kind, number) = match &code[2..] {
+        "8" => ('1', format!("{number}1")),
+        "9" => match number.strip_prefix('0').map(|n| n.trim_start_matches('0')) {
+            Some(number) if !number.is_empty() => ('0', number.to_string()),
+            _ => return Err(YuchoError::InvalidAccountNumber),
+        },
+        _ => return Err(YuchoError::InvalidBranchCode),
+    };
+    Ok(SymbolNumber {
+        symbol_prefix: format!("{kind}{}", &code[..2]),
+        number,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_zengin() {
+        let account = to_zengin("10140", "12345671").unwrap();
+        assert_eq!(account.branch_code.as_str(), "018");
+        assert_eq!(account.account_type, AccountType::Ordinary);
+        assert_eq!(account.number, "1234567");
+
+        let account = to_zengin("00120", "4567").unwrap();
+        assert_eq!(account.branch_code.as_str(), "019");
+        assert_eq!(account.account_type, AccountType::Checking);
+        assert_eq!(account.number, "0004567");
+
+        assert_eq!(to_zengin("20140", "1"), Err(YuchoError::InvalidSymbol));
+        assert_eq!(to_zengin("1014", "1"), Err(YuchoError::InvalidSymbol));
+        assert_eq!(
+            to_zengin("10140", "12345678"),
+            Err(YuchoError::InvalidNumber)
+        );
+        assert_eq!(
+            to_zengin("00120", "1234567"),
+            Err(YuchoError::InvalidNumber)
+        );
+        assert_eq!(to_zengin("00120", "0"), Err(YuchoError::InvalidNumber));
+        assert_eq!(to_zengin("00120", "000000"), Err(YuchoError::InvalidNumber));
+    }
+
+    #[test]
+    fn test_from_zengin() {
+        let symbol = from_zengin("018".parse().unwrap(), "1234567").unwrap();
+        assert_eq!(symbol.symbol_prefix, "101");
+        assert_eq!(symbol.number, "12345671");
+
+        let symbol = from_zengin("019".parse().unwrap(), "0004567").unwrap();
+        assert_eq!(symbol.symbol_prefix, "001");
+        assert_eq!(symbol.number, "4567");
+
+        let account = to_zengin("00120", &symbol.number).unwrap();
+        assert_eq!(account.number, "0004567");
+        assert_eq!(
+            from_zengin("001".parse().unwrap(), "1234567"),
+            Err(YuchoError::InvalidBranchCode)
+        );
+        assert_eq!(
+            from_zengin("019".parse().unwrap(), "1234567"),
+            Err(YuchoError::InvalidAccountNumber)
+        );
+        assert_eq!(
+            from_zengin("019".parse().unwrap(), "0000000"),
+            Err(YuchoError::InvalidAccountNumber)
+        );
+    }
+}
@@ -0,0 +1,62 @@
+CREATE TABLE IF NOT EXISTS banks (
+  code CHAR(4) NOT NULL PRIMARY KEY,
+  name TEXT NOT NULL,
+  kana TEXT NOT NULL,
+  hira TEXT NOT NULL,
+  roma TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS branches (
+  bank_code CHAR(4) NOT NULL REFERENCES banks (code),
+  code CHAR(3) NOT NULL,
+  name TEXT NOT NULL,
+  kana TEXT NOT NULL,
+  hira TEXT NOT NULL,
+  roma TEXT NOT NULL,
+  PRIMARY KEY (bank_code, code)
+);
+CREATE INDEX IF NOT EXISTS branches_name ON branches (name);
+CREATE INDEX IF NOT EXISTS branches_kana ON branches (kana);
+BEGIN;
+INSERT INTO banks (code, name, kana, hira, roma) VALUES
+('0001', 'みずほ', 'ミズホ', 'みずほ', 'mizuho'),
+('0005', '三菱ＵＦＪ', 'ミツビシユ－エフジエイ', 'みつびしゆ－えふじえい', 'mitsubishiyu-efujiei'),
+('0009', '三井住友', 'ミツイスミトモ', 'みついすみとも', 'mitsuisumitomo'),
+('0010', 'りそな', 'リソナ', 'りそな', 'risona'),
+('0033', 'ＰａｙＰａｙ', 'ペイペイ', 'ぺいぺい', 'peipei'),
+('0117', '北海道', 'ホツカイドウ', 'ほつかいどう', 'hotsukaidou'),
+('0289', 'みずほ信託', 'ミズホシンタク', 'みずほしんたく', 'mizuhoshintaku'),
+('1000', '信金中央金庫', 'シンキンチユウオウキンコ', 'しんきんちゆうおうきんこ', 'shinkinchiyuuoukinko'),
+('1310', '朝日信用金庫', 'アサヒシンキン', 'あさひしんきん', 'asahishinkin'),
+('2010', '全国信用協同組合連合会', 'ゼンシンキヨウレン', 'ぜんしんきようれん', 'zenshinkiyouren'),
+('2950', '労働金庫連合会', 'ロウキンレン', 'ろうきんれん', 'roukinren'),
+('3008', '農林中金', 'ノウリンチユウキン', 'のうりんちゆうきん', 'nourinchiyuukin'),
+('9900', 'ゆうちょ', 'ユウチヨ', 'ゆうちよ', 'yuuchiyo')
+ON CONFLICT DO NOTHING;
+INSERT INTO branches (bank_code, code, name, kana, hira, roma) VALUES
+('0001', '001', '東京営業部', 'トウキヨウ', 'とうきよう', 'toukiyou'),
+('0001', '004', '丸の内中央', 'マルノウチチユウオウ', 'まるのうちちゆうおう', 'marunouchichiyuuou'),
+('0001', '110', '新宿', 'シンジユク', 'しんじゆく', 'shinjiyuku'),
+('0001', '251', '渋谷', 'シブヤ', 'しぶや', 'shibuya'),
+('0005', '001', '本店', 'ホンテン', 'ほんてん', 'honten'),
+('0005', '135', '渋谷', 'シブヤ', 'しぶや', 'shibuya'),
+('0005', '330', '新宿', 'シンジユク', 'しんじゆく', 'shinjiyuku'),
+('0005', '869', '大阪営業部', 'オオサカ', 'おおさか', 'oosaka'),
+('0009', '001', '本店営業部', 'ホンテン', 'ほんてん', 'honten'),
+('0009', '221', '新宿西口', 'シンジユクニシグチ', 'しんじゆくにしぐち', 'shinjiyukunishiguchi'),
+('0009', '625', '駅前出張所', 'エキマエ', 'えきまえ', 'ekimae'),
+('0010', '001', '東京営業部', 'トウキヨウ', 'とうきよう', 'toukiyou'),
+('0033', '001', 'はやぶさ', 'ハヤブサ', 'はやぶさ', 'hayabusa'),
+('0033', '002', 'すずめ', 'スズメ', 'すずめ', 'suzume'),
+('0117', '101', '本店営業部', 'ホンテン', 'ほんてん', 'honten'),
+('0289', '001', '本店', 'ホンテン', 'ほんてん', 'honten'),
+('1000', '001', '本店', 'ホンテン', 'ほんてん', 'honten'),
+('1310', '001', '本店営業部', 'ホンテン', 'ほんてん', 'honten'),
+('1310', '012', '駅前', 'エキマエ', 'えきまえ', 'ekimae'),
+('2010', '001', '本店', 'ホンテン', 'ほんてん', 'honten'),
+('2950', '001', '本店', 'ホンテン', 'ほんてん', 'honten'),
+('3008', '001', '本店', 'ホンテン', 'ほんてん', 'honten'),
+('9900', '018', '〇一八', 'ゼロイチハチ', 'ぜろいちはち', 'zeroichihachi'),
+('9900', '019', '〇一九', 'ゼロイチキユウ', 'ぜろいちきゆう', 'zeroichikiyuu'),
+('9900', '408', '四〇八', 'ヨンゼロハチ', 'よんぜろはち', 'yonzerohachi')
+ON CONFLICT DO NOTHING;
+COMMIT;